@@ -0,0 +1,123 @@
+//! The point operations per curve and coordinate system, for tracking
+//! optimizations of the arithmetic.
+//!
+//! The benchmarks are named by operation, coordinate system and curve, e.g.
+//! `add/JacobianCoordinates/Secp256k1`. Run with `cargo bench`, or e.g.
+//! `cargo bench --bench curve_operations -- ^add/` for a single operation.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ecc_calc::ecc::prime::curves::{ECCurve, ECCurveCalculation, Secp256k1, Secp256r1};
+use ecc_calc::ecc::prime::points::{
+   AffineCoordinates,
+   ChudnovskyJacobianCoordinates,
+   CompleteProjectiveCoordinates,
+   JacobianCoordinates,
+   ModifiedJacobianCoordinates,
+   PointCalculation,
+   PointFrom,
+   StandardProjectiveCoordinates,
+};
+use ecc_calc::ecc::ECCValue;
+use num::BigInt;
+
+/// A scalar with about as many bits as `n`.
+fn scalar<C: ECCurve>(curve: &C) -> BigInt { curve.n() - BigInt::from(0x1234) }
+
+/// `add`, `mixed_add`, `double`, `multiply` and the conversions from and to
+/// affine coordinates in the projective coordinates `P`.
+///
+/// The operands of `add` and `double` are the results of calculations, with
+/// `z != 1`, while `mixed_add` adds a point fresh from affine coordinates.
+fn projective<C, P>(c: &mut Criterion, curve: &C, coordinates: &str)
+where
+   C: ECCurveCalculation<P>,
+   P: PointCalculation<C, Output = P> + PointFrom<AffineCoordinates>,
+   AffineCoordinates: PointFrom<P>,
+{
+   let id = |operation: &str| format!("{}/{}/{}", operation, coordinates, curve.name());
+   let point_g = P::convert_from(&curve.base_point(), curve).unwrap_finite();
+   let point_2g = curve.double_point(&point_g);
+   let point_3g = curve.add_points(&point_2g, &point_g);
+   let affine = AffineCoordinates::convert_from(&point_3g, curve).unwrap_finite();
+   let k = scalar(curve);
+
+   c.bench_function(&id("add"), |b| {
+      b.iter(|| curve.add_points(black_box(&point_2g), black_box(&point_3g)))
+   });
+   c.bench_function(&id("mixed_add"), |b| {
+      b.iter(|| curve.add_points(black_box(&point_2g), black_box(&point_g)))
+   });
+   c.bench_function(&id("double"), |b| b.iter(|| curve.double_point(black_box(&point_3g))));
+   c.bench_function(&id("multiply"), |b| {
+      b.iter(|| curve.multipy_point(black_box(&point_g), black_box(k.clone())))
+   });
+   c.bench_function(&id("to_affine"), |b| {
+      b.iter(|| AffineCoordinates::convert_from(black_box(&point_3g), curve))
+   });
+   c.bench_function(&id("from_affine"), |b| {
+      b.iter(|| P::convert_from(black_box(&affine), curve))
+   });
+}
+
+/// The operations in affine coordinates, which have no mixed addition and
+/// nothing to convert.
+fn affine<C>(c: &mut Criterion, curve: &C)
+where
+   C: ECCurveCalculation<AffineCoordinates> + ECCurveCalculation<JacobianCoordinates>,
+{
+   let id = |operation: &str| format!("{}/AffineCoordinates/{}", operation, curve.name());
+   let point_g = curve.base_point();
+   let jacobian = JacobianCoordinates::convert_from(&point_g, curve).unwrap_finite();
+   let point_2g = curve.double_point(&jacobian);
+   let point_2g = AffineCoordinates::convert_from(&point_2g, curve).unwrap_finite();
+   let k = scalar(curve);
+
+   c.bench_function(&id("add"), |b| {
+      b.iter(|| curve.add_points(black_box(&point_2g), black_box(&point_g)))
+   });
+   c.bench_function(&id("double"), |b| b.iter(|| curve.double_point(black_box(&point_2g))));
+   c.bench_function(&id("multiply"), |b| {
+      b.iter(|| curve.multipy_point(black_box(&point_g), black_box(k.clone())))
+   });
+}
+
+/// Parsing compressed and uncompressed SEC 1 public keys, the former with a
+/// square root.
+fn public_keys<C: ECCurve>(c: &mut Criterion, curve: &C) {
+   let point_g = ECCValue::from(curve.base_point());
+   let (compressed, uncompressed) = (point_g.to_compressed(), point_g.to_uncompressed());
+
+   c.bench_function(&format!("parse_uncompressed/{}", curve.name()), |b| {
+      b.iter(|| curve.parse_public_key(black_box(uncompressed.as_str())))
+   });
+   c.bench_function(&format!("parse_compressed/{}", curve.name()), |b| {
+      b.iter(|| curve.parse_public_key(black_box(compressed.as_str())))
+   });
+}
+
+fn every_coordinate_system<C>(c: &mut Criterion, curve: &C)
+where
+   C: ECCurveCalculation<AffineCoordinates>
+      + ECCurveCalculation<JacobianCoordinates>
+      + ECCurveCalculation<StandardProjectiveCoordinates>
+      + ECCurveCalculation<ChudnovskyJacobianCoordinates>
+      + ECCurveCalculation<ModifiedJacobianCoordinates>
+      + ECCurveCalculation<CompleteProjectiveCoordinates>,
+{
+   affine(c, curve);
+   projective::<_, JacobianCoordinates>(c, curve, "JacobianCoordinates");
+   projective::<_, StandardProjectiveCoordinates>(c, curve, "StandardProjectiveCoordinates");
+   projective::<_, ChudnovskyJacobianCoordinates>(c, curve, "ChudnovskyJacobianCoordinates");
+   projective::<_, ModifiedJacobianCoordinates>(c, curve, "ModifiedJacobianCoordinates");
+   projective::<_, CompleteProjectiveCoordinates>(c, curve, "CompleteProjectiveCoordinates");
+   public_keys(c, curve);
+}
+
+fn secp256k1(c: &mut Criterion) { every_coordinate_system(c, &Secp256k1::new()); }
+
+fn secp256r1(c: &mut Criterion) { every_coordinate_system(c, &Secp256r1::new()); }
+
+criterion_group!(benches, secp256k1, secp256r1);
+criterion_main!(benches);
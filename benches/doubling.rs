@@ -0,0 +1,75 @@
+//! Doubling-heavy workloads over every projective representation.
+//!
+//! Run with `cargo bench --bench doubling`.
+#![allow(non_snake_case)]
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ecc_calc::ecc::prime::curves::{ECCurve, ECCurveCalculation, Secp256r1};
+use ecc_calc::ecc::prime::points::affine::AffineCoordinates;
+use ecc_calc::ecc::prime::points::{
+   ChudnovskyJacobianCoordinates,
+   CompleteProjectiveCoordinates,
+   JacobianCoordinates,
+   ModifiedJacobianCoordinates,
+   PointCalculation,
+   PointFrom,
+   StandardProjectiveCoordinates,
+};
+use num::BigInt;
+
+/// Number of successive doublings, about the bit length of a scalar.
+const DOUBLINGS: usize = 256;
+
+fn base_point<P: PointFrom<AffineCoordinates>>(curve: &Secp256r1) -> P {
+   P::convert_from(&curve.base_point(), curve).unwrap_finite()
+}
+
+fn repeated_doubling<P>(c: &mut Criterion, name: &str)
+where
+   P: PointCalculation<Secp256r1, Output = P> + PointFrom<AffineCoordinates>,
+   Secp256r1: ECCurveCalculation<P>,
+{
+   let curve = Secp256r1::new();
+   let point_G = base_point::<P>(&curve);
+   c.bench_function(&format!("doubling_{}", name), |b| {
+      b.iter(|| {
+         let mut point_R = curve.double_point(&point_G);
+         for _ in 1..DOUBLINGS {
+            point_R = curve.double_point(&point_R);
+         }
+         point_R
+      })
+   });
+}
+
+fn multipication<P>(c: &mut Criterion, name: &str)
+where
+   P: PointCalculation<Secp256r1, Output = P> + PointFrom<AffineCoordinates>,
+   Secp256r1: ECCurveCalculation<P>,
+{
+   let curve = Secp256r1::new();
+   let point_G = base_point::<P>(&curve);
+   let k = &curve.n() - BigInt::from(0x1234);
+   c.bench_function(&format!("multipication_{}", name), |b| {
+      b.iter(|| curve.multipy_point(&point_G, k.clone()))
+   });
+}
+
+fn doubling(c: &mut Criterion) {
+   repeated_doubling::<JacobianCoordinates>(c, "jacobian");
+   repeated_doubling::<StandardProjectiveCoordinates>(c, "standard_projective");
+   repeated_doubling::<ChudnovskyJacobianCoordinates>(c, "chudnovsky_jacobian");
+   repeated_doubling::<CompleteProjectiveCoordinates>(c, "complete_projective");
+   repeated_doubling::<ModifiedJacobianCoordinates>(c, "modified_jacobian");
+}
+
+fn multipications(c: &mut Criterion) {
+   multipication::<JacobianCoordinates>(c, "jacobian");
+   multipication::<StandardProjectiveCoordinates>(c, "standard_projective");
+   multipication::<ChudnovskyJacobianCoordinates>(c, "chudnovsky_jacobian");
+   multipication::<CompleteProjectiveCoordinates>(c, "complete_projective");
+   multipication::<ModifiedJacobianCoordinates>(c, "modified_jacobian");
+}
+
+criterion_group!(benches, doubling, multipications);
+criterion_main!(benches);
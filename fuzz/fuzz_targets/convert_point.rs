@@ -0,0 +1,74 @@
+//! Conversions of any Jacobian point, the point at infinity included, to
+//! every other coordinate system and back: the point has to stay the same.
+//!
+//! The first byte selects the curve, the next 96 bytes are `x`, `y`, `z`,
+//! reduced modulo `p`. Points off the curve are converted all the same.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use num::bigint::Sign;
+use num::{BigInt, Integer};
+
+use ecc_calc::ecc::prime::curves::{ECCurve, Secp256k1, Secp256r1};
+use ecc_calc::ecc::prime::points::{
+   AffineCoordinates,
+   ChudnovskyJacobianCoordinates,
+   CompleteProjectiveCoordinates,
+   JacobianCoordinates,
+   ModifiedJacobianCoordinates,
+   PointFraction,
+   PointFrom,
+   StandardProjectiveCoordinates,
+};
+use ecc_calc::ecc::prime::ECCurvePoint;
+use ecc_calc::ecc::ECCValueRes;
+
+/// Convert `point` to `P` and back.
+fn round_trip<C, P>(curve: &C, point: &JacobianCoordinates)
+where
+   C: ECCurvePoint<JacobianCoordinates> + ECCurvePoint<P>,
+   P: PointFrom<JacobianCoordinates> + PointFraction,
+   JacobianCoordinates: PointFrom<P>,
+{
+   match P::convert_from(point, curve) {
+      ECCValueRes::Finite(converted) => {
+         assert!(curve.points_equal(point, &converted), "{:x} != {:x}", point, converted);
+         let back = JacobianCoordinates::convert_from(&converted, curve).unwrap_finite();
+         assert!(curve.points_equal(point, &back), "{:x} != {:x}", point, back);
+      },
+      ECCValueRes::Infinity => assert!(point.is_point_at_infinity()),
+   }
+}
+
+fn convert<C>(curve: &C, coordinates: &[u8])
+where
+   C: ECCurvePoint<JacobianCoordinates>
+      + ECCurvePoint<AffineCoordinates>
+      + ECCurvePoint<StandardProjectiveCoordinates>
+      + ECCurvePoint<ChudnovskyJacobianCoordinates>
+      + ECCurvePoint<ModifiedJacobianCoordinates>
+      + ECCurvePoint<CompleteProjectiveCoordinates>,
+{
+   let p = curve.p();
+   let mut values = coordinates
+      .chunks(32)
+      .map(|bytes| BigInt::from_bytes_be(Sign::Plus, bytes).mod_floor(&p));
+   let point = match (values.next(), values.next(), values.next()) {
+      (Some(x), Some(y), Some(z)) => JacobianCoordinates { x, y, z },
+      _ => return,
+   };
+
+   round_trip::<_, AffineCoordinates>(curve, &point);
+   round_trip::<_, StandardProjectiveCoordinates>(curve, &point);
+   round_trip::<_, ChudnovskyJacobianCoordinates>(curve, &point);
+   round_trip::<_, ModifiedJacobianCoordinates>(curve, &point);
+   round_trip::<_, CompleteProjectiveCoordinates>(curve, &point);
+}
+
+fuzz_target!(|data: &[u8]| {
+   match data.split_first() {
+      Some((&curve, coordinates)) if curve % 2 == 0 => convert(&Secp256k1::new(), coordinates),
+      Some((_, coordinates)) => convert(&Secp256r1::new(), coordinates),
+      None => {},
+   }
+});
@@ -0,0 +1,65 @@
+//! Scalar multiplication, differentially: `kP` in Jacobian and in standard
+//! projective coordinates have to be the same point.
+//!
+//! The first byte selects the curve and the sign of `k`, the next 32 bytes
+//! are the x coordinate of `P`, the base point if there is no point with it,
+//! and the rest is `k`, which may be longer than `n`.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use num::bigint::Sign;
+use num::BigInt;
+
+use ecc_calc::ecc::prime::curves::{ECCurve, ECCurveCalculation, Secp256k1, Secp256r1};
+use ecc_calc::ecc::prime::points::{
+   AffineCoordinates,
+   JacobianCoordinates,
+   PointFrom,
+   StandardProjectiveCoordinates,
+};
+use ecc_calc::ecc::prime::ECCurvePoint;
+
+fn multiply<C>(curve: &C, sign: Sign, data: &[u8])
+where
+   C: ECCurveCalculation<JacobianCoordinates>
+      + ECCurveCalculation<StandardProjectiveCoordinates>
+      + ECCurvePoint<JacobianCoordinates>,
+{
+   if data.len() < 32 {
+      return;
+   }
+   let (x, k) = data.split_at(32);
+   let x = BigInt::from_bytes_be(Sign::Plus, x);
+   let point = curve.decompress_point(&x, x.bits() % 2 == 1).unwrap_or_else(|| curve.base_point());
+   let k = BigInt::from_bytes_be(sign, k);
+
+   let jacobian = JacobianCoordinates::convert_from(&point, curve).unwrap_finite();
+   let projective = StandardProjectiveCoordinates::convert_from(&point, curve).unwrap_finite();
+   let jacobian = curve.multipy_point(&jacobian, k.clone());
+   let projective = curve.multipy_point(&projective, k.clone());
+   assert!(
+      curve.points_equal(&jacobian, &projective),
+      "{:x} * {:x}: {:x} != {:x}",
+      k,
+      point,
+      jacobian,
+      projective
+   );
+   assert_eq!(
+      AffineCoordinates::convert_from(&jacobian, curve),
+      AffineCoordinates::convert_from(&projective, curve)
+   );
+}
+
+fuzz_target!(|data: &[u8]| {
+   let (selector, data) = match data.split_first() {
+      Some((&selector, data)) => (selector, data),
+      None => return,
+   };
+   let sign = if selector & 2 == 0 { Sign::Plus } else { Sign::Minus };
+   if selector & 1 == 0 {
+      multiply(&Secp256k1::new(), sign, data);
+   } else {
+      multiply(&Secp256r1::new(), sign, data);
+   }
+});
@@ -0,0 +1,44 @@
+//! The `NewPoint::try_new` constructors on any strings and base: no panics.
+//!
+//! The first byte is the base, the rest are the coordinates separated by
+//! commas.
+#![no_main]
+
+use std::str;
+
+use libfuzzer_sys::fuzz_target;
+use ecc_calc::ecc::prime::points::affine::{AffineCoordinates, NewPoint as NewAffine};
+use ecc_calc::ecc::prime::points::jacobian::{JacobianCoordinates, NewPoint as NewJacobian};
+use ecc_calc::ecc::prime::points::standard_projective::{
+   NewPoint as NewStandardProjective,
+   StandardProjectiveCoordinates,
+};
+
+fuzz_target!(|data: &[u8]| {
+   let (base, coordinates) = match data.split_first() {
+      Some((&base, coordinates)) => (u32::from(base), coordinates),
+      None => return,
+   };
+   let coordinates: Vec<&str> = match str::from_utf8(coordinates) {
+      Ok(coordinates) => coordinates.split(',').collect(),
+      Err(_) => return,
+   };
+
+   match coordinates[..] {
+      [x, y] => {
+         if let Ok(point) = AffineCoordinates::try_new(x, y, base) {
+            let _ = format!("{} {:x}", point, point);
+         }
+         let _ = AffineCoordinates::try_new(x.to_string(), y.to_string(), base);
+      },
+      [x, y, z] => {
+         if let Ok(point) = JacobianCoordinates::try_new(x, y, z, base) {
+            let _ = format!("{} {:x}", point, point);
+         }
+         if let Ok(point) = StandardProjectiveCoordinates::try_new(x, y, z, base) {
+            let _ = format!("{} {:x}", point, point);
+         }
+      },
+      _ => {},
+   }
+});
@@ -0,0 +1,39 @@
+//! `parse_public_key` on any string: no panics, and whatever is accepted is
+//! encoded the same way again.
+#![no_main]
+
+use std::str;
+
+use libfuzzer_sys::fuzz_target;
+use ecc_calc::ecc::prime::curves::{ECCurve, Secp256k1, Secp256r1};
+use ecc_calc::ecc::prime::points::AffineCoordinates;
+use ecc_calc::ecc::prime::ECCurvePoint;
+use ecc_calc::ecc::ECCValue;
+
+fn parse<C: ECCurvePoint<AffineCoordinates>>(curve: &C, key: &str) {
+   let value = match curve.parse_public_key(key) {
+      Ok(value) => value,
+      Err(_) => return,
+   };
+   let key = key.to_lowercase();
+   match (key.get(..2), value) {
+      (Some("00"), ECCValue::Infinity) => {},
+      (Some("02"), value @ ECCValue::Finite { .. })
+      | (Some("03"), value @ ECCValue::Finite { .. }) => {
+         assert_eq!(value.to_compressed(), key);
+         if let ECCValue::Finite { x, y } = value {
+            assert!(curve.point_is_on_curve(&AffineCoordinates { x, y }));
+         }
+      },
+      // The point is not checked to be on the curve.
+      (Some("04"), value @ ECCValue::Finite { .. }) => assert_eq!(value.to_uncompressed(), key),
+      (prefix, value) => panic!("{:?} parsed as {:?}", prefix, value),
+   }
+}
+
+fuzz_target!(|data: &[u8]| {
+   if let Ok(key) = str::from_utf8(data) {
+      parse(&Secp256k1::new(), key);
+      parse(&Secp256r1::new(), key);
+   }
+});
@@ -0,0 +1,50 @@
+use std::{error, fmt};
+
+#[derive(Debug, Clone, PartialEq)]
+// Returned when a command line can not be parsed or executed.
+pub enum CliError {
+   /// The arguments do not form a command, with what was wrong.
+   Usage(String),
+   /// The argument is neither `G`, a SEC 1 encoded point nor `x,y`.
+   InvalidPoint(String),
+   /// The argument is neither a decimal nor a `0x` prefixed hex number.
+   InvalidScalar(String),
+   /// The result is the point at infinity, which has no coordinates.
+   Infinity,
+   /// A REPL expression can not be evaluated, with what was wrong.
+   Expression(String),
+   /// Reading or writing the REPL failed.
+   Io(String),
+}
+
+impl fmt::Display for CliError {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         CliError::Usage(ref message) => write!(f, "{}", message),
+         CliError::InvalidPoint(ref point) => write!(f, "Invalid point: {}", point),
+         CliError::InvalidScalar(ref scalar) => write!(f, "Invalid number: {}", scalar),
+         CliError::Infinity => write!(f, "Result is the point at infinity"),
+         CliError::Expression(ref message) => write!(f, "{}", message),
+         CliError::Io(ref message) => write!(f, "I/O error: {}", message),
+      }
+   }
+}
+
+// This is important for other errors to wrap this one.
+impl error::Error for CliError {
+   fn description(&self) -> &str {
+      match *self {
+         CliError::Usage(_) => "Invalid command line",
+         CliError::InvalidPoint(_) => "Invalid point",
+         CliError::InvalidScalar(_) => "Invalid number",
+         CliError::Infinity => "Result is the point at infinity",
+         CliError::Expression(_) => "Invalid expression",
+         CliError::Io(_) => "I/O error",
+      }
+   }
+
+   fn cause(&self) -> Option<&dyn error::Error> {
+      // Generic error, underlying cause isn't tracked.
+      None
+   }
+}
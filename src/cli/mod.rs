@@ -0,0 +1,201 @@
+//! The `ecc_calc` command line: one calculation per invocation, printed to
+//! stdout.
+
+use num::{BigInt, Num};
+
+use std::io::{self, IsTerminal};
+
+use ecc_calc::ecc::prime::curves::{ECCurve, ECCurveCalculation, Secp256k1, Secp256r1};
+use ecc_calc::ecc::prime::points::{
+   AffineCoordinates,
+   JacobianCoordinates,
+   PointFrom,
+   StandardProjectiveCoordinates,
+};
+use ecc_calc::ecc::prime::ECCurvePoint;
+use ecc_calc::ecc::ECCValue;
+
+pub mod errors;
+pub mod repl;
+
+use self::errors::CliError;
+
+pub const USAGE: &str = "\
+Usage: ecc_calc [--curve secp256k1|secp256r1] <command> [arguments]
+
+Commands:
+  mul <point> <k>                 k * point
+  add <point> <point>             the sum of the points
+  double <point>                  2 * point
+  compress <point>                the compressed SEC 1 encoding
+  decompress <key>                the uncompressed SEC 1 encoding
+  on-curve <point>                whether the point is on the curve
+  convert --to <coordinates> <point>
+                                  the point in affine, jacobian or projective
+                                  coordinates
+  repl                            an interactive shell, see help in it
+
+A point is G for the base point, a SEC 1 encoded public key (00, 02, 03 or
+04 prefixed hex) or x,y. Numbers are decimal, or hex with a 0x prefix.
+Points are printed uncompressed.";
+
+/// The curves the command line knows by name.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CurveName {
+   Secp256k1,
+   Secp256r1,
+}
+
+impl CurveName {
+   pub fn parse(name: &str) -> Result<CurveName, CliError> {
+      match name.to_lowercase().as_str() {
+         "secp256k1" => Ok(CurveName::Secp256k1),
+         "secp256r1" | "p-256" | "p256" => Ok(CurveName::Secp256r1),
+         _ => Err(CliError::Usage(format!("Unknown curve: {}", name))),
+      }
+   }
+}
+
+/// Run the command line `args`, without the program name, and return what is
+/// to be printed. The REPL prints as it goes and returns nothing.
+pub fn run(args: &[String]) -> Result<String, CliError> {
+   let mut curve = CurveName::Secp256k1;
+   let mut args = args;
+   while let Some(flag) = args.first() {
+      match flag.as_str() {
+         "--curve" | "-c" => {
+            let name = args
+               .get(1)
+               .ok_or_else(|| CliError::Usage("--curve needs a value".to_string()))?;
+            curve = CurveName::parse(name)?;
+            args = &args[2..];
+         },
+         "--help" | "-h" => return Ok(USAGE.to_string()),
+         _ => break,
+      }
+   }
+
+   if args.first().map(String::as_str) == Some("repl") {
+      let stdin = io::stdin();
+      let prompt = if stdin.is_terminal() { "> " } else { "" };
+      repl::Repl::new(curve).run(stdin.lock(), &mut io::stdout(), prompt)?;
+      return Ok(String::new());
+   }
+
+   match curve {
+      CurveName::Secp256k1 => execute(&Secp256k1::new(), args),
+      CurveName::Secp256r1 => execute(&Secp256r1::new(), args),
+   }
+}
+
+/// Execute a command, `args` starting with its name, on `curve`.
+pub fn execute<C>(curve: &C, args: &[String]) -> Result<String, CliError>
+where
+   C: ECCurveCalculation<JacobianCoordinates> + ECCurvePoint<AffineCoordinates>,
+{
+   let (command, args) = args
+      .split_first()
+      .ok_or_else(|| CliError::Usage(USAGE.to_string()))?;
+   let arity = |count: usize| {
+      if args.len() == count {
+         Ok(())
+      } else {
+         Err(CliError::Usage(format!("{} takes {} argument(s)", command, count)))
+      }
+   };
+
+   match command.as_str() {
+      "mul" => {
+         arity(2)?;
+         let point = parse_point(curve, &args[0])?;
+         let k = parse_scalar(&args[1])?;
+         Ok(format_point(curve, &curve.multipy_point(&point, k)))
+      },
+      "add" => {
+         arity(2)?;
+         let former = parse_point(curve, &args[0])?;
+         let latter = parse_point(curve, &args[1])?;
+         Ok(format_point(curve, &curve.add_points(&former, &latter)))
+      },
+      "double" => {
+         arity(1)?;
+         let point = parse_point(curve, &args[0])?;
+         Ok(format_point(curve, &curve.double_point(&point)))
+      },
+      "compress" => {
+         arity(1)?;
+         let point = parse_point(curve, &args[0])?;
+         Ok(to_ecc_value(curve, &point).to_compressed())
+      },
+      "decompress" => {
+         arity(1)?;
+         let point = curve
+            .parse_public_key(args[0].as_str())
+            .map_err(|_| CliError::InvalidPoint(args[0].clone()))?;
+         Ok(point.to_uncompressed())
+      },
+      "on-curve" => {
+         arity(1)?;
+         let on_curve = match to_ecc_value(curve, &parse_point(curve, &args[0])?) {
+            ECCValue::Finite { x, y } => curve.point_is_on_curve(&AffineCoordinates { x, y }),
+            ECCValue::Infinity => true,
+         };
+         Ok(on_curve.to_string())
+      },
+      "convert" => {
+         arity(3)?;
+         if args[0] != "--to" {
+            return Err(CliError::Usage("convert needs --to <coordinates>".to_string()));
+         }
+         let point = parse_point(curve, &args[2])?;
+         let affine = AffineCoordinates::convert_from(&point, curve);
+         match args[1].as_str() {
+            "affine" => Ok(format!("{:x}", affine.ok_or(CliError::Infinity)?)),
+            "jacobian" => Ok(format!("{:x}", point)),
+            "projective" => {
+               let point = StandardProjectiveCoordinates::from(ECCValue::from(affine));
+               Ok(format!("{:x}", point))
+            },
+            other => Err(CliError::Usage(format!("Unknown coordinates: {}", other))),
+         }
+      },
+      other => Err(CliError::Usage(format!("Unknown command: {}\n\n{}", other, USAGE))),
+   }
+}
+
+/// Parse `G`, a SEC 1 encoded point or `x,y`. The point is not checked to be
+/// on the curve.
+pub fn parse_point<C: ECCurve>(curve: &C, point: &str) -> Result<JacobianCoordinates, CliError> {
+   let invalid = || CliError::InvalidPoint(point.to_string());
+
+   let value = if point == "G" || point == "g" {
+      ECCValue::from(curve.base_point())
+   } else if let Some(comma) = point.find(',') {
+      let x = parse_scalar(point[..comma].trim()).map_err(|_| invalid())?;
+      let y = parse_scalar(point[comma + 1..].trim()).map_err(|_| invalid())?;
+      ECCValue::Finite { x, y }
+   } else {
+      curve.parse_public_key(point).map_err(|_| invalid())?
+   };
+   Ok(JacobianCoordinates::from(value))
+}
+
+/// Parse a decimal number, or a hex number with a `0x` prefix.
+pub fn parse_scalar(scalar: &str) -> Result<BigInt, CliError> {
+   let (digits, radix) = match scalar.strip_prefix("0x").or_else(|| scalar.strip_prefix("0X")) {
+      Some(hex) => (hex, 16),
+      None => (scalar, 10),
+   };
+   if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+      return Err(CliError::InvalidScalar(scalar.to_string()));
+   }
+   BigInt::from_str_radix(digits, radix).map_err(|_| CliError::InvalidScalar(scalar.to_string()))
+}
+
+fn to_ecc_value<C: ECCurve>(curve: &C, point: &JacobianCoordinates) -> ECCValue {
+   ECCValue::from(AffineCoordinates::convert_from(point, curve))
+}
+
+fn format_point<C: ECCurve>(curve: &C, point: &JacobianCoordinates) -> String {
+   to_ecc_value(curve, point).to_uncompressed()
+}
@@ -0,0 +1,460 @@
+//! An interactive shell for exploring a curve: bind points and scalars to
+//! names and evaluate expressions with `+`, `-` and `*`.
+//!
+//! ```text
+//! > P = G * 0x1234
+//! > Q = 2 * P - G
+//! > Q as compressed
+//! > (Q + P) as jacobian
+//! ```
+
+use num::{BigInt, Integer, Zero};
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use ecc_calc::ecc::prime::curves::{ECCurve, ECCurveCalculation, Secp256k1, Secp256r1};
+use ecc_calc::ecc::prime::points::{
+   AffineCoordinates,
+   JacobianCoordinates,
+   PointFrom,
+   StandardProjectiveCoordinates,
+};
+use ecc_calc::ecc::prime::ECCurvePoint;
+use ecc_calc::ecc::ECCValue;
+
+use super::errors::CliError;
+use super::{parse_scalar, CurveName};
+
+pub const HELP: &str = "\
+  <name> = <expression>           bind a point or scalar to a name
+  <expression> [as <format>]      print a point or scalar
+  curve [secp256k1|secp256r1]     print or select the curve, names are cleared
+  base [hex|dec]                  print or select how numbers are printed
+  vars                            print the bound names
+  help                            print this help
+  quit                            leave the shell
+
+Expressions combine G, names, numbers, (x, y) points and parentheses with
++, - and *. Scalars multiply points and are reduced modulo n there.
+Formats are affine, jacobian, projective, compressed and uncompressed.";
+
+/// A value of an expression.
+#[derive(Debug, Clone)]
+pub enum Value {
+   Point(JacobianCoordinates),
+   Scalar(BigInt),
+}
+
+/// What a line of input asks for.
+#[derive(Debug, PartialEq)]
+enum Statement<'a> {
+   Assign(&'a str, &'a str),
+   Print(&'a str, Option<&'a str>),
+   Curve(Option<&'a str>),
+   Base(Option<&'a str>),
+   Vars,
+   Help,
+   Quit,
+   Empty,
+}
+
+impl<'a> Statement<'a> {
+   fn parse(line: &'a str) -> Statement<'a> {
+      let line = line.trim();
+      let (word, rest) = match line.find(char::is_whitespace) {
+         Some(end) => (&line[..end], Some(line[end..].trim())),
+         None => (line, None),
+      };
+      match word {
+         "" => return Statement::Empty,
+         "curve" => return Statement::Curve(rest),
+         "base" => return Statement::Base(rest),
+         "vars" if rest.is_none() => return Statement::Vars,
+         "help" if rest.is_none() => return Statement::Help,
+         "quit" | "exit" if rest.is_none() => return Statement::Quit,
+         _ => {},
+      }
+
+      if let Some(equals) = line.find('=') {
+         return Statement::Assign(line[..equals].trim(), line[equals + 1..].trim());
+      }
+      match line.rfind(" as ") {
+         Some(start) => Statement::Print(line[..start].trim(), Some(line[start + 4..].trim())),
+         None => Statement::Print(line, None),
+      }
+   }
+}
+
+/// The state of a shell session, the curve, the bound names and the base
+/// numbers are printed in.
+#[derive(Debug, Clone)]
+pub struct Repl {
+   curve: CurveName,
+   names: BTreeMap<String, Value>,
+   hex: bool,
+}
+
+impl Repl {
+   pub fn new(curve: CurveName) -> Repl {
+      Repl {
+         curve,
+         names: BTreeMap::new(),
+         hex: true,
+      }
+   }
+
+   /// Read lines from `input` until it ends or `quit`, writing results and
+   /// errors to `output`. `prompt` is written before every line.
+   pub fn run<R: BufRead, W: Write>(
+      &mut self,
+      input: R,
+      output: &mut W,
+      prompt: &str,
+   ) -> Result<(), CliError> {
+      let io_error = |err: io::Error| CliError::Io(err.to_string());
+
+      write!(output, "{}", prompt).map_err(io_error)?;
+      output.flush().map_err(io_error)?;
+      for line in input.lines() {
+         let line = line.map_err(io_error)?;
+         if Statement::parse(&line) == Statement::Quit {
+            break;
+         }
+         match self.eval(&line) {
+            Ok(Some(result)) => writeln!(output, "{}", result),
+            Ok(None) => Ok(()),
+            Err(err) => writeln!(output, "error: {}", err),
+         }
+         .map_err(io_error)?;
+         write!(output, "{}", prompt).map_err(io_error)?;
+         output.flush().map_err(io_error)?;
+      }
+      Ok(())
+   }
+
+   /// Evaluate a line of input and return what is to be printed, if
+   /// anything.
+   pub fn eval(&mut self, line: &str) -> Result<Option<String>, CliError> {
+      match Statement::parse(line) {
+         Statement::Empty | Statement::Quit => Ok(None),
+         Statement::Help => Ok(Some(HELP.to_string())),
+         Statement::Vars => Ok(Some(self.vars())),
+         Statement::Curve(None) => Ok(Some(format!("{:?}", self.curve).to_lowercase())),
+         Statement::Curve(Some(name)) => {
+            self.curve = CurveName::parse(name)?;
+            // Points of one curve mean nothing on another.
+            self.names.clear();
+            Ok(None)
+         },
+         Statement::Base(None) => Ok(Some(if self.hex { "hex" } else { "dec" }.to_string())),
+         Statement::Base(Some(base)) => {
+            self.hex = match base {
+               "hex" | "16" => true,
+               "dec" | "10" => false,
+               _ => return Err(CliError::Usage(format!("Unknown base: {}", base))),
+            };
+            Ok(None)
+         },
+         Statement::Assign(name, expression) => {
+            if !is_name(name) {
+               return Err(CliError::Expression(format!("Invalid name: {}", name)));
+            }
+            let value = self.evaluate(expression)?;
+            self.names.insert(name.to_string(), value);
+            Ok(None)
+         },
+         Statement::Print(expression, format) => {
+            let value = self.evaluate(expression)?;
+            self.format(&value, format).map(Some)
+         },
+      }
+   }
+
+   fn evaluate(&self, expression: &str) -> Result<Value, CliError> {
+      match self.curve {
+         CurveName::Secp256k1 => evaluate(&Secp256k1::new(), &self.names, expression),
+         CurveName::Secp256r1 => evaluate(&Secp256r1::new(), &self.names, expression),
+      }
+   }
+
+   fn format(&self, value: &Value, format: Option<&str>) -> Result<String, CliError> {
+      match self.curve {
+         CurveName::Secp256k1 => format_value(&Secp256k1::new(), value, format, self.hex),
+         CurveName::Secp256r1 => format_value(&Secp256r1::new(), value, format, self.hex),
+      }
+   }
+
+   fn vars(&self) -> String {
+      self
+         .names
+         .iter()
+         .map(|(name, value)| match *value {
+            Value::Point(_) => format!("{}: point", name),
+            Value::Scalar(_) => format!("{}: scalar", name),
+         })
+         .collect::<Vec<_>>()
+         .join("\n")
+   }
+}
+
+fn is_name(name: &str) -> bool {
+   let mut chars = name.chars();
+   match chars.next() {
+      Some(first) if first.is_ascii_alphabetic() || first == '_' => {},
+      _ => return false,
+   }
+   name != "G" && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+   Number(String),
+   Name(String),
+   Plus,
+   Minus,
+   Times,
+   Open,
+   Close,
+   Comma,
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, CliError> {
+   let mut tokens = Vec::new();
+   let mut chars = expression.chars().peekable();
+   while let Some(&c) = chars.peek() {
+      let token = match c {
+         c if c.is_whitespace() => {
+            chars.next();
+            continue;
+         },
+         c if c.is_ascii_alphanumeric() || c == '_' => {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+               if !(c.is_ascii_alphanumeric() || c == '_') {
+                  break;
+               }
+               word.push(c);
+               chars.next();
+            }
+            tokens.push(if c.is_ascii_digit() { Token::Number(word) } else { Token::Name(word) });
+            continue;
+         },
+         '+' => Token::Plus,
+         // Also the minus sign, U+2212.
+         '-' | '\u{2212}' => Token::Minus,
+         '*' => Token::Times,
+         '(' => Token::Open,
+         ')' => Token::Close,
+         ',' => Token::Comma,
+         other => return Err(CliError::Expression(format!("Unexpected character: {}", other))),
+      };
+      chars.next();
+      tokens.push(token);
+   }
+   Ok(tokens)
+}
+
+/// A recursive descent parser evaluating as it goes:
+///
+/// ```text
+/// expression = term (("+" | "-") term)*
+/// term       = unary ("*" unary)*
+/// unary      = "-" unary | atom
+/// atom       = number | name | "(" expression ["," expression] ")"
+/// ```
+struct Evaluator<'a, C: 'a> {
+   curve: &'a C,
+   names: &'a BTreeMap<String, Value>,
+   tokens: Vec<Token>,
+   position: usize,
+}
+
+fn evaluate<C>(
+   curve: &C,
+   names: &BTreeMap<String, Value>,
+   expression: &str,
+) -> Result<Value, CliError>
+where
+   C: ECCurveCalculation<JacobianCoordinates> + ECCurvePoint<AffineCoordinates>,
+{
+   let mut evaluator = Evaluator {
+      curve,
+      names,
+      tokens: tokenize(expression)?,
+      position: 0,
+   };
+   let value = evaluator.expression()?;
+   match evaluator.tokens.get(evaluator.position) {
+      None => Ok(value),
+      Some(token) => Err(CliError::Expression(format!("Unexpected {:?}", token))),
+   }
+}
+
+impl<'a, C> Evaluator<'a, C>
+where
+   C: ECCurveCalculation<JacobianCoordinates> + ECCurvePoint<AffineCoordinates>,
+{
+   fn next_if(&mut self, token: &Token) -> bool {
+      if self.tokens.get(self.position) == Some(token) {
+         self.position += 1;
+         true
+      } else {
+         false
+      }
+   }
+
+   fn expression(&mut self) -> Result<Value, CliError> {
+      let mut value = self.term()?;
+      loop {
+         if self.next_if(&Token::Plus) {
+            let rhs = self.term()?;
+            value = self.add(value, rhs)?;
+         } else if self.next_if(&Token::Minus) {
+            let rhs = self.term()?;
+            let rhs = self.negate(rhs);
+            value = self.add(value, rhs)?;
+         } else {
+            return Ok(value);
+         }
+      }
+   }
+
+   fn term(&mut self) -> Result<Value, CliError> {
+      let mut value = self.unary()?;
+      while self.next_if(&Token::Times) {
+         let rhs = self.unary()?;
+         value = self.multiply(value, rhs)?;
+      }
+      Ok(value)
+   }
+
+   fn unary(&mut self) -> Result<Value, CliError> {
+      if self.next_if(&Token::Minus) {
+         let value = self.unary()?;
+         Ok(self.negate(value))
+      } else {
+         self.atom()
+      }
+   }
+
+   fn atom(&mut self) -> Result<Value, CliError> {
+      let token = self.tokens.get(self.position).cloned();
+      self.position += 1;
+      match token {
+         Some(Token::Number(ref number)) => parse_scalar(number).map(Value::Scalar),
+         Some(Token::Name(ref name)) if name == "G" => {
+            let point_g = JacobianCoordinates::convert_from(&self.curve.base_point(), self.curve);
+            Ok(Value::Point(point_g.unwrap_finite()))
+         },
+         Some(Token::Name(ref name)) => self
+            .names
+            .get(name)
+            .cloned()
+            .ok_or_else(|| CliError::Expression(format!("Unknown name: {}", name))),
+         Some(Token::Open) => {
+            let value = self.expression()?;
+            let value = if self.next_if(&Token::Comma) {
+               let y = self.expression()?;
+               self.point(value, y)?
+            } else {
+               value
+            };
+            if self.next_if(&Token::Close) {
+               Ok(value)
+            } else {
+               Err(CliError::Expression("Missing )".to_string()))
+            }
+         },
+         Some(token) => Err(CliError::Expression(format!("Unexpected {:?}", token))),
+         None => Err(CliError::Expression("Unexpected end of input".to_string())),
+      }
+   }
+
+   /// The point `(x, y)`, which has to be on the curve.
+   fn point(&self, x: Value, y: Value) -> Result<Value, CliError> {
+      let (x, y) = match (x, y) {
+         (Value::Scalar(x), Value::Scalar(y)) => (x, y),
+         _ => return Err(CliError::Expression("Coordinates must be numbers".to_string())),
+      };
+      let p = self.curve.p();
+      let point = AffineCoordinates {
+         x: x.mod_floor(&p),
+         y: y.mod_floor(&p),
+      };
+      if !self.curve.point_is_on_curve(&point) {
+         return Err(CliError::InvalidPoint(format!("({:x}, {:x})", point.x, point.y)));
+      }
+      Ok(Value::Point(JacobianCoordinates::convert_from(&point, self.curve).unwrap_finite()))
+   }
+
+   fn negate(&self, value: Value) -> Value {
+      match value {
+         Value::Point(point) => Value::Point(self.curve.negate_point(&point)),
+         Value::Scalar(k) => Value::Scalar(-k),
+      }
+   }
+
+   fn add(&self, former: Value, latter: Value) -> Result<Value, CliError> {
+      match (former, latter) {
+         (Value::Point(former), Value::Point(latter)) => {
+            Ok(Value::Point(self.curve.add_points(&former, &latter)))
+         },
+         (Value::Scalar(former), Value::Scalar(latter)) => Ok(Value::Scalar(former + latter)),
+         _ => Err(CliError::Expression("Points and scalars can not be added".to_string())),
+      }
+   }
+
+   fn multiply(&self, former: Value, latter: Value) -> Result<Value, CliError> {
+      match (former, latter) {
+         (Value::Point(point), Value::Scalar(k)) | (Value::Scalar(k), Value::Point(point)) => {
+            Ok(Value::Point(self.curve.multipy_point(&point, k)))
+         },
+         (Value::Scalar(former), Value::Scalar(latter)) => Ok(Value::Scalar(former * latter)),
+         _ => Err(CliError::Expression("Points can not be multiplied".to_string())),
+      }
+   }
+}
+
+/// Format `value` with the `Display` or `LowerHex` impls of the points, or as
+/// a SEC 1 encoding.
+fn format_value<C>(
+   curve: &C,
+   value: &Value,
+   format: Option<&str>,
+   hex: bool,
+) -> Result<String, CliError>
+where
+   C: ECCurveCalculation<JacobianCoordinates>,
+{
+   let point = match *value {
+      Value::Scalar(ref k) if format.is_none() => {
+         return Ok(match (hex, k < &BigInt::zero()) {
+            (true, true) => format!("-0x{:x}", -k),
+            (true, false) => format!("0x{:x}", k),
+            (false, _) => k.to_string(),
+         });
+      },
+      Value::Scalar(_) => return Err(CliError::Expression("Scalars have no format".to_string())),
+      Value::Point(ref point) => point,
+   };
+
+   let affine = AffineCoordinates::convert_from(point, curve);
+   match format.unwrap_or("affine") {
+      "affine" => Ok(show(&affine.ok_or(CliError::Infinity)?, hex)),
+      "jacobian" => Ok(show(point, hex)),
+      "projective" => Ok(show(&StandardProjectiveCoordinates::from(ECCValue::from(affine)), hex)),
+      "compressed" => Ok(ECCValue::from(affine).to_compressed()),
+      "uncompressed" => Ok(ECCValue::from(affine).to_uncompressed()),
+      other => Err(CliError::Usage(format!("Unknown format: {}", other))),
+   }
+}
+
+fn show<T: fmt::Display + fmt::LowerHex>(point: &T, hex: bool) -> String {
+   if hex {
+      format!("{:x}", point)
+   } else {
+      format!("{}", point)
+   }
+}
@@ -1,20 +1,46 @@
-extern crate num;
-use self::num::{BigInt, Integer};
+use num::{BigInt, Integer};
+
+use alloc::format;
+use alloc::string::String;
+
+use super::InfinityError;
 
 /// Value wil be defined as
 /// - a point on curve
 /// - infinity (not a point)
-#[derive(Debug, Clone)]
+///
+/// Infinity compares greater than every finite point.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum ECCValue {
    Finite { x: BigInt, y: BigInt },
    Infinity,
 }
 
-/// This is supposed to replace the current `ECCValue`.
-/// Calculating ECCValue::Finate everywhere is very annoying.
+/// A result of type `T` which may be the point at infinity, e.g. a point
+/// after an addition or a conversion to `AffineCoordinates`.
+///
+/// This enum aims to act like the built-in `Result<T>`, with `Infinity` as
+/// the error: `T` is kept, unlike in `ECCValue`, so the value stays useful.
+///
+/// ### Example
 ///
-/// This enum aims to act like the built-in `Result<T>`.
-/// Functions will state the `T`, so the value will be useful.
+/// ```
+/// use ecc_calc::ecc::prime::curves::{ECCurve, ECCurveCalculation, Secp256k1};
+/// use ecc_calc::ecc::prime::points::AffineCoordinates;
+/// use ecc_calc::ecc::InfinityError;
+///
+/// # fn main() {
+/// # let curve = Secp256k1::new();
+/// # let p = curve.base_point();
+/// # let q = curve.negate_point(&p);
+/// let point: Result<AffineCoordinates, InfinityError> =
+///    curve.add_points(&p, &q).and_then(|r| curve.double_point(&r)).into();
+/// assert!(point.is_err());
+/// # }
+/// ```
+///
+/// As for `ECCValue`, `Infinity` compares greater than every `Finite(_)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum ECCValueRes<T> {
    Finite(T),
    Infinity,
@@ -23,21 +49,87 @@ pub enum ECCValueRes<T> {
 impl ECCValue {
    pub fn to_uncompressed(&self) -> String {
       match self {
-         &ECCValue::Finite { ref x, ref y } => format!("04{:064x}{:064x}", x, y),
-         &ECCValue::Infinity => format!("00{:064x}", 0),
+         ECCValue::Finite { x, y } => format!("04{:064x}{:064x}", x, y),
+         ECCValue::Infinity => format!("00{:064x}", 0),
       }
    }
 
    pub fn to_compressed(&self) -> String {
       match self {
-         &ECCValue::Finite { ref x, ref y } => {
+         ECCValue::Finite { x, y } => {
             if y.is_even() {
                format!("02{:064x}", x)
             } else {
                format!("03{:064x}", x)
             }
          },
-         &ECCValue::Infinity => format!("00{:064x}", 0),
+         ECCValue::Infinity => format!("00{:064x}", 0),
+      }
+   }
+}
+
+impl<T> ECCValueRes<T> {
+   pub fn is_finite(&self) -> bool {
+      match *self {
+         ECCValueRes::Finite(_) => true,
+         ECCValueRes::Infinity => false,
+      }
+   }
+
+   pub fn is_infinity(&self) -> bool { !self.is_finite() }
+
+   /// Return the finite value.
+   ///
+   /// ### Panics
+   /// Panics if the value is infinity.
+   pub fn unwrap_finite(self) -> T {
+      match self {
+         ECCValueRes::Finite(val) => val,
+         ECCValueRes::Infinity => panic!("called `ECCValueRes::unwrap_finite()` on infinity"),
+      }
+   }
+
+   /// Apply `f` to a finite value, infinity stays infinity.
+   pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> ECCValueRes<U> {
+      match self {
+         ECCValueRes::Finite(val) => ECCValueRes::Finite(f(val)),
+         ECCValueRes::Infinity => ECCValueRes::Infinity,
+      }
+   }
+
+   /// Chain a calculation which may itself result in infinity.
+   pub fn and_then<U, F: FnOnce(T) -> ECCValueRes<U>>(self, f: F) -> ECCValueRes<U> {
+      match self {
+         ECCValueRes::Finite(val) => f(val),
+         ECCValueRes::Infinity => ECCValueRes::Infinity,
+      }
+   }
+
+   /// Turn infinity into the error `err`.
+   pub fn ok_or<E>(self, err: E) -> Result<T, E> {
+      match self {
+         ECCValueRes::Finite(val) => Ok(val),
+         ECCValueRes::Infinity => Err(err),
+      }
+   }
+
+   pub fn as_ref(&self) -> ECCValueRes<&T> {
+      match *self {
+         ECCValueRes::Finite(ref val) => ECCValueRes::Finite(val),
+         ECCValueRes::Infinity => ECCValueRes::Infinity,
+      }
+   }
+}
+
+impl<T> From<ECCValueRes<T>> for Result<T, InfinityError> {
+   fn from(val: ECCValueRes<T>) -> Result<T, InfinityError> { val.ok_or(InfinityError) }
+}
+
+impl<T> From<ECCValueRes<T>> for Option<T> {
+   fn from(val: ECCValueRes<T>) -> Option<T> {
+      match val {
+         ECCValueRes::Finite(val) => Some(val),
+         ECCValueRes::Infinity => None,
       }
    }
 }
@@ -3,5 +3,6 @@ mod infinity_error;
 mod ecc_value;
 
 pub use self::ecc_value::{ECCValue, ECCValueRes};
+pub use self::infinity_error::InfinityError;
 pub mod prime;
 pub mod binary;
@@ -0,0 +1,360 @@
+//! Birational maps between short Weierstrass, Montgomery and twisted Edwards
+//! curves, e.g. Wei25519, Curve25519 and edwards25519 are the same group.
+//!
+//! Every model is given by its parameters over `Fp`, and points by
+//! `AffineCoordinates`, with `ECCValueRes::Infinity` for the point at
+//! infinity of Weierstrass and Montgomery curves. Points are not checked to
+//! be on the curve, use `is_on_curve` for that.
+//!
+//! ### Example
+//!
+//! ```
+//! use ecc_calc::ecc::prime::birational::{EdwardsForm, MontgomeryEdwardsMap, MontgomeryForm};
+//! use ecc_calc::ecc::prime::edwards::{Edwards25519, TwistedEdwardsCurve};
+//! use ecc_calc::ecc::prime::montgomery::{Curve25519, MontgomeryCurve};
+//! use ecc_calc::ecc::ECCValueRes;
+//!
+//! # fn main() {
+//! let montgomery = MontgomeryForm::from_curve(&Curve25519::new());
+//! let edwards = EdwardsForm::from_curve(&Edwards25519::new());
+//! let map = MontgomeryEdwardsMap::between(&montgomery, &edwards).unwrap();
+//! # let point_b = Edwards25519::new().base_point();
+//! # let montgomery_point = map.to_montgomery(&point_b).unwrap_finite();
+//! let point = map.to_edwards(&ECCValueRes::Finite(montgomery_point));
+//! # assert_eq!(point, Some(point_b));
+//! # }
+//! ```
+
+use num::{BigInt, Integer, One, Zero};
+
+use super::curves::ECCurve;
+use super::edwards::TwistedEdwardsCurve;
+use super::field;
+use super::montgomery::MontgomeryCurve;
+use super::points::AffineCoordinates;
+use super::super::ECCValueRes;
+
+/// `E: y^2 = x^3 + ax + b over Fp`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeierstrassForm {
+   pub p: BigInt,
+   pub a: BigInt,
+   pub b: BigInt,
+}
+
+/// `E: By^2 = x^3 + Ax^2 + x over Fp`, with `(u, v)` for `(x, y)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MontgomeryForm {
+   pub p: BigInt,
+   pub a: BigInt,
+   pub b: BigInt,
+}
+
+/// `E: ax^2 + y^2 = 1 + dx^2y^2 over Fp`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdwardsForm {
+   pub p: BigInt,
+   pub a: BigInt,
+   pub d: BigInt,
+}
+
+impl WeierstrassForm {
+   pub fn from_curve<C: ECCurve>(curve: &C) -> Self {
+      let p = curve.p();
+      WeierstrassForm {
+         a: curve.a().mod_floor(&p),
+         b: curve.b().mod_floor(&p),
+         p,
+      }
+   }
+
+   pub fn is_on_curve(&self, point: &ECCValueRes<AffineCoordinates>) -> bool {
+      match *point {
+         ECCValueRes::Finite(ref point) => {
+            let left = &point.y * &point.y;
+            let right = &point.x * &point.x * &point.x + &self.a * &point.x + &self.b;
+            (left - right).mod_floor(&self.p).is_zero()
+         },
+         ECCValueRes::Infinity => true,
+      }
+   }
+}
+
+impl MontgomeryForm {
+   pub fn from_curve<C: MontgomeryCurve>(curve: &C) -> Self {
+      let p = curve.p();
+      MontgomeryForm {
+         a: curve.a().mod_floor(&p),
+         b: curve.b().mod_floor(&p),
+         p,
+      }
+   }
+
+   pub fn is_on_curve(&self, point: &ECCValueRes<AffineCoordinates>) -> bool {
+      match *point {
+         ECCValueRes::Finite(ref point) => {
+            let left = &self.b * &point.y * &point.y;
+            let right = &point.x * &point.x * &point.x + &self.a * &point.x * &point.x + &point.x;
+            (left - right).mod_floor(&self.p).is_zero()
+         },
+         ECCValueRes::Infinity => true,
+      }
+   }
+}
+
+impl EdwardsForm {
+   pub fn from_curve<C: TwistedEdwardsCurve>(curve: &C) -> Self {
+      let p = curve.p();
+      EdwardsForm {
+         a: curve.a().mod_floor(&p),
+         d: curve.d().mod_floor(&p),
+         p,
+      }
+   }
+
+   pub fn is_on_curve(&self, point: &AffineCoordinates) -> bool {
+      let x2 = &point.x * &point.x;
+      let y2 = &point.y * &point.y;
+      let left = &self.a * &x2 + &y2;
+      let right = BigInt::one() + &self.d * x2 * y2;
+      (left - right).mod_floor(&self.p).is_zero()
+   }
+}
+
+/// `(u, v) -> (x, y) = (cu/v, (u - 1)/(u + 1))` from a Montgomery to a twisted
+/// Edwards curve, and `(x, y) -> (u, v) = ((1 + y)/(1 - y), cu/x)` back.
+///
+/// The neutral elements map to each other, and `(0, 0)` to `(0, -1)`. The
+/// remaining points with `v = 0` or `u = -1` have no affine image, which
+/// can't happen when `d` is not a square.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MontgomeryEdwardsMap {
+   montgomery: MontgomeryForm,
+   edwards: EdwardsForm,
+   c: BigInt,
+}
+
+impl MontgomeryEdwardsMap {
+   /// The Edwards curve `a = (A + 2)/B, d = (A - 2)/B` of `montgomery`, with
+   /// `c = 1`.
+   pub fn from_montgomery(montgomery: &MontgomeryForm) -> Self {
+      let p = &montgomery.p;
+      let b_inv = field::inverse(&montgomery.b, p);
+      let edwards = EdwardsForm {
+         p: p.clone(),
+         a: ((&montgomery.a + BigInt::from(2_u8)) * &b_inv).mod_floor(p),
+         d: ((&montgomery.a - BigInt::from(2_u8)) * &b_inv).mod_floor(p),
+      };
+      MontgomeryEdwardsMap {
+         montgomery: montgomery.clone(),
+         edwards,
+         c: BigInt::one(),
+      }
+   }
+
+   /// The Montgomery curve `A = 2(a + d)/(a - d), B = 4/(a - d)` of `edwards`,
+   /// with `c = 1`.
+   pub fn from_edwards(edwards: &EdwardsForm) -> Self {
+      let p = &edwards.p;
+      let difference_inv = field::inverse(&(&edwards.a - &edwards.d), p);
+      let montgomery = MontgomeryForm {
+         p: p.clone(),
+         a: (BigInt::from(2_u8) * (&edwards.a + &edwards.d) * &difference_inv).mod_floor(p),
+         b: (BigInt::from(4_u8) * &difference_inv).mod_floor(p),
+      };
+      MontgomeryEdwardsMap {
+         montgomery,
+         edwards: edwards.clone(),
+         c: BigInt::one(),
+      }
+   }
+
+   /// The map between two given curves, which exists if
+   /// `d/a = (A - 2)/(A + 2)` and `c^2 = (A + 2)/(Ba)` has a solution.
+   /// Returns `None` otherwise.
+   ///
+   /// Of the two solutions `c` the one at most `(p - 1) / 2` is taken. The
+   /// other gives the map followed by negation on the Edwards curve, see
+   /// `negated`.
+   pub fn between(montgomery: &MontgomeryForm, edwards: &EdwardsForm) -> Option<Self> {
+      let p = &montgomery.p;
+      if p != &edwards.p {
+         return None;
+      }
+
+      let plus_two = (&montgomery.a + BigInt::from(2_u8)).mod_floor(p);
+      let minus_two = (&montgomery.a - BigInt::from(2_u8)).mod_floor(p);
+      if plus_two.is_zero() || edwards.a.is_zero() || montgomery.b.is_zero() {
+         return None;
+      }
+      // d(A + 2) = a(A - 2)
+      if !(&edwards.d * &plus_two - &edwards.a * &minus_two).mod_floor(p).is_zero() {
+         return None;
+      }
+
+      let c2 = plus_two * field::inverse(&(&montgomery.b * &edwards.a), p);
+      let c = field::sqrt(&c2, p)?;
+      Some(MontgomeryEdwardsMap {
+         montgomery: montgomery.clone(),
+         edwards: edwards.clone(),
+         c,
+      })
+   }
+
+   /// The same map followed by `(x, y) -> (-x, y)`, that is with `-c`.
+   pub fn negated(&self) -> Self {
+      MontgomeryEdwardsMap {
+         montgomery: self.montgomery.clone(),
+         edwards: self.edwards.clone(),
+         c: (-&self.c).mod_floor(&self.edwards.p),
+      }
+   }
+
+   pub fn montgomery(&self) -> &MontgomeryForm { &self.montgomery }
+
+   pub fn edwards(&self) -> &EdwardsForm { &self.edwards }
+
+   /// Return `None` for the points without an affine image.
+   pub fn to_edwards(&self, point: &ECCValueRes<AffineCoordinates>) -> Option<AffineCoordinates> {
+      let p = &self.edwards.p;
+      let point = match *point {
+         ECCValueRes::Finite(ref point) => point,
+         ECCValueRes::Infinity => {
+            return Some(AffineCoordinates {
+               x: BigInt::zero(),
+               y: BigInt::one(),
+            })
+         },
+      };
+
+      let u = point.x.mod_floor(p);
+      let v = point.y.mod_floor(p);
+      if u.is_zero() && v.is_zero() {
+         return Some(AffineCoordinates {
+            x: BigInt::zero(),
+            y: p - BigInt::one(),
+         });
+      }
+      let u_plus_one = (&u + BigInt::one()).mod_floor(p);
+      if v.is_zero() || u_plus_one.is_zero() {
+         return None;
+      }
+
+      Some(AffineCoordinates {
+         x: (&self.c * &u * field::inverse(&v, p)).mod_floor(p),
+         y: ((&u - BigInt::one()) * field::inverse(&u_plus_one, p)).mod_floor(p),
+      })
+   }
+
+   pub fn to_montgomery(&self, point: &AffineCoordinates) -> ECCValueRes<AffineCoordinates> {
+      let p = &self.montgomery.p;
+      let x = point.x.mod_floor(p);
+      let y = point.y.mod_floor(p);
+      if x.is_zero() {
+         // (0, 1) and (0, -1), the only points on the curve with x = 0
+         return if y.is_one() {
+            ECCValueRes::Infinity
+         } else {
+            ECCValueRes::Finite(AffineCoordinates {
+               x: BigInt::zero(),
+               y: BigInt::zero(),
+            })
+         };
+      }
+
+      let u = ((BigInt::one() + &y) * field::inverse(&(BigInt::one() - &y), p)).mod_floor(p);
+      let v = (&self.c * &u * field::inverse(&x, p)).mod_floor(p);
+      ECCValueRes::Finite(AffineCoordinates { x: u, y: v })
+   }
+}
+
+/// `(u, v) -> (x, y) = (u/B + A/(3B), v/B)` from a Montgomery to a short
+/// Weierstrass curve, and `(x, y) -> (u, v) = (Bx - A/3, By)` back. The
+/// points at infinity map to each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeierstrassMontgomeryMap {
+   weierstrass: WeierstrassForm,
+   montgomery: MontgomeryForm,
+}
+
+impl WeierstrassMontgomeryMap {
+   /// The Weierstrass curve `a = (3 - A^2)/(3B^2), b = (2A^3 - 9A)/(27B^3)`
+   /// of `montgomery`.
+   pub fn from_montgomery(montgomery: &MontgomeryForm) -> Self {
+      let p = &montgomery.p;
+      let big_a = &montgomery.a;
+      let big_b = &montgomery.b;
+
+      let a = (BigInt::from(3_u8) - big_a * big_a)
+         * field::inverse(&(BigInt::from(3_u8) * big_b * big_b), p);
+      let b = (BigInt::from(2_u8) * big_a * big_a * big_a - BigInt::from(9_u8) * big_a)
+         * field::inverse(&(BigInt::from(27_u8) * big_b * big_b * big_b), p);
+      WeierstrassMontgomeryMap {
+         weierstrass: WeierstrassForm {
+            p: p.clone(),
+            a: a.mod_floor(p),
+            b: b.mod_floor(p),
+         },
+         montgomery: montgomery.clone(),
+      }
+   }
+
+   /// The Montgomery curve `A = 3αs, B = s` of `weierstrass`, where `α` is a
+   /// root of `x^3 + ax + b` and `s = 1/sqrt(3α^2 + a)`. Returns `None` if
+   /// `alpha` is not a root, or `3α^2 + a` is not a nonzero square, in which
+   /// case the curve has no Montgomery form through `alpha`.
+   ///
+   /// Of the two square roots the one at most `(p - 1) / 2` is taken.
+   pub fn from_weierstrass(weierstrass: &WeierstrassForm, alpha: &BigInt) -> Option<Self> {
+      let p = &weierstrass.p;
+      let alpha = alpha.mod_floor(p);
+      let root = &alpha * &alpha * &alpha + &weierstrass.a * &alpha + &weierstrass.b;
+      if !root.mod_floor(p).is_zero() {
+         return None;
+      }
+
+      let t = field::sqrt(&(BigInt::from(3_u8) * &alpha * &alpha + &weierstrass.a), p)?;
+      if t.is_zero() {
+         return None;
+      }
+      let s = field::inverse(&t, p);
+      Some(WeierstrassMontgomeryMap {
+         weierstrass: weierstrass.clone(),
+         montgomery: MontgomeryForm {
+            p: p.clone(),
+            a: (BigInt::from(3_u8) * alpha * &s).mod_floor(p),
+            b: s,
+         },
+      })
+   }
+
+   pub fn weierstrass(&self) -> &WeierstrassForm { &self.weierstrass }
+
+   pub fn montgomery(&self) -> &MontgomeryForm { &self.montgomery }
+
+   pub fn to_weierstrass(
+      &self,
+      point: &ECCValueRes<AffineCoordinates>,
+   ) -> ECCValueRes<AffineCoordinates> {
+      let p = &self.weierstrass.p;
+      let b_inv = field::inverse(&self.montgomery.b, p);
+      let a_third = &self.montgomery.a * field::inverse(&BigInt::from(3_u8), p);
+      point.clone().map(|point| AffineCoordinates {
+         x: ((point.x + a_third) * &b_inv).mod_floor(p),
+         y: (point.y * &b_inv).mod_floor(p),
+      })
+   }
+
+   pub fn to_montgomery(
+      &self,
+      point: &ECCValueRes<AffineCoordinates>,
+   ) -> ECCValueRes<AffineCoordinates> {
+      let p = &self.montgomery.p;
+      let big_b = &self.montgomery.b;
+      let a_third = &self.montgomery.a * field::inverse(&BigInt::from(3_u8), p);
+      point.clone().map(|point| AffineCoordinates {
+         x: (big_b * point.x - a_third).mod_floor(p),
+         y: (big_b * point.y).mod_floor(p),
+      })
+   }
+}
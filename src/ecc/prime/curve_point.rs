@@ -0,0 +1,257 @@
+use num::BigInt;
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use super::curves::{ECCurve, ECCurveCalculation};
+use super::points::{AffineCoordinates, Point, PointCalculation, PointFraction, PointFrom};
+use super::scalar::Scalar;
+use super::ECCurvePoint;
+use super::super::ECCValueRes;
+
+/// A point bound to the curve `C`, stored in the representation `P`.
+///
+/// The curve is part of the type, so operators can be used without passing a
+/// curve around, and points of different curves can not be mixed up.
+///
+/// ### Example
+///
+/// ```
+/// use ecc_calc::ecc::prime::curve_point::CurvePoint;
+/// use ecc_calc::ecc::prime::curves::Secp256k1;
+/// use ecc_calc::ecc::prime::points::JacobianCoordinates;
+/// use num::BigInt;
+///
+/// # fn main() {
+/// let g = CurvePoint::<Secp256k1, JacobianCoordinates>::generator();
+/// let mut p = &g + &g;
+/// p += g.clone();
+/// assert_eq!(p, g * BigInt::from(3));
+/// # }
+/// ```
+///
+/// Mixing curves does not compile:
+///
+/// ```compile_fail
+/// use ecc_calc::ecc::prime::curve_point::CurvePoint;
+/// use ecc_calc::ecc::prime::curves::{Secp256k1, Secp256r1};
+/// use ecc_calc::ecc::prime::points::JacobianCoordinates;
+///
+/// # fn main() {
+/// let k1 = CurvePoint::<Secp256k1, JacobianCoordinates>::generator();
+/// let r1 = CurvePoint::<Secp256r1, JacobianCoordinates>::generator();
+/// let _ = k1 + r1;
+/// # }
+/// ```
+pub struct CurvePoint<C: ECCurve, P: Point> {
+   point: P,
+   curve: PhantomData<C>,
+}
+
+impl<C, P> CurvePoint<C, P>
+where
+   C: ECCurveCalculation<P>,
+   P: PointCalculation<C, Output = P>,
+{
+   /// Wrap `point`, which has to be on the curve `C`.
+   pub fn new(point: P) -> Self {
+      CurvePoint {
+         point,
+         curve: PhantomData,
+      }
+   }
+
+   /// Return the base point of the curve.
+   pub fn generator() -> Self
+   where
+      P: PointFrom<AffineCoordinates>,
+   {
+      let curve = C::new();
+      Self::new(P::convert_from(&curve.base_point(), &curve).unwrap_finite())
+   }
+
+   /// Return the point at infinity.
+   pub fn identity() -> Self
+   where
+      P: PointFrom<AffineCoordinates>,
+   {
+      Self::generator() * Scalar::zero()
+   }
+
+   pub fn point(&self) -> &P { &self.point }
+
+   pub fn into_point(self) -> P { self.point }
+
+   pub fn to_affine(&self) -> ECCValueRes<AffineCoordinates>
+   where
+      AffineCoordinates: PointFrom<P>,
+   {
+      AffineCoordinates::convert_from(&self.point, &C::new())
+   }
+}
+
+/* -- Trait impls -- */
+// These are written by hand, as deriving them would require `C` itself to
+// implement the traits.
+impl<C: ECCurve, P: Point> Clone for CurvePoint<C, P> {
+   fn clone(&self) -> Self {
+      CurvePoint {
+         point: self.point.clone(),
+         curve: PhantomData,
+      }
+   }
+}
+
+impl<C: ECCurve, P: Point> fmt::Debug for CurvePoint<C, P> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "CurvePoint<{}>(", C::new().name())?;
+      fmt::Debug::fmt(&self.point, f)?;
+      write!(f, ")")
+   }
+}
+
+impl<C: ECCurve, P: Point> fmt::Display for CurvePoint<C, P> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::Display::fmt(&self.point, f) }
+}
+
+impl<C: ECCurve, P: Point> fmt::LowerHex for CurvePoint<C, P> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::LowerHex::fmt(&self.point, f) }
+}
+
+impl<C: ECCurve, P: Point> fmt::UpperHex for CurvePoint<C, P> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::UpperHex::fmt(&self.point, f) }
+}
+
+/// Points are equal if they are the same point on `C`, regardless of their
+/// projective coordinates.
+impl<C, P> PartialEq for CurvePoint<C, P>
+where
+   C: ECCurvePoint<P>,
+   P: PointFraction,
+{
+   fn eq(&self, other: &Self) -> bool { C::new().points_equal(&self.point, &other.point) }
+}
+
+impl<C, P> Eq for CurvePoint<C, P>
+where
+   C: ECCurvePoint<P>,
+   P: PointFraction,
+{
+}
+/* -- Trait impls -- */
+
+/* -- Arithmetic impls -- */
+impl<C, P> Add<&CurvePoint<C, P>> for &CurvePoint<C, P>
+where
+   C: ECCurveCalculation<P>,
+   P: PointCalculation<C, Output = P>,
+{
+   type Output = CurvePoint<C, P>;
+
+   fn add(self, other: &CurvePoint<C, P>) -> CurvePoint<C, P> {
+      CurvePoint::new(C::new().add_points(&self.point, &other.point))
+   }
+}
+
+impl<C, P> Add for CurvePoint<C, P>
+where
+   C: ECCurveCalculation<P>,
+   P: PointCalculation<C, Output = P>,
+{
+   type Output = CurvePoint<C, P>;
+
+   fn add(self, other: CurvePoint<C, P>) -> CurvePoint<C, P> { &self + &other }
+}
+
+impl<C, P> AddAssign for CurvePoint<C, P>
+where
+   C: ECCurveCalculation<P>,
+   P: PointCalculation<C, Output = P>,
+{
+   fn add_assign(&mut self, other: CurvePoint<C, P>) { *self = &*self + &other; }
+}
+
+impl<C, P> Sub<&CurvePoint<C, P>> for &CurvePoint<C, P>
+where
+   C: ECCurveCalculation<P>,
+   P: PointCalculation<C, Output = P>,
+{
+   type Output = CurvePoint<C, P>;
+
+   fn sub(self, other: &CurvePoint<C, P>) -> CurvePoint<C, P> {
+      CurvePoint::new(C::new().subtract_points(&self.point, &other.point))
+   }
+}
+
+impl<C, P> Sub for CurvePoint<C, P>
+where
+   C: ECCurveCalculation<P>,
+   P: PointCalculation<C, Output = P>,
+{
+   type Output = CurvePoint<C, P>;
+
+   fn sub(self, other: CurvePoint<C, P>) -> CurvePoint<C, P> { &self - &other }
+}
+
+impl<C, P> SubAssign for CurvePoint<C, P>
+where
+   C: ECCurveCalculation<P>,
+   P: PointCalculation<C, Output = P>,
+{
+   fn sub_assign(&mut self, other: CurvePoint<C, P>) { *self = &*self - &other; }
+}
+
+impl<C, P> Neg for &CurvePoint<C, P>
+where
+   C: ECCurveCalculation<P>,
+   P: PointCalculation<C, Output = P>,
+{
+   type Output = CurvePoint<C, P>;
+
+   fn neg(self) -> CurvePoint<C, P> { CurvePoint::new(C::new().negate_point(&self.point)) }
+}
+
+impl<C, P> Neg for CurvePoint<C, P>
+where
+   C: ECCurveCalculation<P>,
+   P: PointCalculation<C, Output = P>,
+{
+   type Output = CurvePoint<C, P>;
+
+   fn neg(self) -> CurvePoint<C, P> { -&self }
+}
+
+impl<C, P> Mul<&Scalar<C>> for &CurvePoint<C, P>
+where
+   C: ECCurveCalculation<P>,
+   P: PointCalculation<C, Output = P>,
+{
+   type Output = CurvePoint<C, P>;
+
+   fn mul(self, k: &Scalar<C>) -> CurvePoint<C, P> {
+      CurvePoint::new(C::new().multipy_point(&self.point, k.clone()))
+   }
+}
+
+impl<C, P> Mul<Scalar<C>> for CurvePoint<C, P>
+where
+   C: ECCurveCalculation<P>,
+   P: PointCalculation<C, Output = P>,
+{
+   type Output = CurvePoint<C, P>;
+
+   fn mul(self, k: Scalar<C>) -> CurvePoint<C, P> { &self * &k }
+}
+
+impl<C, P> Mul<BigInt> for CurvePoint<C, P>
+where
+   C: ECCurveCalculation<P>,
+   P: PointCalculation<C, Output = P>,
+{
+   type Output = CurvePoint<C, P>;
+
+   /// `k` is reduced modulo `n` first.
+   fn mul(self, k: BigInt) -> CurvePoint<C, P> { &self * &Scalar::new(k) }
+}
+/* -- Arithmetic impls -- */
@@ -1,13 +1,20 @@
-extern crate num;
-
-use self::num::{BigInt, Integer, Num};
-use ecc::prime::points::{AffineCoordinates, PointCalculation};
-use ecc::ECCValue;
-use std;
+use num::{BigInt, Integer, Num, One, Signed};
+use alloc::string::String;
+use alloc::vec::Vec;
+use crate::ecc::prime::field;
+use crate::ecc::prime::points::{
+   AffineCoordinates,
+   JacobianCoordinates,
+   PointCalculation,
+   PointFraction,
+   PointFrom,
+};
+use crate::ecc::prime::scalar::Scalar;
+use crate::ecc::{ECCValue, ECCValueRes};
 use super::errors;
 
 /// Implement basic curve related functions and lookups.
-pub trait ECCurve {
+pub trait ECCurve: Sized {
    /// Return an copy of the curve.
    fn new() -> Self;
 
@@ -26,10 +33,37 @@ pub trait ECCurve {
    /// Return the `n` value where `E: y2 = x3 + ax + b over Fp`
    fn n(&self) -> BigInt;
 
+   /// Return the cofactor `h`, where `h * n` is the number of points on the
+   /// curve.
+   fn h(&self) -> BigInt;
+
    /// Return the `AffineCoordinates` representing the base point of the given
    /// curve.
    fn base_point(&self) -> AffineCoordinates;
 
+   /// Return the point on the curve with the x coordinate `x` and a y
+   /// coordinate of the given parity, or `None` if there is no such point.
+   fn decompress_point(&self, x: &BigInt, y_is_odd: bool) -> Option<AffineCoordinates> {
+      let p = self.p();
+      if x.is_negative() || x >= &p {
+         return None;
+      }
+
+      // `E: y2 = x3 + ax + b over Fp`
+      #[allow(non_snake_case)]
+      let THREE = BigInt::from(3_u8);
+      let y2 = (x.modpow(&THREE, &p) + &self.a() * x + self.b()).mod_floor(&p);
+      // `y2` may not be a square at all.
+      let y = field::sqrt(&y2, &p)?;
+
+      let y = if y.is_odd() == y_is_odd {
+         y
+      } else {
+         (&p - &y).mod_floor(&p)
+      };
+      Some(AffineCoordinates { x: x.clone(), y })
+   }
+
    // /// decode "04.." "03.." "02.." into point.
    // fn decode_public_key(&self, String) -> Result<ECCValue, ParseError>
 
@@ -38,49 +72,32 @@ pub trait ECCurve {
       key: S,
    ) -> Result<ECCValue, errors::PublicKeyParseError> {
       let key_string: String = key.into();
+      // Hex digits of a coordinate
+      let len = (self.p().bits() as usize).div_ceil(8) * 2;
+      let parse =
+         |hex: &str| BigInt::from_str_radix(hex, 16).map_err(|_| errors::PublicKeyParseError);
+
+      // `from_str_radix` takes signs and underscores as well, and slicing
+      // anything but ASCII may panic.
+      if !key_string.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+         return Err(errors::PublicKeyParseError);
+      }
 
-      // `E: y2 = x3 + ax + b over Fp`
-      //
-      // r^2 mod m where m = 3 mod 4 (as secp256k1's p does)
-      // then
-      // r = +-r2^((m+1)/4) mod m
-      #[allow(non_snake_case)]
-      let y_calc = |x: &BigInt| {
-         let THREE = BigInt::from(3_u8);
-         let y2 = (x.modpow(&THREE, &self.p()) + &self.a() * x + self.b()).mod_floor(&self.p());
-         // FIXME: this should vary by p's value
-         let tmp: BigInt = (&self.p() + 1) / 4;
-         let y = y2.modpow(&tmp, &self.p());
-         return y;
-      };
-
-      match &key_string[..2] {
-         "00" => Ok(ECCValue::Infinity),
-         "02" => {
-            let x = BigInt::from_str_radix(&key_string[2..], 16).unwrap();
-            let y = y_calc(&x);
-            let val = if y.is_odd() {
-               let y = &self.p() - &y;
-               ECCValue::Finite { x, y }
-            } else {
-               ECCValue::Finite { x, y }
-            };
-            Ok(val)
+      match key_string.get(..2) {
+         // `00` as in SEC 1, or zero padded as by `ECCValue::to_uncompressed`
+         Some("00") if key_string[2..].bytes().all(|byte| byte == b'0') => {
+            Ok(ECCValue::Infinity)
          },
-         "03" => {
-            let x = BigInt::from_str_radix(&key_string[2..], 16).unwrap();
-            let y = y_calc(&x);
-            let val = if y.is_even() {
-               let y = &self.p() - &y;
-               ECCValue::Finite { x, y }
-            } else {
-               ECCValue::Finite { x, y }
-            };
-            Ok(val)
+         Some(prefix @ "02") | Some(prefix @ "03") if key_string.len() == 2 + len => {
+            let x = parse(&key_string[2..])?;
+            match self.decompress_point(&x, prefix == "03") {
+               Some(point) => Ok(ECCValue::from(point)),
+               None => Err(errors::PublicKeyParseError),
+            }
          },
-         "04" => {
-            let x = BigInt::from_str_radix(&key_string[2..66], 16).unwrap();
-            let y = BigInt::from_str_radix(&key_string[66..], 16).unwrap();
+         Some("04") if key_string.len() == 2 + 2 * len => {
+            let x = parse(&key_string[2..2 + len])?;
+            let y = parse(&key_string[2 + len..])?;
             Ok(ECCValue::Finite { x, y })
          },
          _ => Err(errors::PublicKeyParseError),
@@ -91,28 +108,132 @@ pub trait ECCurve {
 pub trait ECCurveCalculation<P>: ECCurve
 where
    P: PointCalculation<Self>,
-   Self: std::marker::Sized,
+   Self: core::marker::Sized,
 {
-   fn add_points(&self, former: &P, latter: &P) -> P {
+   fn add_points(&self, former: &P, latter: &P) -> P::Output {
       PointCalculation::point_addition(self, former, latter)
    }
 
-   fn subtract_points(&self, former: &P, latter: &P) -> P {
+   fn subtract_points(&self, former: &P, latter: &P) -> P::Output {
       PointCalculation::point_subtraction(self, former, latter)
    }
 
-   fn double_point(&self, point: &P) -> P { PointCalculation::point_doublation(self, point) }
+   fn double_point(&self, point: &P) -> P::Output {
+      PointCalculation::point_doublation(self, point)
+   }
 
-   fn multipy_point(&self, point: &P, b: BigInt) -> P {
-      PointCalculation::point_multipication(self, point, b)
+   /// Return `k * point`. `k` is reduced modulo `n` first, so plain `BigInt`s
+   /// like `-1` or `n + 1` are accepted as well.
+   fn multipy_point<K: Into<Scalar<Self>>>(&self, point: &P, k: K) -> P::Output {
+      PointCalculation::point_multipication(self, point, k.into().into_bigint())
+   }
+
+   fn negate_point(&self, point: &P) -> P { PointCalculation::point_negation(self, point) }
+
+   /// Whether `point` is in the subgroup of order `n` generated by the base
+   /// point, assuming it is on the curve.
+   ///
+   /// The multiplication is done in `JacobianCoordinates`, so `point` may be
+   /// in any representation, `AffineCoordinates` included.
+   fn is_in_subgroup(&self, point: &P) -> bool
+   where
+      Self: ECCurveCalculation<JacobianCoordinates>,
+      JacobianCoordinates: PointFrom<P>,
+   {
+      let point = match JacobianCoordinates::convert_from(point, self) {
+         ECCValueRes::Finite(point) => point,
+         ECCValueRes::Infinity => return true,
+      };
+      // Every point is in the subgroup when the curve itself has prime order.
+      if self.h().is_one() {
+         return true;
+      }
+      // `multipy_point` would reduce `n` to zero.
+      is_infinity(&JacobianCoordinates::point_multipication(self, &point, self.n()))
+   }
+
+   /// Return the order of `point`, assuming it is on the curve and `n` is
+   /// prime. The divisors of `h` are tried one by one, so this is only
+   /// practical for small cofactors.
+   ///
+   /// As for `is_in_subgroup`, `point` may be in any representation.
+   fn point_order(&self, point: &P) -> BigInt
+   where
+      Self: ECCurveCalculation<JacobianCoordinates>,
+      JacobianCoordinates: PointFrom<P>,
+   {
+      let point = match JacobianCoordinates::convert_from(point, self) {
+         ECCValueRes::Finite(point) => point,
+         ECCValueRes::Infinity => return BigInt::one(),
+      };
+      let n = self.n();
+      if self.h().is_one() {
+         return n;
+      }
+
+      // The order divides `h * n`, whose divisors are `d` and `d * n` for
+      // every `d` dividing `h`.
+      let h = self.h();
+      let mut divisors = Vec::new();
+      let mut d = BigInt::one();
+      while d <= h {
+         if h.is_multiple_of(&d) {
+            divisors.push(d.clone());
+         }
+         d += BigInt::one();
+      }
+
+      let multiples = divisors.iter().cloned().chain(divisors.iter().map(|d| d * &n));
+      for k in multiples {
+         if is_infinity(&JacobianCoordinates::point_multipication(self, &point, k.clone())) {
+            return k;
+         }
+      }
+      unreachable!("The order of a point divides h * n")
+   }
+
+   /// Return `h * point`, which is in the subgroup of order `n`.
+   fn clear_cofactor(&self, point: &P) -> P
+   where
+      P: PointCalculation<Self, Output = P>,
+   {
+      P::point_multipication(self, point, self.h())
    }
 }
 
+fn is_infinity<P: PointFraction>(point: &P) -> bool { point.affine_fraction().is_infinity() }
+
 #[test]
 fn try_parse_public_key() {
    let curve = super::Secp256k1::new();
    let val = curve
       .parse_public_key("020F031CA83F3FB372BD6C2430119E0B947CF059D19CDEA98F4CEFFEF620C584F9")
       .unwrap();
-   assert_eq!(val.to_uncompressed(), "040F031CA83F3FB372BD6C2430119E0B947CF059D19CDEA98F4CEFFEF620C584F9F064F1FDE4BC07D4F48C5114680AD1ADAF5F6EAA2166F7E4B4887703A681B548".to_lowercase())
+   assert_eq!(
+      val.to_uncompressed(),
+      "040F031CA83F3FB372BD6C2430119E0B947CF059D19CDEA98F4CEFFEF620C584F9\
+       F064F1FDE4BC07D4F48C5114680AD1ADAF5F6EAA2166F7E4B4887703A681B548"
+         .to_lowercase()
+   )
+}
+
+#[test]
+fn parse_public_key_rejects_malformed_keys() {
+   let curve = super::Secp256k1::new();
+   let x = "0f031ca83f3fb372bd6c2430119e0b947cf059d19cdea98f4ceffef620c584f9";
+   let keys = vec![
+      String::new(),
+      "00ff".to_string(),
+      format!("02+{}", &x[1..]),
+      format!("02-{}", &x[1..]),
+      format!("02{}_{}", &x[..31], &x[32..]),
+      // A multi-byte character across the boundary of the coordinates
+      format!("04a{}a", "é".repeat(63)),
+   ];
+   for key in keys {
+      assert!(curve.parse_public_key(key.as_str()).is_err(), "{}", key);
+   }
+   for key in ["00".to_string(), ECCValue::Infinity.to_uncompressed()] {
+      assert_eq!(curve.parse_public_key(key).unwrap(), ECCValue::Infinity);
+   }
 }
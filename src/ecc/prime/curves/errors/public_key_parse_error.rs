@@ -1,4 +1,6 @@
-use std::{error, fmt};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
 
 #[derive(Debug, Clone)]
 // Define our error types. These may be customized for our error handling cases.
@@ -18,10 +20,11 @@ impl fmt::Display for PublicKeyParseError {
 }
 
 // This is important for other errors to wrap this one.
+#[cfg(feature = "std")]
 impl error::Error for PublicKeyParseError {
    fn description(&self) -> &str { "String wasn't public key" }
 
-   fn cause(&self) -> Option<&error::Error> {
+   fn cause(&self) -> Option<&dyn error::Error> {
       // Generic error, underlying cause isn't tracked.
       None
    }
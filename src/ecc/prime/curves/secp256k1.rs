@@ -1,12 +1,11 @@
-extern crate num;
-
-use self::num::{BigInt, Num};
+use num::{BigInt, Num};
 
 use super::super::ECCurvePoint;
 use super::super::points;
 use self::points::Point;
 use self::points::affine::{AffineCoordinates, NewPoint};
 use super::super::curves::{ECCurve, ECCurveCalculation};
+use super::super::ecdsa::ECDSA;
 
 /// filed `p` where `E: y2 = x3 + ax + b over Fp`
 const P: &str = "FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFE_FFFFFC2F";
@@ -16,6 +15,8 @@ const A: &str = "00000000_00000000_00000000_00000000_00000000_00000000_00000000_
 const B: &str = "00000000_00000000_00000000_00000000_00000000_00000000_00000000_00000007";
 /// order `n`
 const N: &str = "FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFE_BAAEDCE6_AF48A03B_BFD25E8C_D0364141";
+/// cofactor `h`
+const H: &str = "01";
 ///Base point `G` in uncompressed form.
 #[allow(dead_code)]
 const G: &str = "04_79BE667E_F9DCBBAC_55A06295_CE870B07_029BFCDB_2DCE28D9_59F2815B_16F81798_483ADA77_26A3C465_5DA4FBFC_0E1108A8_FD17B448_A6855419_9C47D08F_FB10D4B8";
@@ -32,25 +33,31 @@ impl Secp256k1 {
    #[inline]
    fn p() -> BigInt {
       // We know this will succeed.
-      return BigInt::from_str_radix(P, 16).unwrap();
+      BigInt::from_str_radix(P, 16).unwrap()
    }
 
    #[inline]
    fn a() -> BigInt {
       // We know this will succeed.
-      return BigInt::from_str_radix(A, 16).unwrap();
+      BigInt::from_str_radix(A, 16).unwrap()
    }
 
    #[inline]
    fn b() -> BigInt {
       // We know this will succeed.
-      return BigInt::from_str_radix(B, 16).unwrap();
+      BigInt::from_str_radix(B, 16).unwrap()
    }
 
    #[inline]
    fn n() -> BigInt {
       // We know this will succeed.
-      return BigInt::from_str_radix(N, 16).unwrap();
+      BigInt::from_str_radix(N, 16).unwrap()
+   }
+
+   #[inline]
+   fn h() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(H, 16).unwrap()
    }
 
    #[inline]
@@ -62,30 +69,39 @@ impl Secp256k1 {
 
 impl ECCurve for Secp256k1 {
    #[inline]
-   fn new() -> Secp256k1 { return Secp256k1 {}; }
+   fn new() -> Secp256k1 { Secp256k1 {} }
 
    #[inline]
    fn name(&self) -> &str { "Secp256k1" }
 
    #[inline]
-   fn p(&self) -> BigInt { return Self::p(); }
+   fn p(&self) -> BigInt { Self::p() }
+
+   #[inline]
+   fn a(&self) -> BigInt { Self::a() }
 
    #[inline]
-   fn a(&self) -> BigInt { return Self::a(); }
+   fn b(&self) -> BigInt { Self::b() }
 
    #[inline]
-   fn b(&self) -> BigInt { return Self::b(); }
+   fn n(&self) -> BigInt { Self::n() }
 
    #[inline]
-   fn n(&self) -> BigInt { return Self::n(); }
+   fn h(&self) -> BigInt { Self::h() }
 
    #[inline]
-   fn base_point(&self) -> AffineCoordinates { return Self::base_point(); }
+   fn base_point(&self) -> AffineCoordinates { Self::base_point() }
 }
 
 impl<P: Point> ECCurvePoint<P> for Secp256k1 {}
 // impl ECCurvePoint<point::affine::AffineCoordinates> for Secp256k1 {}
 // impl ECCurvePoint<point::jacobian::JacobianCoordinates> for Secp256k1 {}
 
+impl ECCurveCalculation<points::AffineCoordinates> for Secp256k1 {}
 impl ECCurveCalculation<points::JacobianCoordinates> for Secp256k1 {}
 impl ECCurveCalculation<points::StandardProjectiveCoordinates> for Secp256k1 {}
+impl ECCurveCalculation<points::ChudnovskyJacobianCoordinates> for Secp256k1 {}
+impl ECCurveCalculation<points::CompleteProjectiveCoordinates> for Secp256k1 {}
+impl ECCurveCalculation<points::ModifiedJacobianCoordinates> for Secp256k1 {}
+
+impl ECDSA for Secp256k1 {}
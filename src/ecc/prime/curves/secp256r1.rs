@@ -1,12 +1,11 @@
-extern crate num;
-
-use self::num::{BigInt, Num};
+use num::{BigInt, Num};
 
 use super::super::ECCurvePoint;
 use super::super::points;
 use self::points::Point;
 use self::points::affine::{AffineCoordinates, NewPoint};
 use super::super::curves::{ECCurve, ECCurveCalculation};
+use super::super::ecdsa::ECDSA;
 
 /// filed `p` where `E: y2 = x3 + ax + b over Fp`
 const P: &str = "FFFFFFFF_00000001_00000000_00000000_00000000_FFFFFFFF_FFFFFFFF_FFFFFFFF";
@@ -16,6 +15,8 @@ const A: &str = "FFFFFFFF_00000001_00000000_00000000_00000000_FFFFFFFF_FFFFFFFF_
 const B: &str = "5AC635D8_AA3A93E7_B3EBBD55_769886BC_651D06B0_CC53B0F6_3BCE3C3E_27D2604B";
 /// order `n`
 const N: &str = "FFFFFFFF_00000000_FFFFFFFF_FFFFFFFF_BCE6FAAD_A7179E84_F3B9CAC2_FC632551";
+/// cofactor `h`
+const H: &str = "01";
 ///Base point `G` in uncompressed form.
 #[allow(dead_code)]
 const G: &str = "046B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C2964FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5";
@@ -32,25 +33,31 @@ impl Secp256r1 {
    #[inline]
    fn p() -> BigInt {
       // We know this will succeed.
-      return BigInt::from_str_radix(P, 16).unwrap();
+      BigInt::from_str_radix(P, 16).unwrap()
    }
 
    #[inline]
    fn a() -> BigInt {
       // We know this will succeed.
-      return BigInt::from_str_radix(A, 16).unwrap();
+      BigInt::from_str_radix(A, 16).unwrap()
    }
 
    #[inline]
    fn b() -> BigInt {
       // We know this will succeed.
-      return BigInt::from_str_radix(B, 16).unwrap();
+      BigInt::from_str_radix(B, 16).unwrap()
    }
 
    #[inline]
    fn n() -> BigInt {
       // We know this will succeed.
-      return BigInt::from_str_radix(N, 16).unwrap();
+      BigInt::from_str_radix(N, 16).unwrap()
+   }
+
+   #[inline]
+   fn h() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(H, 16).unwrap()
    }
 
    #[inline]
@@ -62,30 +69,39 @@ impl Secp256r1 {
 
 impl ECCurve for Secp256r1 {
    #[inline]
-   fn new() -> Secp256r1 { return Secp256r1 {}; }
+   fn new() -> Secp256r1 { Secp256r1 {} }
 
    #[inline]
    fn name(&self) -> &str { "Secp256r1" }
 
    #[inline]
-   fn p(&self) -> BigInt { return Self::p(); }
+   fn p(&self) -> BigInt { Self::p() }
+
+   #[inline]
+   fn a(&self) -> BigInt { Self::a() }
 
    #[inline]
-   fn a(&self) -> BigInt { return Self::a(); }
+   fn b(&self) -> BigInt { Self::b() }
 
    #[inline]
-   fn b(&self) -> BigInt { return Self::b(); }
+   fn n(&self) -> BigInt { Self::n() }
 
    #[inline]
-   fn n(&self) -> BigInt { return Self::n(); }
+   fn h(&self) -> BigInt { Self::h() }
 
    #[inline]
-   fn base_point(&self) -> AffineCoordinates { return Self::base_point(); }
+   fn base_point(&self) -> AffineCoordinates { Self::base_point() }
 }
 
 impl<P: Point> ECCurvePoint<P> for Secp256r1 {}
 // impl ECCurvePoint<point::affine::AffineCoordinates> for Secp256r1 {}
 // impl ECCurvePoint<point::jacobian::JacobianCoordinates> for Secp256r1 {}
 
+impl ECCurveCalculation<points::AffineCoordinates> for Secp256r1 {}
 impl ECCurveCalculation<points::JacobianCoordinates> for Secp256r1 {}
 impl ECCurveCalculation<points::StandardProjectiveCoordinates> for Secp256r1 {}
+impl ECCurveCalculation<points::ChudnovskyJacobianCoordinates> for Secp256r1 {}
+impl ECCurveCalculation<points::CompleteProjectiveCoordinates> for Secp256r1 {}
+impl ECCurveCalculation<points::ModifiedJacobianCoordinates> for Secp256r1 {}
+
+impl ECDSA for Secp256r1 {}
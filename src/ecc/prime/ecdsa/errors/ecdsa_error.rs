@@ -0,0 +1,52 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+#[derive(Debug, Clone, PartialEq)]
+// Returned when a signature can not be created, verified or used to recover a
+// public key, or the public key itself is invalid.
+pub enum ECDSAError {
+   /// `r` or `s` was zero.
+   InvalidSignature,
+   /// The signature does not match the message and public key.
+   VerificationFailed,
+   /// The recovery id does not describe a point `R` on the curve.
+   InvalidRecoveryId,
+   /// The calculation resulted in the point at infinity.
+   Infinity,
+   /// The public key is not a point of order `n` on the curve.
+   InvalidPublicKey,
+}
+
+impl fmt::Display for ECDSAError {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         ECDSAError::InvalidSignature => write!(f, "Signature has a zero component"),
+         ECDSAError::VerificationFailed => write!(f, "Signature does not match"),
+         ECDSAError::InvalidRecoveryId => {
+            write!(f, "Recovery id does not match a point on the curve")
+         },
+         ECDSAError::Infinity => write!(f, "The value is infinity"),
+         ECDSAError::InvalidPublicKey => write!(f, "Public key is not a valid curve point"),
+      }
+   }
+}
+
+// This is important for other errors to wrap this one.
+#[cfg(feature = "std")]
+impl error::Error for ECDSAError {
+   fn description(&self) -> &str {
+      match *self {
+         ECDSAError::InvalidSignature => "Signature has a zero component",
+         ECDSAError::VerificationFailed => "Signature does not match",
+         ECDSAError::InvalidRecoveryId => "Recovery id does not match a point on the curve",
+         ECDSAError::Infinity => "The value is infinity",
+         ECDSAError::InvalidPublicKey => "Public key is not a valid curve point",
+      }
+   }
+
+   fn cause(&self) -> Option<&dyn error::Error> {
+      // Generic error, underlying cause isn't tracked.
+      None
+   }
+}
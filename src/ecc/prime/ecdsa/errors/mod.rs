@@ -0,0 +1,3 @@
+mod ecdsa_error;
+
+pub use self::ecdsa_error::ECDSAError;
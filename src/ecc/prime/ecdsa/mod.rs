@@ -0,0 +1,316 @@
+use num::bigint::Sign;
+use num::{BigInt, Integer, Signed, ToPrimitive};
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::curves::{ECCurve, ECCurveCalculation};
+use super::ECCurvePoint;
+use super::points::{AffineCoordinates, JacobianCoordinates, PointFrom};
+use super::scalar::errors::ScalarDecodeError;
+use super::scalar::Scalar;
+use super::super::ECCValueRes;
+
+pub mod errors;
+
+use self::errors::ECDSAError;
+
+/// An ECDSA signature `(r, s)` on the curve `C`, SEC 1 4.1.
+pub struct Signature<C: ECCurve> {
+   pub r: Scalar<C>,
+   pub s: Scalar<C>,
+}
+
+impl<C: ECCurve> Signature<C> {
+   pub fn new(r: Scalar<C>, s: Scalar<C>) -> Self { Signature { r, s } }
+
+   /// Encode as `r || s`, each of `Scalar::byte_len()` bytes.
+   pub fn to_bytes(&self) -> Vec<u8> {
+      let mut bytes = self.r.to_bytes_be();
+      bytes.extend(self.s.to_bytes_be());
+      bytes
+   }
+
+   /// Decode `r || s`, each of `Scalar::byte_len()` bytes.
+   pub fn from_bytes(bytes: &[u8]) -> Result<Self, ScalarDecodeError> {
+      let len = Scalar::<C>::byte_len();
+      if bytes.len() != 2 * len {
+         return Err(ScalarDecodeError::InvalidLength {
+            expected: 2 * len,
+            found:    bytes.len(),
+         });
+      }
+
+      let r = Scalar::from_bytes_be(&bytes[..len])?;
+      let s = Scalar::from_bytes_be(&bytes[len..])?;
+      Ok(Signature { r, s })
+   }
+
+   /// Encode as the DER `SEQUENCE { r INTEGER, s INTEGER }`, SEC 1 C.8.
+   pub fn to_der(&self) -> Vec<u8> {
+      let mut content = der_integer(self.r.value());
+      content.extend(der_integer(self.s.value()));
+
+      let mut der = vec![0x30];
+      if content.len() >= 0x80 {
+         der.push(0x81);
+      }
+      der.push(content.len() as u8);
+      der.extend(content);
+      der
+   }
+
+   /// Decode the DER `SEQUENCE { r INTEGER, s INTEGER }`. BER leniencies,
+   /// like long form or indefinite lengths where the short form fits,
+   /// padded or negative integers and trailing bytes, are rejected, as are
+   /// `r` and `s` not in `[0, n)`.
+   pub fn from_der(bytes: &[u8]) -> Result<Self, ScalarDecodeError> {
+      let content = match *bytes {
+         [0x30, len, ref rest @ ..] if len < 0x80 && rest.len() == len as usize => rest,
+         [0x30, 0x81, len, ref rest @ ..] if len >= 0x80 && rest.len() == len as usize => rest,
+         _ => return Err(ScalarDecodeError::InvalidDER),
+      };
+      let (r, rest) = from_der_integer(content)?;
+      let (s, rest) = from_der_integer(rest)?;
+      if !rest.is_empty() {
+         return Err(ScalarDecodeError::InvalidDER);
+      }
+
+      let order = Scalar::<C>::order();
+      if r >= order || s >= order {
+         return Err(ScalarDecodeError::OutOfRange);
+      }
+      Ok(Signature {
+         r: Scalar::new(r),
+         s: Scalar::new(s),
+      })
+   }
+
+   /// Whether `s` is at most `n/2`, as Bitcoin requires.
+   pub fn is_low_s(&self) -> bool { self.s.value() <= &(Scalar::<C>::order() / 2) }
+
+   /// Return the signature with `s` replaced by `n - s` if it is high. Both
+   /// are valid, but the recovery id of the result has its parity flipped.
+   pub fn normalize_s(&self) -> Self {
+      if self.is_low_s() {
+         self.clone()
+      } else {
+         Signature {
+            r: self.r.clone(),
+            s: -&self.s,
+         }
+      }
+   }
+}
+
+/// The DER `INTEGER` of a non-negative `value`, with a leading zero byte if
+/// the high bit is set.
+fn der_integer(value: &BigInt) -> Vec<u8> {
+   let (_, mut bytes) = value.to_bytes_be();
+   if bytes[0] & 0x80 != 0 {
+      bytes.insert(0, 0);
+   }
+   let mut der = vec![0x02, bytes.len() as u8];
+   der.extend(bytes);
+   der
+}
+
+/// Decode a minimally encoded, non-negative DER `INTEGER` at the start of
+/// `bytes` and return it with the bytes following it.
+fn from_der_integer(bytes: &[u8]) -> Result<(BigInt, &[u8]), ScalarDecodeError> {
+   let (content, rest) = match *bytes {
+      [0x02, len, ref rest @ ..] if len < 0x80 && rest.len() >= len as usize => {
+         rest.split_at(len as usize)
+      },
+      _ => return Err(ScalarDecodeError::InvalidDER),
+   };
+   match *content {
+      // Empty, negative or padded with a zero byte which is not needed.
+      [] => Err(ScalarDecodeError::InvalidDER),
+      [first, ..] if first & 0x80 != 0 => Err(ScalarDecodeError::InvalidDER),
+      [0, second, ..] if second & 0x80 == 0 => Err(ScalarDecodeError::InvalidDER),
+      _ => Ok((BigInt::from_bytes_be(Sign::Plus, content), rest)),
+   }
+}
+
+/* -- Trait impls -- */
+impl<C: ECCurve> Clone for Signature<C> {
+   fn clone(&self) -> Self {
+      Signature {
+         r: self.r.clone(),
+         s: self.s.clone(),
+      }
+   }
+}
+
+impl<C: ECCurve> PartialEq for Signature<C> {
+   fn eq(&self, other: &Self) -> bool { self.r == other.r && self.s == other.s }
+}
+
+impl<C: ECCurve> Eq for Signature<C> {}
+
+impl<C: ECCurve> fmt::Debug for Signature<C> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "Signature(r: {:x}, s: {:x})", self.r, self.s)
+   }
+}
+/* -- Trait impls -- */
+
+/// ECDSA over the curve, following SEC 1 4.1. Points are calculated in
+/// Jacobian coordinates.
+///
+/// `hash` is always the hash of the message, it is converted to an integer as
+/// in SEC 1 4.1.3 step 5.
+///
+/// ### Example
+///
+/// ```
+/// use ecc_calc::ecc::prime::curves::{ECCurve, Secp256k1};
+/// use ecc_calc::ecc::prime::ecdsa::errors::ECDSAError;
+/// use ecc_calc::ecc::prime::ecdsa::ECDSA;
+/// use ecc_calc::ecc::prime::scalar::Scalar;
+/// use num::BigInt;
+///
+/// # fn main() -> Result<(), ECDSAError> {
+/// # let curve = Secp256k1::new();
+/// # let hash = [0x5a; 32];
+/// # let d = Scalar::new(BigInt::from(0x1234_5678_u64));
+/// # let k = Scalar::new(BigInt::from(0x0bad_cafe_u64));
+/// let (signature, recovery_id) = curve.sign_with_nonce(&d, &hash, &k)?;
+/// let public_key = curve.recover_public_key(&hash, &signature, recovery_id)?;
+/// curve.verify(&public_key, &hash, &signature)?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait ECDSA: ECCurveCalculation<JacobianCoordinates> + ECCurvePoint<AffineCoordinates> {
+   /// Return the leftmost `log2(n)` bits of `hash` as an integer modulo `n`.
+   fn hash_to_scalar(&self, hash: &[u8]) -> Scalar<Self> {
+      let e = BigInt::from_bytes_be(Sign::Plus, hash);
+      let hash_bits = hash.len() * 8;
+      let n_bits = self.n().bits() as usize;
+      if hash_bits > n_bits {
+         Scalar::new(e >> (hash_bits - n_bits))
+      } else {
+         Scalar::new(e)
+      }
+   }
+
+   /// Sign `hash` with the private key `d` and the nonce `k`, which must be
+   /// secret and never reused. Return the signature and its recovery id.
+   fn sign_with_nonce(
+      &self,
+      d: &Scalar<Self>,
+      hash: &[u8],
+      k: &Scalar<Self>,
+   ) -> Result<(Signature<Self>, u8), ECDSAError> {
+      let n = self.n();
+      let point_r = self.multipy_point(&base_point(self), k.clone());
+      let point_r = AffineCoordinates::convert_from(&point_r, self).ok_or(ECDSAError::Infinity)?;
+
+      let r = Scalar::new(point_r.x.clone());
+      let e = self.hash_to_scalar(hash);
+      let s = match k.invert() {
+         Some(k_inv) => &k_inv * &(&e + &(&r * d)),
+         None => return Err(ECDSAError::InvalidSignature),
+      };
+      if r.is_zero() || s.is_zero() {
+         return Err(ECDSAError::InvalidSignature);
+      }
+
+      // bit 0: parity of y, higher bits: how often n was subtracted from x.
+      let (overflow, _) = point_r.x.div_rem(&n);
+      let overflow = overflow.to_u8().filter(|j| *j < 0x80).ok_or(ECDSAError::InvalidRecoveryId)?;
+      let recovery_id = overflow << 1 | if point_r.y.is_odd() { 1 } else { 0 };
+      Ok((Signature { r, s }, recovery_id))
+   }
+
+   /// Verify `signature` of `hash` against `public_key`, which is validated
+   /// first as in SEC 1 3.2.2.1.
+   fn verify(
+      &self,
+      public_key: &AffineCoordinates,
+      hash: &[u8],
+      signature: &Signature<Self>,
+   ) -> Result<(), ECDSAError> {
+      let Signature { ref r, ref s } = *signature;
+      let s_inv = s.invert().ok_or(ECDSAError::InvalidSignature)?;
+      if r.is_zero() {
+         return Err(ECDSAError::InvalidSignature);
+      }
+
+      // R = (e/s)G + (r/s)Q
+      let u1 = &self.hash_to_scalar(hash) * &s_inv;
+      let u2 = r * &s_inv;
+      let point_q = JacobianCoordinates::convert_from(public_key, self).unwrap_finite();
+      if !is_valid_public_key(self, public_key, &point_q) {
+         return Err(ECDSAError::InvalidPublicKey);
+      }
+      let point_r = self.add_points(
+         &self.multipy_point(&base_point(self), u1),
+         &self.multipy_point(&point_q, u2),
+      );
+
+      match AffineCoordinates::convert_from(&point_r, self) {
+         ECCValueRes::Finite(ref point) if &Scalar::new(point.x.clone()) == r => Ok(()),
+         _ => Err(ECDSAError::VerificationFailed),
+      }
+   }
+
+   /// Recover the public key from `signature` of `hash`, SEC 1 4.1.6.
+   ///
+   /// Bit 0 of `recovery_id` is the parity of the y coordinate of `R`, the
+   /// other bits are `j` where the x coordinate of `R` is `r + j * n`.
+   fn recover_public_key(
+      &self,
+      hash: &[u8],
+      signature: &Signature<Self>,
+      recovery_id: u8,
+   ) -> Result<AffineCoordinates, ECDSAError> {
+      let Signature { ref r, ref s } = *signature;
+      let r_inv = r.invert().ok_or(ECDSAError::InvalidSignature)?;
+      if s.is_zero() {
+         return Err(ECDSAError::InvalidSignature);
+      }
+
+      // x may have overflowed n, in which case it is r + j * n < p.
+      let j = BigInt::from(recovery_id >> 1);
+      let x = r.value() + j * self.n();
+      let point_r = self
+         .decompress_point(&x, recovery_id & 1 == 1)
+         .ok_or(ECDSAError::InvalidRecoveryId)?;
+      let point_r = JacobianCoordinates::convert_from(&point_r, self).unwrap_finite();
+      if !self.is_in_subgroup(&point_r) {
+         return Err(ECDSAError::InvalidRecoveryId);
+      }
+
+      // Q = r^-1 (sR - eG)
+      let u1 = -&(&self.hash_to_scalar(hash) * &r_inv);
+      let u2 = s * &r_inv;
+      let point_q = self.add_points(
+         &self.multipy_point(&base_point(self), u1),
+         &self.multipy_point(&point_r, u2),
+      );
+
+      AffineCoordinates::convert_from(&point_q, self).ok_or(ECDSAError::Infinity)
+   }
+}
+
+fn base_point<C: ECCurve>(curve: &C) -> JacobianCoordinates {
+   JacobianCoordinates::convert_from(&curve.base_point(), curve).unwrap_finite()
+}
+
+/// Whether `point`, which is `point_q` in Jacobian coordinates, has both
+/// coordinates in `[0, p)`, is on the curve and in the subgroup of order `n`.
+fn is_valid_public_key<C: ECDSA>(
+   curve: &C,
+   point: &AffineCoordinates,
+   point_q: &JacobianCoordinates,
+) -> bool {
+   let p = curve.p();
+   let in_field = |v: &BigInt| !v.is_negative() && v < &p;
+   in_field(&point.x)
+      && in_field(&point.y)
+      && curve.point_is_on_curve(point)
+      && curve.is_in_subgroup(point_q)
+}
@@ -0,0 +1,45 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+#[derive(Debug, Clone, PartialEq)]
+// Returned when an ECIES message can not be encrypted or decrypted.
+pub enum ECIESError {
+   /// The private key or the ephemeral key is zero.
+   InvalidPrivateKey,
+   /// The public key is not on the curve or not in the subgroup of order `n`.
+   InvalidPublicKey,
+   /// The ciphertext is too short, or its ephemeral public key is invalid.
+   InvalidCiphertext,
+   /// The tag does not match the ciphertext.
+   TagMismatch,
+}
+
+impl fmt::Display for ECIESError {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         ECIESError::InvalidPrivateKey => write!(f, "Private key is out of range"),
+         ECIESError::InvalidPublicKey => write!(f, "Public key is invalid"),
+         ECIESError::InvalidCiphertext => write!(f, "Ciphertext is malformed"),
+         ECIESError::TagMismatch => write!(f, "Tag does not match"),
+      }
+   }
+}
+
+// This is important for other errors to wrap this one.
+#[cfg(feature = "std")]
+impl error::Error for ECIESError {
+   fn description(&self) -> &str {
+      match *self {
+         ECIESError::InvalidPrivateKey => "Private key is out of range",
+         ECIESError::InvalidPublicKey => "Public key is invalid",
+         ECIESError::InvalidCiphertext => "Ciphertext is malformed",
+         ECIESError::TagMismatch => "Tag does not match",
+      }
+   }
+
+   fn cause(&self) -> Option<&dyn error::Error> {
+      // Generic error, underlying cause isn't tracked.
+      None
+   }
+}
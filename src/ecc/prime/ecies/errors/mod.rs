@@ -0,0 +1,3 @@
+mod ecies_error;
+
+pub use self::ecies_error::ECIESError;
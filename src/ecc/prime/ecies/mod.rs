@@ -0,0 +1,254 @@
+//! The Elliptic Curve Integrated Encryption Scheme, SEC 1 5.1.
+//! @see http://www.secg.org/sec1-v2.pdf
+//!
+//! A ciphertext is `R || EM || D`, the uncompressed ephemeral public key
+//! `R = kG`, the encrypted message and the tag. The encryption and MAC keys
+//! are derived from the x coordinate of `kQ = dR`.
+//!
+//! ### Example
+//!
+//! ```
+//! use ecc_calc::ecc::prime::curves::{ECCurve, ECCurveCalculation, Secp256k1};
+//! use ecc_calc::ecc::prime::ecies::errors::ECIESError;
+//! use ecc_calc::ecc::prime::ecies::{ECIES, AES128CTR, HMACSHA256, X963KDF};
+//! use ecc_calc::ecc::prime::scalar::Scalar;
+//!
+//! # fn main() -> Result<(), ECIESError> {
+//! # let curve = Secp256k1::new();
+//! # let mut rng = rand::thread_rng();
+//! # let d = Scalar::random(&mut rng);
+//! # let public_key = curve.multipy_point(&curve.base_point(), d.clone()).unwrap_finite();
+//! # let message = b"message";
+//! let ecies = ECIES::new(X963KDF, AES128CTR, HMACSHA256);
+//! let ciphertext = ecies.encrypt(&curve, &mut rng, &public_key, message)?;
+//! let message = ecies.decrypt(&curve, &d, &ciphertext)?;
+//! # assert_eq!(message, b"message");
+//! # Ok(())
+//! # }
+//! ```
+
+use num::bigint::Sign;
+use num::BigInt;
+use rand::Rng;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::curves::ECCurveCalculation;
+use super::points::{AffineCoordinates, JacobianCoordinates, PointFrom};
+use super::scalar::Scalar;
+use super::ECCurvePoint;
+
+pub mod errors;
+mod primitives;
+
+pub use self::primitives::{Cipher, AES128CTR, HMACSHA256, KDF, MAC, X963KDF, XORCipher};
+
+use self::errors::ECIESError;
+
+/// The scheme with a choice of KDF, cipher and MAC. `shared_info1` is passed
+/// to the KDF and `shared_info2` is appended to the encrypted message for
+/// the MAC, both are empty unless set with `with_shared_info`.
+#[derive(Debug, Clone, Default)]
+pub struct ECIES<K: KDF, E: Cipher, M: MAC> {
+   pub kdf: K,
+   pub cipher: E,
+   pub mac: M,
+   shared_info1: Vec<u8>,
+   shared_info2: Vec<u8>,
+}
+
+impl<K: KDF, E: Cipher, M: MAC> ECIES<K, E, M> {
+   pub fn new(kdf: K, cipher: E, mac: M) -> Self {
+      ECIES {
+         kdf,
+         cipher,
+         mac,
+         shared_info1: Vec::new(),
+         shared_info2: Vec::new(),
+      }
+   }
+
+   pub fn with_shared_info(mut self, shared_info1: &[u8], shared_info2: &[u8]) -> Self {
+      self.shared_info1 = shared_info1.to_vec();
+      self.shared_info2 = shared_info2.to_vec();
+      self
+   }
+
+   /// Encrypt `message` to `public_key` with a random ephemeral key.
+   pub fn encrypt<C, R: Rng>(
+      &self,
+      curve: &C,
+      rng: &mut R,
+      public_key: &AffineCoordinates,
+      message: &[u8],
+   ) -> Result<Vec<u8>, ECIESError>
+   where
+      C: ECCurveCalculation<JacobianCoordinates> + ECCurvePoint<AffineCoordinates>,
+   {
+      // `Scalar::random` is never zero.
+      self.encrypt_with_ephemeral_key(curve, &Scalar::random(rng), public_key, message)
+   }
+
+   /// Encrypt `message` to `public_key` with the ephemeral private key `k`,
+   /// which must be secret and never reused. Fixing `k` gives deterministic
+   /// ciphertexts for testing.
+   pub fn encrypt_with_ephemeral_key<C>(
+      &self,
+      curve: &C,
+      k: &Scalar<C>,
+      public_key: &AffineCoordinates,
+      message: &[u8],
+   ) -> Result<Vec<u8>, ECIESError>
+   where
+      C: ECCurveCalculation<JacobianCoordinates> + ECCurvePoint<AffineCoordinates>,
+   {
+      if k.is_zero() {
+         return Err(ECIESError::InvalidPrivateKey);
+      }
+      let point_q = validate_public_key(curve, public_key).ok_or(ECIESError::InvalidPublicKey)?;
+
+      let point_g = JacobianCoordinates::convert_from(&curve.base_point(), curve).unwrap_finite();
+      // kG is never the point at infinity for 0 < k < n.
+      let point_r = curve.multipy_point(&point_g, k.clone());
+      let point_r = AffineCoordinates::convert_from(&point_r, curve).unwrap_finite();
+      let z = shared_secret(curve, &point_q, k).ok_or(ECIESError::InvalidPublicKey)?;
+
+      let (encryption_key, mac_key) = self.derive_keys(&z, message.len());
+      let encrypted = self.cipher.encrypt(&encryption_key, message);
+      let tag = self.tag(&mac_key, &encrypted);
+
+      let mut ciphertext = encode_point(curve, &point_r);
+      ciphertext.extend(encrypted);
+      ciphertext.extend(tag);
+      Ok(ciphertext)
+   }
+
+   /// Decrypt `ciphertext` with the private key `d`.
+   pub fn decrypt<C>(
+      &self,
+      curve: &C,
+      d: &Scalar<C>,
+      ciphertext: &[u8],
+   ) -> Result<Vec<u8>, ECIESError>
+   where
+      C: ECCurveCalculation<JacobianCoordinates> + ECCurvePoint<AffineCoordinates>,
+   {
+      if d.is_zero() {
+         return Err(ECIESError::InvalidPrivateKey);
+      }
+
+      let point_len = 1 + 2 * field_len(curve);
+      let tag_len = self.mac.tag_len();
+      if ciphertext.len() < point_len + tag_len {
+         return Err(ECIESError::InvalidCiphertext);
+      }
+      let (encoded_r, rest) = ciphertext.split_at(point_len);
+      let (encrypted, tag) = rest.split_at(rest.len() - tag_len);
+
+      let point_r = decode_point(curve, encoded_r)
+         .and_then(|point| validate_public_key(curve, &point))
+         .ok_or(ECIESError::InvalidCiphertext)?;
+      let z = shared_secret(curve, &point_r, d).ok_or(ECIESError::InvalidCiphertext)?;
+
+      let (encryption_key, mac_key) = self.derive_keys(&z, encrypted.len());
+      if !constant_time_eq(&self.tag(&mac_key, encrypted), tag) {
+         return Err(ECIESError::TagMismatch);
+      }
+      Ok(self.cipher.decrypt(&encryption_key, encrypted))
+   }
+
+   /// `EK || MK = KDF(z, shared_info1)`
+   fn derive_keys(&self, z: &[u8], message_len: usize) -> (Vec<u8>, Vec<u8>) {
+      let encryption_key_len = self.cipher.key_len(message_len);
+      let mut keys = self
+         .kdf
+         .derive(z, &self.shared_info1, encryption_key_len + self.mac.key_len());
+      let mac_key = keys.split_off(encryption_key_len);
+      (keys, mac_key)
+   }
+
+   /// `MAC(MK, EM || shared_info2)`
+   fn tag(&self, mac_key: &[u8], encrypted: &[u8]) -> Vec<u8> {
+      let mut data = encrypted.to_vec();
+      data.extend_from_slice(&self.shared_info2);
+      self.mac.tag(mac_key, &data)
+   }
+}
+
+/// Return the byte length of a field element.
+fn field_len<C: ECCurveCalculation<JacobianCoordinates>>(curve: &C) -> usize {
+   (curve.p().bits() as usize).div_ceil(8)
+}
+
+/// `x` as a big-endian integer of `field_len` bytes.
+fn encode_field_element<C>(curve: &C, x: &BigInt) -> Vec<u8>
+where
+   C: ECCurveCalculation<JacobianCoordinates>,
+{
+   let (_, bytes) = x.to_bytes_be();
+   let mut encoded = vec![0_u8; field_len(curve) - bytes.len()];
+   encoded.extend(bytes);
+   encoded
+}
+
+/// The uncompressed encoding `04 || x || y`, SEC 1 2.3.3.
+fn encode_point<C>(curve: &C, point: &AffineCoordinates) -> Vec<u8>
+where
+   C: ECCurveCalculation<JacobianCoordinates>,
+{
+   let mut encoded = vec![0x04];
+   encoded.extend(encode_field_element(curve, &point.x));
+   encoded.extend(encode_field_element(curve, &point.y));
+   encoded
+}
+
+fn decode_point<C>(curve: &C, bytes: &[u8]) -> Option<AffineCoordinates>
+where
+   C: ECCurveCalculation<JacobianCoordinates>,
+{
+   let len = field_len(curve);
+   if bytes.len() != 1 + 2 * len || bytes[0] != 0x04 {
+      return None;
+   }
+   Some(AffineCoordinates {
+      x: BigInt::from_bytes_be(Sign::Plus, &bytes[1..1 + len]),
+      y: BigInt::from_bytes_be(Sign::Plus, &bytes[1 + len..]),
+   })
+}
+
+/// Check the public key as in SEC 1 3.2.2.1: coordinates in `[0, p)`, on the
+/// curve and in the subgroup of order `n`.
+fn validate_public_key<C>(curve: &C, point: &AffineCoordinates) -> Option<JacobianCoordinates>
+where
+   C: ECCurveCalculation<JacobianCoordinates> + ECCurvePoint<AffineCoordinates>,
+{
+   let p = curve.p();
+   let in_range = |v: &BigInt| v.sign() != Sign::Minus && v < &p;
+   if !in_range(&point.x) || !in_range(&point.y) || !curve.point_is_on_curve(point) {
+      return None;
+   }
+
+   let point = JacobianCoordinates::convert_from(point, curve).unwrap_finite();
+   if curve.is_in_subgroup(&point) {
+      Some(point)
+   } else {
+      None
+   }
+}
+
+/// The Elliptic Curve Diffie-Hellman primitive, SEC 1 3.3.1: the encoded x
+/// coordinate of `d * point`, `None` for the point at infinity.
+fn shared_secret<C>(curve: &C, point: &JacobianCoordinates, d: &Scalar<C>) -> Option<Vec<u8>>
+where
+   C: ECCurveCalculation<JacobianCoordinates>,
+{
+   let point_p = AffineCoordinates::convert_from(&curve.multipy_point(point, d.clone()), curve);
+   Option::from(point_p).map(|point: AffineCoordinates| encode_field_element(curve, &point.x))
+}
+
+/// Compare the tags without returning early.
+fn constant_time_eq(former: &[u8], latter: &[u8]) -> bool {
+   former.len() == latter.len()
+      && former.iter().zip(latter).fold(0_u8, |acc, (f, l)| acc | (f ^ l)) == 0
+}
@@ -0,0 +1,102 @@
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use alloc::vec::Vec;
+
+/// Key derivation function, deriving the encryption and MAC keys from the
+/// shared secret, SEC 1 3.6.
+pub trait KDF {
+   /// Return `len` bytes derived from the shared secret `z` and `shared_info`.
+   fn derive(&self, z: &[u8], shared_info: &[u8], len: usize) -> Vec<u8>;
+}
+
+/// Symmetric encryption scheme, SEC 1 3.8. Every key is used only once.
+pub trait Cipher {
+   /// Return the key length for a message of `message_len` bytes.
+   fn key_len(&self, message_len: usize) -> usize;
+
+   fn encrypt(&self, key: &[u8], message: &[u8]) -> Vec<u8>;
+
+   fn decrypt(&self, key: &[u8], ciphertext: &[u8]) -> Vec<u8>;
+}
+
+/// Message authentication code, SEC 1 3.7.
+pub trait MAC {
+   fn key_len(&self) -> usize;
+
+   fn tag_len(&self) -> usize;
+
+   fn tag(&self, key: &[u8], data: &[u8]) -> Vec<u8>;
+}
+
+/// ANSI-X9.63-KDF with SHA-256, `SHA256(z || counter || shared_info)` for
+/// the 32 bit big-endian counters `1, 2, ...`, concatenated.
+#[derive(Debug, Clone, Default)]
+pub struct X963KDF;
+
+impl KDF for X963KDF {
+   fn derive(&self, z: &[u8], shared_info: &[u8], len: usize) -> Vec<u8> {
+      let mut key = Vec::with_capacity(len);
+      let mut counter = 1_u32;
+      while key.len() < len {
+         let mut hasher = Sha256::new();
+         hasher.update(z);
+         hasher.update(counter.to_be_bytes());
+         hasher.update(shared_info);
+         key.extend(hasher.finalize());
+         counter += 1;
+      }
+      key.truncate(len);
+      key
+   }
+}
+
+/// The XOR encryption scheme, the key is as long as the message.
+#[derive(Debug, Clone, Default)]
+pub struct XORCipher;
+
+impl Cipher for XORCipher {
+   fn key_len(&self, message_len: usize) -> usize { message_len }
+
+   fn encrypt(&self, key: &[u8], message: &[u8]) -> Vec<u8> {
+      message.iter().zip(key).map(|(m, k)| m ^ k).collect()
+   }
+
+   fn decrypt(&self, key: &[u8], ciphertext: &[u8]) -> Vec<u8> { self.encrypt(key, ciphertext) }
+}
+
+/// AES-128 in CTR mode, with an all-zero initial counter block as the key is
+/// never reused.
+#[derive(Debug, Clone, Default)]
+pub struct AES128CTR;
+
+impl Cipher for AES128CTR {
+   fn key_len(&self, _: usize) -> usize { 16 }
+
+   fn encrypt(&self, key: &[u8], message: &[u8]) -> Vec<u8> {
+      let mut buffer = message.to_vec();
+      let mut cipher = ctr::Ctr128BE::<aes::Aes128>::new(key.into(), &[0_u8; 16].into());
+      cipher.apply_keystream(&mut buffer);
+      buffer
+   }
+
+   fn decrypt(&self, key: &[u8], ciphertext: &[u8]) -> Vec<u8> { self.encrypt(key, ciphertext) }
+}
+
+/// HMAC-SHA-256 with a 32 byte key and the full 32 byte tag.
+#[derive(Debug, Clone, Default)]
+pub struct HMACSHA256;
+
+impl MAC for HMACSHA256 {
+   fn key_len(&self) -> usize { 32 }
+
+   fn tag_len(&self) -> usize { 32 }
+
+   fn tag(&self, key: &[u8], data: &[u8]) -> Vec<u8> {
+      // HMAC takes keys of any length.
+      let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+      mac.update(data);
+      mac.finalize().into_bytes().to_vec()
+   }
+}
@@ -0,0 +1,184 @@
+//! Ed25519 signatures on edwards25519, RFC 8032 5.1.
+//! @see https://tools.ietf.org/html/rfc8032#section-5.1
+//!
+//! ### Example
+//!
+//! ```
+//! use ecc_calc::ecc::prime::edwards::ed25519;
+//! use ecc_calc::ecc::prime::edwards::errors::EdDSAError;
+//!
+//! # fn main() -> Result<(), EdDSAError> {
+//! # let secret_key = [0x42_u8; 32];
+//! # let message = b"message";
+//! let public_key = ed25519::public_key(&secret_key);
+//! let signature = ed25519::sign(&secret_key, message);
+//! ed25519::verify(&public_key, message, &signature)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use num::bigint::Sign;
+use num::{BigInt, Integer, Signed};
+use sha2::{Digest, Sha512};
+
+use super::super::points::AffineCoordinates;
+use super::errors::EdDSAError;
+use super::{Edwards25519, ExtendedCoordinates, TwistedEdwardsCurve};
+
+/// The encoded point `A`, together with the decoded point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+   point: AffineCoordinates,
+   encoded: [u8; 32],
+}
+
+impl PublicKey {
+   /// Decode the 32 byte point encoding.
+   pub fn from_bytes(bytes: &[u8]) -> Result<Self, EdDSAError> {
+      let point = Edwards25519::new()
+         .decode_point(bytes)
+         .ok_or(EdDSAError::InvalidPointEncoding)?;
+      let mut encoded = [0_u8; 32];
+      encoded.copy_from_slice(bytes);
+      Ok(PublicKey { point, encoded })
+   }
+
+   pub fn to_bytes(&self) -> [u8; 32] { self.encoded }
+
+   pub fn point(&self) -> &AffineCoordinates { &self.point }
+}
+
+/// An Ed25519 signature, the encoded point `R` and `S`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+   pub r: [u8; 32],
+   pub s: BigInt,
+}
+
+impl Signature {
+   /// Decode `ENC(R) || ENC(S)`, rejecting `S >= n`. `R` is decoded during
+   /// verification.
+   pub fn from_bytes(bytes: &[u8]) -> Result<Self, EdDSAError> {
+      if bytes.len() != 64 {
+         return Err(EdDSAError::InvalidSignature);
+      }
+
+      let mut r = [0_u8; 32];
+      r.copy_from_slice(&bytes[..32]);
+      let s = BigInt::from_bytes_le(Sign::Plus, &bytes[32..]);
+      if s >= Edwards25519::new().n() {
+         return Err(EdDSAError::InvalidSignature);
+      }
+      Ok(Signature { r, s })
+   }
+
+   pub fn to_bytes(&self) -> [u8; 64] {
+      let mut bytes = [0_u8; 64];
+      bytes[..32].copy_from_slice(&self.r);
+      bytes[32..].copy_from_slice(&le_bytes(&self.s));
+      bytes
+   }
+}
+
+/// Return the public key of the 32 byte `secret_key`, RFC 8032 5.1.5.
+pub fn public_key(secret_key: &[u8; 32]) -> PublicKey {
+   let (s, _) = expand_secret_key(secret_key);
+   let curve = Edwards25519::new();
+   let point = multipy_base_point(&curve, s);
+   PublicKey {
+      encoded: encode(&curve, &point),
+      point,
+   }
+}
+
+/// Sign `message` with the 32 byte `secret_key`, RFC 8032 5.1.6.
+pub fn sign(secret_key: &[u8; 32], message: &[u8]) -> Signature {
+   let curve = Edwards25519::new();
+   let n = curve.n();
+   let (s, prefix) = expand_secret_key(secret_key);
+   let public_key = public_key(secret_key);
+
+   let r = hash_to_scalar(&[&prefix, message], &n);
+   let r_encoded = encode(&curve, &multipy_base_point(&curve, r.clone()));
+   let k = hash_to_scalar(&[&r_encoded, &public_key.encoded, message], &n);
+
+   Signature {
+      r: r_encoded,
+      s: (r + k * s).mod_floor(&n),
+   }
+}
+
+/// Verify `signature` of `message` against `public_key`, RFC 8032 5.1.7,
+/// with the cofactored equation `[8][S]B = [8]R + [8][k]A`. `S` has to be in
+/// `[0, n)`, as `Signature::from_bytes` checks, since the fields are public.
+pub fn verify(
+   public_key: &PublicKey,
+   message: &[u8],
+   signature: &Signature,
+) -> Result<(), EdDSAError> {
+   let curve = Edwards25519::new();
+   if signature.s.is_negative() || signature.s >= curve.n() {
+      return Err(EdDSAError::InvalidSignature);
+   }
+   let point_r = curve
+      .decode_point(&signature.r)
+      .ok_or(EdDSAError::InvalidSignature)?;
+   let k = hash_to_scalar(&[&signature.r, &public_key.encoded, message], &curve.n());
+
+   let left = curve.to_extended(&multipy_base_point(&curve, signature.s.clone()));
+   let right = curve.add_points(
+      &curve.to_extended(&point_r),
+      &curve.multipy_point(&curve.to_extended(&public_key.point), k),
+   );
+   let difference = curve.multipy_point(&curve.subtract_points(&left, &right), curve.h());
+
+   if curve.points_equal(&difference, &ExtendedCoordinates::identity()) {
+      Ok(())
+   } else {
+      Err(EdDSAError::VerificationFailed)
+   }
+}
+
+/// Hash the secret key with SHA-512, and return the clamped scalar `s` from
+/// the first half and the prefix from the second half.
+fn expand_secret_key(secret_key: &[u8; 32]) -> (BigInt, [u8; 32]) {
+   let hash = Sha512::digest(secret_key);
+
+   let mut scalar = [0_u8; 32];
+   scalar.copy_from_slice(&hash[..32]);
+   scalar[0] &= 248;
+   scalar[31] &= 127;
+   scalar[31] |= 64;
+
+   let mut prefix = [0_u8; 32];
+   prefix.copy_from_slice(&hash[32..]);
+   (BigInt::from_bytes_le(Sign::Plus, &scalar), prefix)
+}
+
+/// `SHA-512(data[0] || data[1] || ...)` as a little-endian integer modulo `n`.
+fn hash_to_scalar(data: &[&[u8]], n: &BigInt) -> BigInt {
+   let mut hasher = Sha512::new();
+   for bytes in data {
+      hasher.update(bytes);
+   }
+   BigInt::from_bytes_le(Sign::Plus, &hasher.finalize()).mod_floor(n)
+}
+
+fn multipy_base_point(curve: &Edwards25519, k: BigInt) -> AffineCoordinates {
+   let point_b = curve.to_extended(&curve.base_point());
+   curve.to_affine(&curve.multipy_point(&point_b, k))
+}
+
+fn encode(curve: &Edwards25519, point: &AffineCoordinates) -> [u8; 32] {
+   let mut encoded = [0_u8; 32];
+   encoded.copy_from_slice(&curve.encode_point(point));
+   encoded
+}
+
+/// Encode a value below `2^256` as 32 little-endian bytes.
+fn le_bytes(value: &BigInt) -> [u8; 32] {
+   let (_, bytes) = value.to_bytes_le();
+   let mut encoded = [0_u8; 32];
+   encoded[..bytes.len()].copy_from_slice(&bytes);
+   encoded
+}
@@ -0,0 +1,89 @@
+use num::{BigInt, Num};
+
+use super::super::points::affine::{AffineCoordinates, NewPoint};
+use super::TwistedEdwardsCurve;
+
+/// filed `p` where `E: ax^2 + y^2 = 1 + dx^2y^2 over Fp`, `2^255 - 19`
+const P: &str = "7FFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFED";
+/// const `a` where `E: ax^2 + y^2 = 1 + dx^2y^2 over Fp`, `-1`
+const A: &str = "-01";
+/// const `d` where `E: ax^2 + y^2 = 1 + dx^2y^2 over Fp`, `-121665/121666`
+const D: &str = "52036CEE_2B6FFE73_8CC74079_7779E898_00700A4D_4141D8AB_75EB4DCA_135978A3";
+/// order `n`, `2^252 + 27742317777372353535851937790883648493`
+const N: &str = "10000000_00000000_00000000_00000000_14DEF9DE_A2F79CD6_5812631A_5CF5D3ED";
+/// cofactor `h`
+const H: &str = "08";
+#[allow(non_upper_case_globals)]
+const Bx: &str = "216936D3_CD6E53FE_C0A4E231_FDD6DC5C_692CC760_9525A7B2_C9562D60_8F25D51A";
+#[allow(non_upper_case_globals)]
+const By: &str = "66666666_66666666_66666666_66666666_66666666_66666666_66666666_66666658";
+
+/// ## Edwards25519
+/// The twisted Edwards curve birationally equivalent to Curve25519, used by
+/// Ed25519.
+/// @see https://tools.ietf.org/html/rfc8032#section-5.1
+pub struct Edwards25519 {}
+
+impl Edwards25519 {
+   #[inline]
+   fn p() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(P, 16).unwrap()
+   }
+
+   #[inline]
+   fn a() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(A, 16).unwrap()
+   }
+
+   #[inline]
+   fn d() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(D, 16).unwrap()
+   }
+
+   #[inline]
+   fn n() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(N, 16).unwrap()
+   }
+
+   #[inline]
+   fn h() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(H, 16).unwrap()
+   }
+
+   #[inline]
+   fn base_point() -> AffineCoordinates {
+      // We know this will succeed.
+      AffineCoordinates::try_new(Bx, By, 16).unwrap()
+   }
+}
+
+impl TwistedEdwardsCurve for Edwards25519 {
+   #[inline]
+   fn new() -> Edwards25519 { Edwards25519 {} }
+
+   #[inline]
+   fn name(&self) -> &str { "Edwards25519" }
+
+   #[inline]
+   fn p(&self) -> BigInt { Self::p() }
+
+   #[inline]
+   fn a(&self) -> BigInt { Self::a() }
+
+   #[inline]
+   fn d(&self) -> BigInt { Self::d() }
+
+   #[inline]
+   fn n(&self) -> BigInt { Self::n() }
+
+   #[inline]
+   fn h(&self) -> BigInt { Self::h() }
+
+   #[inline]
+   fn base_point(&self) -> AffineCoordinates { Self::base_point() }
+}
@@ -0,0 +1,41 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+#[derive(Debug, Clone, PartialEq)]
+// Returned when an EdDSA key or signature can not be decoded or verified.
+pub enum EdDSAError {
+   /// The bytes do not encode a point on the curve.
+   InvalidPointEncoding,
+   /// The signature has the wrong length, or `S` is not smaller than `n`.
+   InvalidSignature,
+   /// The signature does not match the message and public key.
+   VerificationFailed,
+}
+
+impl fmt::Display for EdDSAError {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         EdDSAError::InvalidPointEncoding => write!(f, "Point encoding is invalid"),
+         EdDSAError::InvalidSignature => write!(f, "Signature is malformed"),
+         EdDSAError::VerificationFailed => write!(f, "Signature does not match"),
+      }
+   }
+}
+
+// This is important for other errors to wrap this one.
+#[cfg(feature = "std")]
+impl error::Error for EdDSAError {
+   fn description(&self) -> &str {
+      match *self {
+         EdDSAError::InvalidPointEncoding => "Point encoding is invalid",
+         EdDSAError::InvalidSignature => "Signature is malformed",
+         EdDSAError::VerificationFailed => "Signature does not match",
+      }
+   }
+
+   fn cause(&self) -> Option<&dyn error::Error> {
+      // Generic error, underlying cause isn't tracked.
+      None
+   }
+}
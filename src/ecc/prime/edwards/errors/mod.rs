@@ -0,0 +1,3 @@
+mod eddsa_error;
+
+pub use self::eddsa_error::EdDSAError;
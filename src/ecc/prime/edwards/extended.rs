@@ -0,0 +1,98 @@
+use num::{BigInt, Integer, One, Zero};
+
+use core::fmt;
+
+use super::super::points::{AffineCoordinates, Point};
+
+#[derive(Debug, Clone)]
+/// Extended Coordinates are used to represent points on twisted Edwards curves
+/// `ax^2 + y^2 = 1 + dx^2y^2`, as `(X, Y, Z, T)` with `x = X/Z`, `y = Y/Z` and
+/// `xy = T/Z`.
+/// @see https://eprint.iacr.org/2008/522
+pub struct ExtendedCoordinates {
+   pub x: BigInt,
+   pub y: BigInt,
+   pub z: BigInt,
+   pub t: BigInt,
+}
+
+impl ExtendedCoordinates {
+   /// Return the neutral element `(0, 1)`, which is a regular point on Edwards
+   /// curves.
+   pub fn identity() -> Self {
+      ExtendedCoordinates {
+         x: BigInt::zero(),
+         y: BigInt::one(),
+         z: BigInt::one(),
+         t: BigInt::zero(),
+      }
+   }
+
+   pub fn from_affine(point: &AffineCoordinates, p: &BigInt) -> Self {
+      ExtendedCoordinates {
+         x: point.x.clone(),
+         y: point.y.clone(),
+         z: BigInt::one(),
+         t: (&point.x * &point.y).mod_floor(p),
+      }
+   }
+
+   /// (X, Y, Z, T) -> (X/Z, Y/Z) over `Fp`. `Z` is never zero for points on
+   /// the curve.
+   pub fn to_affine(&self, p: &BigInt) -> AffineCoordinates {
+      let inv = self.z.modpow(&(p - BigInt::from(2_u8)), p);
+      AffineCoordinates {
+         x: (&self.x * &inv).mod_floor(p),
+         y: (&self.y * &inv).mod_floor(p),
+      }
+   }
+}
+
+/* -- Formatter impls -- */
+impl fmt::Display for ExtendedCoordinates {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "ExtendedCoordinates(x: ")?;
+      self.x.fmt(f)?;
+      write!(f, ", y: ")?;
+      self.y.fmt(f)?;
+      write!(f, ", z: ")?;
+      self.z.fmt(f)?;
+      write!(f, ", t: ")?;
+      self.t.fmt(f)?;
+      write!(f, ")")?;
+      Ok(())
+   }
+}
+
+impl fmt::LowerHex for ExtendedCoordinates {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "ExtendedCoordinates(x: ")?;
+      self.x.fmt(f)?;
+      write!(f, ", y: ")?;
+      self.y.fmt(f)?;
+      write!(f, ", z: ")?;
+      self.z.fmt(f)?;
+      write!(f, ", t: ")?;
+      self.t.fmt(f)?;
+      write!(f, ")")?;
+      Ok(())
+   }
+}
+
+impl fmt::UpperHex for ExtendedCoordinates {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "ExtendedCoordinates(x: ")?;
+      self.x.fmt(f)?;
+      write!(f, ", y: ")?;
+      self.y.fmt(f)?;
+      write!(f, ", z: ")?;
+      self.z.fmt(f)?;
+      write!(f, ", t: ")?;
+      self.t.fmt(f)?;
+      write!(f, ")")?;
+      Ok(())
+   }
+}
+/* -- Formatter impls -- */
+
+impl Point for ExtendedCoordinates {}
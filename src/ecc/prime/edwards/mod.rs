@@ -0,0 +1,223 @@
+use num::bigint::Sign;
+use num::{BigInt, Integer, One, Signed, Zero};
+
+use alloc::vec::Vec;
+
+use super::field::sqrt;
+use super::points::AffineCoordinates;
+
+pub mod ed25519;
+mod edwards25519;
+pub mod errors;
+mod extended;
+
+pub use self::edwards25519::Edwards25519;
+pub use self::extended::ExtendedCoordinates;
+
+/// Twisted Edwards curves `E: ax^2 + y^2 = 1 + dx^2y^2 over Fp`.
+///
+/// Unlike on short Weierstrass curves, the neutral element `(0, 1)` is a
+/// regular point, and with `a` a square and `d` a non-square in `Fp` the
+/// addition formulas have no exceptional cases.
+///
+/// ### Example
+///
+/// ```
+/// use ecc_calc::ecc::prime::edwards::{Edwards25519, ExtendedCoordinates, TwistedEdwardsCurve};
+/// use num::BigInt;
+///
+/// # fn main() {
+/// # let k = BigInt::from(0x1234);
+/// let curve = Edwards25519::new();
+/// let point_B = ExtendedCoordinates::from_affine(&curve.base_point(), &curve.p());
+/// let encoded = curve.encode_point(&curve.to_affine(&curve.multipy_point(&point_B, k)));
+/// # assert_eq!(encoded.len(), 32);
+/// # }
+/// ```
+pub trait TwistedEdwardsCurve: Sized {
+   /// Return an copy of the curve.
+   fn new() -> Self;
+
+   /// Return the curve friendly name.
+   fn name(&self) -> &str;
+
+   /// Return the field `p` value where `E: ax^2 + y^2 = 1 + dx^2y^2 over Fp`
+   fn p(&self) -> BigInt;
+
+   /// Return the `a` value where `E: ax^2 + y^2 = 1 + dx^2y^2 over Fp`
+   fn a(&self) -> BigInt;
+
+   /// Return the `d` value where `E: ax^2 + y^2 = 1 + dx^2y^2 over Fp`
+   fn d(&self) -> BigInt;
+
+   /// Return the order `n` of the base point.
+   fn n(&self) -> BigInt;
+
+   /// Return the cofactor `h`, where `h * n` is the number of points on the
+   /// curve.
+   fn h(&self) -> BigInt;
+
+   /// Return the `AffineCoordinates` representing the base point of the given
+   /// curve.
+   fn base_point(&self) -> AffineCoordinates;
+
+   fn point_is_on_curve(&self, point: &AffineCoordinates) -> bool {
+      let p = self.p();
+      let x2 = (&point.x * &point.x).mod_floor(&p);
+      let y2 = (&point.y * &point.y).mod_floor(&p);
+
+      let left = (self.a() * &x2 + &y2).mod_floor(&p);
+      let right = (self.d() * x2 * y2 + BigInt::one()).mod_floor(&p);
+      left == right
+   }
+
+   fn to_extended(&self, point: &AffineCoordinates) -> ExtendedCoordinates {
+      ExtendedCoordinates::from_affine(point, &self.p())
+   }
+
+   fn to_affine(&self, point: &ExtendedCoordinates) -> AffineCoordinates {
+      point.to_affine(&self.p())
+   }
+
+   /// Unified addition, add-2008-hwcd. It also doubles.
+   #[allow(non_snake_case)]
+   fn add_points(
+      &self,
+      former: &ExtendedCoordinates,
+      latter: &ExtendedCoordinates,
+   ) -> ExtendedCoordinates {
+      let p = self.p();
+
+      let A = (&former.x * &latter.x).mod_floor(&p);
+      let B = (&former.y * &latter.y).mod_floor(&p);
+      let C = (&former.t * self.d() * &latter.t).mod_floor(&p);
+      let D = (&former.z * &latter.z).mod_floor(&p);
+      let E = ((&former.x + &former.y) * (&latter.x + &latter.y) - &A - &B).mod_floor(&p);
+      let F = (&D - &C).mod_floor(&p);
+      let G = (&D + &C).mod_floor(&p);
+      let H = (&B - self.a() * &A).mod_floor(&p);
+
+      ExtendedCoordinates {
+         x: (&E * &F).mod_floor(&p),
+         y: (&G * &H).mod_floor(&p),
+         z: (&F * &G).mod_floor(&p),
+         t: (&E * &H).mod_floor(&p),
+      }
+   }
+
+   /// dbl-2008-hwcd
+   #[allow(non_snake_case)]
+   fn double_point(&self, point: &ExtendedCoordinates) -> ExtendedCoordinates {
+      let p = self.p();
+      let TWO = BigInt::from(2_u8);
+
+      let A = (&point.x * &point.x).mod_floor(&p);
+      let B = (&point.y * &point.y).mod_floor(&p);
+      let C = (&point.z * &point.z * &TWO).mod_floor(&p);
+      let D = (self.a() * &A).mod_floor(&p);
+      let E = ((&point.x + &point.y) * (&point.x + &point.y) - &A - &B).mod_floor(&p);
+      let G = (&D + &B).mod_floor(&p);
+      let F = (&G - &C).mod_floor(&p);
+      let H = (&D - &B).mod_floor(&p);
+
+      ExtendedCoordinates {
+         x: (&E * &F).mod_floor(&p),
+         y: (&G * &H).mod_floor(&p),
+         z: (&F * &G).mod_floor(&p),
+         t: (&E * &H).mod_floor(&p),
+      }
+   }
+
+   /// `-(x, y) = (-x, y)`
+   fn negate_point(&self, point: &ExtendedCoordinates) -> ExtendedCoordinates {
+      let p = self.p();
+      ExtendedCoordinates {
+         x: (-&point.x).mod_floor(&p),
+         y: point.y.clone(),
+         z: point.z.clone(),
+         t: (-&point.t).mod_floor(&p),
+      }
+   }
+
+   fn subtract_points(
+      &self,
+      former: &ExtendedCoordinates,
+      latter: &ExtendedCoordinates,
+   ) -> ExtendedCoordinates {
+      self.add_points(former, &self.negate_point(latter))
+   }
+
+   /// Double-and-add from the most significant bit. `k` is not reduced, so
+   /// clamped scalars which are larger than `n` work as well.
+   fn multipy_point(&self, point: &ExtendedCoordinates, k: BigInt) -> ExtendedCoordinates {
+      if k.is_negative() {
+         return self.multipy_point(&self.negate_point(point), -k);
+      }
+
+      let mut result = ExtendedCoordinates::identity();
+      let (_, bytes) = k.to_bytes_be();
+      for byte in bytes {
+         for i in (0..8).rev() {
+            result = self.double_point(&result);
+            if (byte >> i) & 1 == 1 {
+               result = self.add_points(&result, point);
+            }
+         }
+      }
+      result
+   }
+
+   /// Compare `X1/Z1 == X2/Z2` and `Y1/Z1 == Y2/Z2` by cross multiplication.
+   fn points_equal(&self, former: &ExtendedCoordinates, latter: &ExtendedCoordinates) -> bool {
+      let p = self.p();
+      (&former.x * &latter.z - &latter.x * &former.z).mod_floor(&p).is_zero()
+         && (&former.y * &latter.z - &latter.y * &former.z).mod_floor(&p).is_zero()
+   }
+
+   /// Return the byte length of an encoded point, enough for `y` and one bit
+   /// of `x`.
+   fn encoded_len(&self) -> usize { (self.p().bits() as usize + 1).div_ceil(8) }
+
+   /// Encode `y` in little-endian, with the least significant bit of `x` in
+   /// the most significant bit of the last byte, RFC 8032 5.1.2.
+   fn encode_point(&self, point: &AffineCoordinates) -> Vec<u8> {
+      let (_, mut bytes) = point.y.to_bytes_le();
+      bytes.resize(self.encoded_len(), 0);
+      if point.x.is_odd() {
+         let last = bytes.len() - 1;
+         bytes[last] |= 0x80;
+      }
+      bytes
+   }
+
+   /// Decode a point encoded by `encode_point`, RFC 8032 5.1.3. Returns
+   /// `None` for non-canonical `y` and for points not on the curve.
+   fn decode_point(&self, bytes: &[u8]) -> Option<AffineCoordinates> {
+      if bytes.len() != self.encoded_len() {
+         return None;
+      }
+
+      let p = self.p();
+      let mut bytes = bytes.to_vec();
+      let last = bytes.len() - 1;
+      let x_is_odd = bytes[last] & 0x80 != 0;
+      bytes[last] &= 0x7f;
+      let y = BigInt::from_bytes_le(Sign::Plus, &bytes);
+      if y >= p {
+         return None;
+      }
+
+      // x^2 = (y^2 - 1) / (dy^2 - a)
+      let y2 = (&y * &y).mod_floor(&p);
+      let u = (&y2 - BigInt::one()).mod_floor(&p);
+      let v = (self.d() * &y2 - self.a()).mod_floor(&p);
+      let v_inv = v.modpow(&(&p - BigInt::from(2_u8)), &p);
+      let x = sqrt(&(u * v_inv).mod_floor(&p), &p)?;
+
+      if x.is_zero() && x_is_odd {
+         return None;
+      }
+      let x = if x.is_odd() == x_is_odd { x } else { &p - x };
+      Some(AffineCoordinates { x, y })
+   }
+}
@@ -0,0 +1,74 @@
+use num::{BigInt, Integer, One, Zero};
+
+/// Return `1/v mod p`, as `v^(p - 2) mod p` for the prime `p`. Zero has no
+/// inverse and is mapped to zero.
+pub fn inverse(v: &BigInt, p: &BigInt) -> BigInt {
+   v.mod_floor(p).modpow(&(p - BigInt::from(2_u8)), p)
+}
+
+/// Whether `v` is a square modulo the odd prime `p`, by Euler's criterion.
+pub fn is_square(v: &BigInt, p: &BigInt) -> bool {
+   let v = v.mod_floor(p);
+   v.is_zero() || v.modpow(&((p - BigInt::one()) / BigInt::from(2_u8)), p).is_one()
+}
+
+/// Return the square root of `v` modulo the odd prime `p` which is at most
+/// `(p - 1) / 2`, or `None` if `v` is not a square. The other root is
+/// `p - sqrt(v)`.
+///
+/// `p = 3 mod 4` takes a single exponentiation, any other `p` is handled by
+/// Tonelli-Shanks.
+pub fn sqrt(v: &BigInt, p: &BigInt) -> Option<BigInt> {
+   let v = v.mod_floor(p);
+   if !is_square(&v, p) {
+      return None;
+   }
+
+   #[allow(non_snake_case)]
+   let TWO = BigInt::from(2_u8);
+   let root = if p.mod_floor(&BigInt::from(4_u8)) == BigInt::from(3_u8) {
+      v.modpow(&((p + BigInt::one()) / BigInt::from(4_u8)), p)
+   } else {
+      // p - 1 = q * 2^s with q odd
+      let mut q = p - BigInt::one();
+      let mut s = 0_usize;
+      while q.is_even() {
+         q /= &TWO;
+         s += 1;
+      }
+
+      // Any non-square z
+      let mut z = TWO.clone();
+      while is_square(&z, p) {
+         z += BigInt::one();
+      }
+
+      let mut m = s;
+      let mut c = z.modpow(&q, p);
+      let mut t = v.modpow(&q, p);
+      let mut r = v.modpow(&((&q + BigInt::one()) / &TWO), p);
+      while !t.is_one() && !t.is_zero() {
+         // The least i with t^(2^i) = 1, which is smaller than m.
+         let mut i = 0_usize;
+         let mut t2i = t.clone();
+         while !t2i.is_one() {
+            t2i = (&t2i * &t2i).mod_floor(p);
+            i += 1;
+         }
+
+         let b = c.modpow(&(BigInt::one() << (m - i - 1)), p);
+         m = i;
+         c = (&b * &b).mod_floor(p);
+         t = (t * &c).mod_floor(p);
+         r = (r * b).mod_floor(p);
+      }
+      if t.is_zero() {
+         BigInt::zero()
+      } else {
+         r
+      }
+   };
+
+   let other = (p - &root).mod_floor(p);
+   Some(if root <= other { root } else { other })
+}
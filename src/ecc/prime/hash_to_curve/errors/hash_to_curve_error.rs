@@ -0,0 +1,39 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+#[derive(Debug, Clone, PartialEq)]
+// Returned when a message can not be hashed to the curve.
+pub enum HashToCurveError {
+   /// `expand_message_xmd` was asked for more than `255 * 32` or `65535`
+   /// bytes.
+   InvalidLength,
+   /// The two mapped points added up to the point at infinity, which only
+   /// happens with negligible probability.
+   Infinity,
+}
+
+impl fmt::Display for HashToCurveError {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         HashToCurveError::InvalidLength => write!(f, "Requested length is too large"),
+         HashToCurveError::Infinity => write!(f, "Result is the point at infinity"),
+      }
+   }
+}
+
+// This is important for other errors to wrap this one.
+#[cfg(feature = "std")]
+impl error::Error for HashToCurveError {
+   fn description(&self) -> &str {
+      match *self {
+         HashToCurveError::InvalidLength => "Requested length is too large",
+         HashToCurveError::Infinity => "Result is the point at infinity",
+      }
+   }
+
+   fn cause(&self) -> Option<&dyn error::Error> {
+      // Generic error, underlying cause isn't tracked.
+      None
+   }
+}
@@ -0,0 +1,3 @@
+mod hash_to_curve_error;
+
+pub use self::hash_to_curve_error::HashToCurveError;
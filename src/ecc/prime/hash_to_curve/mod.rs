@@ -0,0 +1,180 @@
+//! Hashing to elliptic curves, RFC 9380, with the random oracle suites
+//! `P256_XMD:SHA-256_SSWU_RO_` and `secp256k1_XMD:SHA-256_SSWU_RO_`.
+//! @see https://www.rfc-editor.org/rfc/rfc9380
+//!
+//! ### Example
+//!
+//! ```
+//! use ecc_calc::ecc::prime::curves::{ECCurve, Secp256r1};
+//! use ecc_calc::ecc::prime::hash_to_curve::errors::HashToCurveError;
+//! use ecc_calc::ecc::prime::hash_to_curve::HashToCurve;
+//! use ecc_calc::ecc::prime::ECCurvePoint;
+//!
+//! # fn main() -> Result<(), HashToCurveError> {
+//! let curve = Secp256r1::new();
+//! let dst = format!("MY-APP-V01-CS01-with-{}", curve.suite_id());
+//! let point = curve.hash_to_curve(b"message", dst.as_bytes())?;
+//! # assert!(curve.point_is_on_curve(&point));
+//! # Ok(())
+//! # }
+//! ```
+
+use num::bigint::Sign;
+use num::{BigInt, Integer, One, Zero};
+use sha2::{Digest, Sha256};
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::curves::ECCurveCalculation;
+use super::field;
+use super::points::{AffineCoordinates, JacobianCoordinates, PointCalculation, PointFrom};
+
+pub mod errors;
+mod secp256k1;
+mod secp256r1;
+
+use self::errors::HashToCurveError;
+
+/// The security level `k` of both suites, in bits.
+const SECURITY_BITS: usize = 128;
+
+/// `expand_message_xmd` with SHA-256, RFC 9380 5.3.1. Returns `len` uniformly
+/// random bytes derived from `msg` and the domain separation tag `dst`.
+/// Tags longer than 255 bytes are hashed first, 5.3.3.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Result<Vec<u8>, HashToCurveError> {
+   const B_IN_BYTES: usize = 32;
+   const S_IN_BYTES: usize = 64;
+
+   let ell = len.div_ceil(B_IN_BYTES);
+   if ell > 255 || len > 0xffff {
+      return Err(HashToCurveError::InvalidLength);
+   }
+
+   let mut dst_prime = if dst.len() > 255 {
+      let mut hasher = Sha256::new();
+      hasher.update(b"H2C-OVERSIZE-DST-");
+      hasher.update(dst);
+      hasher.finalize().to_vec()
+   } else {
+      dst.to_vec()
+   };
+   let dst_len = dst_prime.len() as u8;
+   dst_prime.push(dst_len);
+
+   let mut hasher = Sha256::new();
+   hasher.update([0_u8; S_IN_BYTES]);
+   hasher.update(msg);
+   hasher.update((len as u16).to_be_bytes());
+   hasher.update([0_u8]);
+   hasher.update(&dst_prime);
+   let b_0 = hasher.finalize();
+
+   let mut uniform_bytes = Vec::with_capacity(ell * B_IN_BYTES);
+   let mut b_i = vec![0_u8; B_IN_BYTES];
+   for i in 1..=ell {
+      // b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime), b_1 has no b_0
+      let mut hasher = Sha256::new();
+      let xored: Vec<u8> = b_0.iter().zip(&b_i).map(|(l, r)| l ^ r).collect();
+      hasher.update(if i == 1 { &b_0[..] } else { &xored[..] });
+      hasher.update([i as u8]);
+      hasher.update(&dst_prime);
+      b_i = hasher.finalize().to_vec();
+      uniform_bytes.extend_from_slice(&b_i);
+   }
+   uniform_bytes.truncate(len);
+   Ok(uniform_bytes)
+}
+
+/// Hashing to the curve with `expand_message_xmd` (SHA-256) and the simplified
+/// Shallue-van de Woestijne-Ulas map. Curves with `a = 0` or `b = 0` map to
+/// an isogenous curve `E': y^2 = x^3 + A'x + B'` first and override
+/// `sswu_a`, `sswu_b` and `iso_map`, RFC 9380 6.6.3.
+///
+/// The calculation is not constant time.
+pub trait HashToCurve: ECCurveCalculation<JacobianCoordinates> {
+   /// Return the suite ID, e.g. `P256_XMD:SHA-256_SSWU_RO_`. RFC 9380 3.1
+   /// recommends ending the domain separation tag with it, as in
+   /// `MY-APP-V01-CS01-with-P256_XMD:SHA-256_SSWU_RO_`.
+   fn suite_id(&self) -> &str;
+
+   /// Return the non-square `Z` of the simplified SWU map.
+   fn sswu_z(&self) -> BigInt;
+
+   /// Return `A'` of the curve the simplified SWU map targets.
+   fn sswu_a(&self) -> BigInt { self.a() }
+
+   /// Return `B'` of the curve the simplified SWU map targets.
+   fn sswu_b(&self) -> BigInt { self.b() }
+
+   /// Map a point on `E'` to the curve.
+   fn iso_map(&self, point: AffineCoordinates) -> AffineCoordinates { point }
+
+   /// `hash_to_field`, RFC 9380 5.2: `count` elements of `Fp`, each from
+   /// `L = ceil((ceil(log2(p)) + k) / 8)` bytes of `expand_message_xmd`.
+   fn hash_to_field(
+      &self,
+      msg: &[u8],
+      dst: &[u8],
+      count: usize,
+   ) -> Result<Vec<BigInt>, HashToCurveError> {
+      let p = self.p();
+      let l = (p.bits() as usize + SECURITY_BITS).div_ceil(8);
+      let uniform_bytes = expand_message_xmd(msg, dst, count * l)?;
+      Ok(uniform_bytes
+         .chunks(l)
+         .map(|bytes| BigInt::from_bytes_be(Sign::Plus, bytes).mod_floor(&p))
+         .collect())
+   }
+
+   /// The simplified SWU map to `E'`, RFC 9380 6.6.2, followed by `iso_map`.
+   fn map_to_curve(&self, u: &BigInt) -> AffineCoordinates {
+      let p = self.p();
+      let a = self.sswu_a();
+      let b = self.sswu_b();
+      let z = self.sswu_z();
+      let g = |x: &BigInt| (x * x * x + &a * x + &b).mod_floor(&p);
+
+      // tv1 = 1 / (Z^2 u^4 + Z u^2), 0 if there is no inverse
+      let z_u2 = (&z * u * u).mod_floor(&p);
+      let tv1 = field::inverse(&(&z_u2 * &z_u2 + &z_u2), &p);
+      let x1 = if tv1.is_zero() {
+         // B / (Z A)
+         &b * field::inverse(&(&z * &a), &p)
+      } else {
+         // -B / A * (1 + tv1)
+         -&b * field::inverse(&a, &p) * (tv1 + BigInt::one())
+      };
+      let x1 = x1.mod_floor(&p);
+      let x2 = (&z_u2 * &x1).mod_floor(&p);
+
+      let (x, y) = match field::sqrt(&g(&x1), &p) {
+         Some(y) => (x1, y),
+         // gx1 * gx2 = Z^3 u^6 gx1^2 is a square times the non-square Z.
+         None => {
+            let y = field::sqrt(&g(&x2), &p).unwrap();
+            (x2, y)
+         },
+      };
+
+      // sgn0(y) = sgn0(u)
+      let y = if y.is_odd() == u.mod_floor(&p).is_odd() { y } else { (&p - y).mod_floor(&p) };
+      self.iso_map(AffineCoordinates { x, y })
+   }
+
+   /// `hash_to_curve`, RFC 9380 3: map two field elements, add the points
+   /// and clear the cofactor.
+   fn hash_to_curve(&self, msg: &[u8], dst: &[u8]) -> Result<AffineCoordinates, HashToCurveError> {
+      let u = self.hash_to_field(msg, dst, 2)?;
+      let mut points = u
+         .iter()
+         .map(|u| JacobianCoordinates::convert_from(&self.map_to_curve(u), self).unwrap_finite());
+      let point_q0 = points.next().unwrap();
+      let point_q1 = points.next().unwrap();
+
+      // `multipy_point` would reduce `h` modulo `n`.
+      let point_r = self.add_points(&point_q0, &point_q1);
+      let point_p = JacobianCoordinates::point_multipication(self, &point_r, self.h());
+      AffineCoordinates::convert_from(&point_p, self).ok_or(HashToCurveError::Infinity)
+   }
+}
@@ -0,0 +1,82 @@
+use num::{BigInt, Integer, Num};
+
+use super::super::curves::{ECCurve, Secp256k1};
+use super::super::field;
+use super::super::points::AffineCoordinates;
+use super::HashToCurve;
+
+/// `A'` of the isogenous curve `E': y^2 = x^3 + A'x + B'`, RFC 9380 8.7
+const ISO_A: &str = "3F8731AB_DD661ADC_A08A5558_F0F5D272_E953D363_CB6F0E5D_405447C0_1A444533";
+/// `B'` of the isogenous curve, 1771
+const ISO_B: &str = "06EB";
+
+/// The constants `k_(1,0) ... k_(1,3)` of the 3-isogeny map, RFC 9380 E.1
+const X_NUM: [&str; 4] = [
+   "8E38E38E_38E38E38_E38E38E3_8E38E38E_38E38E38_E38E38E3_8E38E38D_AAAAA8C7",
+   "07D3D4C8_0BC321D5_B9F315CE_A7FD44C5_D595D2FC_0BF63B92_DFFF1044_F17C6581",
+   "534C328D_23F234E6_E2A413DE_CA25CAEC_E4506144_037C4031_4ECBD0B5_3D9DD262",
+   "8E38E38E_38E38E38_E38E38E3_8E38E38E_38E38E38_E38E38E3_8E38E38D_AAAAA88C",
+];
+/// `k_(2,0)` and `k_(2,1)`, the denominator is monic.
+const X_DEN: [&str; 2] = [
+   "D3577119_3D94918A_9CA34CCB_B7B640DD_86CD4095_42F8487D_9FE6B745_781EB49B",
+   "EDADC6F6_4383DC1D_F7C4B2D5_1B542254_06D36B64_1F5E41BB_C52A5661_2A8C6D14",
+];
+/// `k_(3,0) ... k_(3,3)`
+const Y_NUM: [&str; 4] = [
+   "4BDA12F6_84BDA12F_684BDA12_F684BDA1_2F684BDA_12F684BD_A12F684B_8E38E23C",
+   "C75E0C32_D5CB7C0F_A9D0A54B_12A0A6D5_647AB046_D686DA6F_DFFC90FC_201D71A3",
+   "29A61946_91F91A73_715209EF_6512E576_722830A2_01BE2018_A765E85A_9ECEE931",
+   "2F684BDA_12F684BD_A12F684B_DA12F684_BDA12F68_4BDA12F6_84BDA12F_38E38D84",
+];
+/// `k_(4,0) ... k_(4,2)`, the denominator is monic.
+const Y_DEN: [&str; 3] = [
+   "FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFE_FFFFF93B",
+   "7A06534B_B8BDB49F_D5E9E663_2722C298_9467C1BF_C8E8D978_DFB425D2_685C2573",
+   "6484AA71_6545CA2C_F3A70C3F_A8FE337E_0A3D2116_2F0D6299_A7BF8192_BFD2A76F",
+];
+
+/// `secp256k1_XMD:SHA-256_SSWU_RO_`, RFC 9380 8.7. As `a = 0`, the simplified
+/// SWU map targets a 3-isogenous curve.
+impl HashToCurve for Secp256k1 {
+   fn suite_id(&self) -> &str { "secp256k1_XMD:SHA-256_SSWU_RO_" }
+
+   #[inline]
+   fn sswu_z(&self) -> BigInt { BigInt::from(-11) }
+
+   #[inline]
+   fn sswu_a(&self) -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(ISO_A, 16).unwrap()
+   }
+
+   #[inline]
+   fn sswu_b(&self) -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(ISO_B, 16).unwrap()
+   }
+
+   /// `x = x_num / x_den` and `y = y' * y_num / y_den`, with the polynomials
+   /// in `x'` of RFC 9380 E.1. The exceptional cases, where a denominator is
+   /// zero, are not reached from `map_to_curve`.
+   fn iso_map(&self, point: AffineCoordinates) -> AffineCoordinates {
+      let p = self.p();
+      // Horner's rule, `coefficients` from the constant term up.
+      let polynomial = |coefficients: &[&str], monic: bool| {
+         let mut value = if monic { BigInt::from(1) } else { BigInt::from(0) };
+         for coefficient in coefficients.iter().rev() {
+            // We know this will succeed.
+            let coefficient = BigInt::from_str_radix(coefficient, 16).unwrap();
+            value = (value * &point.x + coefficient).mod_floor(&p);
+         }
+         value
+      };
+
+      let x = polynomial(&X_NUM, false) * field::inverse(&polynomial(&X_DEN, true), &p);
+      let y = &point.y * polynomial(&Y_NUM, false) * field::inverse(&polynomial(&Y_DEN, true), &p);
+      AffineCoordinates {
+         x: x.mod_floor(&p),
+         y: y.mod_floor(&p),
+      }
+   }
+}
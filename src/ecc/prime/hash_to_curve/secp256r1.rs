@@ -0,0 +1,13 @@
+use num::BigInt;
+
+use super::super::curves::Secp256r1;
+use super::HashToCurve;
+
+/// `P256_XMD:SHA-256_SSWU_RO_`, RFC 9380 8.2. The simplified SWU map targets
+/// P-256 itself.
+impl HashToCurve for Secp256r1 {
+   fn suite_id(&self) -> &str { "P256_XMD:SHA-256_SSWU_RO_" }
+
+   #[inline]
+   fn sswu_z(&self) -> BigInt { BigInt::from(-10) }
+}
@@ -1,28 +1,49 @@
-extern crate num;
-
-use self::num::{BigInt, Integer};
+use num::{BigInt, Integer, Zero};
 use self::curves::ECCurve;
+use super::ECCValueRes;
 
+pub mod birational;
+pub mod curve_point;
 pub mod curves;
+pub mod ecdsa;
+pub mod ecies;
+pub mod edwards;
+pub mod field;
+pub mod hash_to_curve;
+pub mod montgomery;
 pub mod points;
+pub mod scalar;
+pub mod schnorr;
+pub mod trace;
 
 /// Functions for points on finite prime eccurves.
 /// ### Example
 ///
-/// ```ignore
-/// let jp: JacobianCoordinates = curve.convert_point_to::<JacobianCoordinates>(&point); 
+/// ```
+/// use ecc_calc::ecc::prime::curves::{ECCurve, Secp256k1};
+/// use ecc_calc::ecc::prime::points::JacobianCoordinates;
+/// use ecc_calc::ecc::prime::ECCurvePoint;
+///
+/// # fn main() {
+/// # let curve = Secp256k1::new();
+/// # let point = curve.base_point();
+/// let jp: JacobianCoordinates = curve.convert_point_to::<JacobianCoordinates>(&point).unwrap();
+/// assert!(curve.point_is_on_curve(&jp));
+/// # }
 /// ```
 pub trait ECCurvePoint<P: points::Point>: ECCurve {
+   /// Whether `point` satisfies the curve equation. The point at infinity is
+   /// on every curve.
    fn point_is_on_curve(&self, point: &P) -> bool
    where
       points::AffineCoordinates: points::PointFrom<P>,
    {
-      use self::points::AffineCoordinates;
+      use self::points::{AffineCoordinates, PointFrom};
 
-      let AffineCoordinates {
-         x: point_x,
-         y: point_y,
-      } = self.convert_point_to::<AffineCoordinates>(point).unwrap();
+      let (point_x, point_y) = match AffineCoordinates::convert_from(point, self) {
+         ECCValueRes::Finite(AffineCoordinates { x, y }) => (x, y),
+         ECCValueRes::Infinity => return true,
+      };
 
       #[allow(non_snake_case)]
       let TWO = BigInt::from(2_u8);
@@ -46,10 +67,50 @@ pub trait ECCurvePoint<P: points::Point>: ECCurve {
    }
 
    /// Type P to Type N
-   fn convert_point_to<N: points::Point>(&self, point: &P) -> Result<N, points::ConvertionError>
+   ///
+   /// Fails with `ConvertionError` if `point` is the point at infinity. Use
+   /// `PointFrom::convert_from` to get an `ECCValueRes` instead.
+   fn convert_point_to<N>(&self, point: &P) -> Result<N, points::ConvertionError>
    where
       N: points::PointFrom<P>,
    {
-      Ok(N::convert_from(point, &self.p()))
+      N::convert_from(point, self).ok_or(points::ConvertionError)
+   }
+
+   /// Whether `former` and `latter` are the same point on the curve, which
+   /// may be in different representations.
+   ///
+   /// The affine coordinates are compared by cross multiplication over `Fp`,
+   /// so nothing is inverted. The point at infinity only equals itself.
+   fn points_equal<Q>(&self, former: &P, latter: &Q) -> bool
+   where
+      P: points::PointFraction,
+      Q: points::PointFraction,
+   {
+      let p = self.p();
+      match (former.affine_fraction(), latter.affine_fraction()) {
+         (ECCValueRes::Finite(f), ECCValueRes::Finite(l)) => {
+            let x = &f.x_num * &l.x_den - &l.x_num * &f.x_den;
+            let y = &f.y_num * &l.y_den - &l.y_num * &f.y_den;
+            x.mod_floor(&p).is_zero() && y.mod_floor(&p).is_zero()
+         },
+         (ECCValueRes::Infinity, ECCValueRes::Infinity) => true,
+         _ => false,
+      }
+   }
+
+   /// Return the unique representation of `point`, affine coordinates
+   /// reduced into `[0, p)`, which can be used as a `HashMap` key or sorted.
+   fn canonical_point(&self, point: &P) -> ECCValueRes<points::AffineCoordinates>
+   where
+      points::AffineCoordinates: points::PointFrom<P>,
+   {
+      use self::points::{AffineCoordinates, PointFrom};
+
+      let p = self.p();
+      AffineCoordinates::convert_from(point, self).map(|point| AffineCoordinates {
+         x: point.x.mod_floor(&p),
+         y: point.y.mod_floor(&p),
+      })
    }
 }
@@ -0,0 +1,88 @@
+use num::{BigInt, Num};
+
+use super::MontgomeryCurve;
+
+/// filed `p` where `E: By^2 = x^3 + Ax^2 + x over Fp`, `2^255 - 19`
+const P: &str = "7FFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFED";
+/// const `A` where `E: By^2 = x^3 + Ax^2 + x over Fp`
+const A: &str = "076D06";
+/// const `B` where `E: By^2 = x^3 + Ax^2 + x over Fp`
+const B: &str = "01";
+/// order `n`, `2^252 + 27742317777372353535851937790883648493`
+const N: &str = "10000000_00000000_00000000_00000000_14DEF9DE_A2F79CD6_5812631A_5CF5D3ED";
+/// cofactor `h`
+const H: &str = "08";
+/// u coordinate of the base point
+#[allow(non_upper_case_globals)]
+const Gu: &str = "09";
+
+/// ## Curve25519
+/// @see https://tools.ietf.org/html/rfc7748#section-4.1
+pub struct Curve25519 {}
+
+impl Curve25519 {
+   #[inline]
+   fn p() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(P, 16).unwrap()
+   }
+
+   #[inline]
+   fn a() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(A, 16).unwrap()
+   }
+
+   #[inline]
+   fn b() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(B, 16).unwrap()
+   }
+
+   #[inline]
+   fn n() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(N, 16).unwrap()
+   }
+
+   #[inline]
+   fn h() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(H, 16).unwrap()
+   }
+
+   #[inline]
+   fn base_u() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(Gu, 16).unwrap()
+   }
+}
+
+impl MontgomeryCurve for Curve25519 {
+   #[inline]
+   fn new() -> Curve25519 { Curve25519 {} }
+
+   #[inline]
+   fn name(&self) -> &str { "Curve25519" }
+
+   #[inline]
+   fn p(&self) -> BigInt { Self::p() }
+
+   #[inline]
+   fn a(&self) -> BigInt { Self::a() }
+
+   #[inline]
+   fn b(&self) -> BigInt { Self::b() }
+
+   #[inline]
+   fn n(&self) -> BigInt { Self::n() }
+
+   #[inline]
+   fn h(&self) -> BigInt { Self::h() }
+
+   #[inline]
+   fn base_u(&self) -> BigInt { Self::base_u() }
+
+   #[inline]
+   fn bits(&self) -> usize { 255  }
+}
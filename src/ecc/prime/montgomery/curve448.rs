@@ -0,0 +1,90 @@
+use num::{BigInt, Num};
+
+use super::MontgomeryCurve;
+
+/// filed `p` where `E: By^2 = x^3 + Ax^2 + x over Fp`, `2^448 - 2^224 - 1`
+const P: &str = "FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFE_\
+                 FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF";
+/// const `A` where `E: By^2 = x^3 + Ax^2 + x over Fp`
+const A: &str = "0262A6";
+/// const `B` where `E: By^2 = x^3 + Ax^2 + x over Fp`
+const B: &str = "01";
+/// order `n`, `2^446 - 13818066809895115352007386748515426880336692474882178609894547503885`
+const N: &str = "3FFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_\
+                 7CCA23E9_C44EDB49_AED63690_216CC272_8DC58F55_2378C292_AB5844F3";
+/// cofactor `h`
+const H: &str = "04";
+/// u coordinate of the base point
+#[allow(non_upper_case_globals)]
+const Gu: &str = "05";
+
+/// ## Curve448
+/// @see https://tools.ietf.org/html/rfc7748#section-4.2
+pub struct Curve448 {}
+
+impl Curve448 {
+   #[inline]
+   fn p() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(P, 16).unwrap()
+   }
+
+   #[inline]
+   fn a() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(A, 16).unwrap()
+   }
+
+   #[inline]
+   fn b() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(B, 16).unwrap()
+   }
+
+   #[inline]
+   fn n() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(N, 16).unwrap()
+   }
+
+   #[inline]
+   fn h() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(H, 16).unwrap()
+   }
+
+   #[inline]
+   fn base_u() -> BigInt {
+      // We know this will succeed.
+      BigInt::from_str_radix(Gu, 16).unwrap()
+   }
+}
+
+impl MontgomeryCurve for Curve448 {
+   #[inline]
+   fn new() -> Curve448 { Curve448 {} }
+
+   #[inline]
+   fn name(&self) -> &str { "Curve448" }
+
+   #[inline]
+   fn p(&self) -> BigInt { Self::p() }
+
+   #[inline]
+   fn a(&self) -> BigInt { Self::a() }
+
+   #[inline]
+   fn b(&self) -> BigInt { Self::b() }
+
+   #[inline]
+   fn n(&self) -> BigInt { Self::n() }
+
+   #[inline]
+   fn h(&self) -> BigInt { Self::h() }
+
+   #[inline]
+   fn base_u(&self) -> BigInt { Self::base_u() }
+
+   #[inline]
+   fn bits(&self) -> usize { 448  }
+}
@@ -0,0 +1,167 @@
+use num::bigint::Sign;
+use num::{BigInt, Integer, One, Zero};
+
+use alloc::vec::Vec;
+use core::mem;
+
+mod curve25519;
+mod curve448;
+
+pub use self::curve25519::Curve25519;
+pub use self::curve448::Curve448;
+
+/// Montgomery curves `E: By^2 = x^3 + Ax^2 + x over Fp`, used for x-only
+/// Diffie-Hellman as in RFC 7748.
+///
+/// Points are given by their u (that is x) coordinate only, encoded as
+/// little-endian byte strings.
+///
+/// ### Example
+///
+/// ```
+/// use ecc_calc::ecc::prime::montgomery;
+///
+/// # fn main() {
+/// # let secret_key = [0x42_u8; 32];
+/// # let their_secret_key = [0x24_u8; 32];
+/// let mut base_u = [0_u8; 32];
+/// base_u[0] = 9;
+/// let public_key = montgomery::x25519(&secret_key, &base_u);
+/// # let their_public_key = montgomery::x25519(&their_secret_key, &base_u);
+/// let shared_secret = montgomery::x25519(&secret_key, &their_public_key);
+/// # assert_eq!(shared_secret, montgomery::x25519(&their_secret_key, &public_key));
+/// # }
+/// ```
+pub trait MontgomeryCurve: Sized {
+   /// Return an copy of the curve.
+   fn new() -> Self;
+
+   /// Return the curve friendly name.
+   fn name(&self) -> &str;
+
+   /// Return the field `p` value where `E: By^2 = x^3 + Ax^2 + x over Fp`
+   fn p(&self) -> BigInt;
+
+   /// Return the `A` value where `E: By^2 = x^3 + Ax^2 + x over Fp`
+   fn a(&self) -> BigInt;
+
+   /// Return the `B` value where `E: By^2 = x^3 + Ax^2 + x over Fp`
+   fn b(&self) -> BigInt;
+
+   /// Return the order `n` of the base point.
+   fn n(&self) -> BigInt;
+
+   /// Return the cofactor `h`, where `h * n` is the number of points on the
+   /// curve.
+   fn h(&self) -> BigInt;
+
+   /// Return the u coordinate of the base point.
+   fn base_u(&self) -> BigInt;
+
+   /// Return the bit length of scalars and u coordinates, 255 for Curve25519
+   /// and 448 for Curve448.
+   fn bits(&self) -> usize;
+
+   /// Return the byte length of encoded scalars and u coordinates.
+   fn encoded_len(&self) -> usize { self.bits().div_ceil(8) }
+
+   /// Decode a little-endian scalar and clamp it, RFC 7748 5: the low
+   /// `log2(h)` bits are cleared, bit `bits - 1` is set and all bits above
+   /// it are cleared.
+   fn clamp_scalar(&self, k: &[u8]) -> BigInt {
+      let top = BigInt::one() << (self.bits() - 1);
+
+      // k mod 2^(bits - 1) with the low bits cleared, plus 2^(bits - 1)
+      let k = BigInt::from_bytes_le(Sign::Plus, k).mod_floor(&top);
+      &k - k.mod_floor(&self.h()) + top
+   }
+
+   /// Decode a little-endian u coordinate. Unused bits of the last byte are
+   /// masked, and non-canonical values (`u >= p`) are accepted and reduced.
+   fn decode_u(&self, u: &[u8]) -> BigInt {
+      let mut u = u.to_vec();
+      if !self.bits().is_multiple_of(8) {
+         let last = u.len() - 1;
+         u[last] &= (1_u8 << (self.bits() % 8)) - 1;
+      }
+      BigInt::from_bytes_le(Sign::Plus, &u).mod_floor(&self.p())
+   }
+
+   /// Encode `u mod p` as `encoded_len()` little-endian bytes.
+   fn encode_u(&self, u: &BigInt) -> Vec<u8> {
+      let (_, mut bytes) = u.mod_floor(&self.p()).to_bytes_le();
+      bytes.resize(self.encoded_len(), 0);
+      bytes
+   }
+
+   /// The x-only Montgomery ladder, RFC 7748 5. Returns the u coordinate of
+   /// `k` times the point with u coordinate `u`, `0` for the neutral element.
+   #[allow(non_snake_case)]
+   fn ladder(&self, k: &BigInt, u: &BigInt) -> BigInt {
+      let p = self.p();
+      // (A - 2) / 4
+      let a24 = (self.a() - BigInt::from(2_u8)) / BigInt::from(4_u8);
+
+      let x_1 = u.mod_floor(&p);
+      let mut x_2 = BigInt::one();
+      let mut z_2 = BigInt::zero();
+      let mut x_3 = x_1.clone();
+      let mut z_3 = BigInt::one();
+      let mut swap = false;
+
+      for t in (0..self.bits()).rev() {
+         let k_t = (k >> t).is_odd();
+         if swap != k_t {
+            mem::swap(&mut x_2, &mut x_3);
+            mem::swap(&mut z_2, &mut z_3);
+         }
+         swap = k_t;
+
+         let A = (&x_2 + &z_2).mod_floor(&p);
+         let AA = (&A * &A).mod_floor(&p);
+         let B = (&x_2 - &z_2).mod_floor(&p);
+         let BB = (&B * &B).mod_floor(&p);
+         let E = (&AA - &BB).mod_floor(&p);
+         let C = (&x_3 + &z_3).mod_floor(&p);
+         let D = (&x_3 - &z_3).mod_floor(&p);
+         let DA = (D * &A).mod_floor(&p);
+         let CB = (C * &B).mod_floor(&p);
+
+         let sum = &DA + &CB;
+         let difference = &DA - &CB;
+         x_3 = (&sum * &sum).mod_floor(&p);
+         z_3 = (&x_1 * &difference * &difference).mod_floor(&p);
+         x_2 = (&AA * &BB).mod_floor(&p);
+         z_2 = (&E * (&AA + &a24 * &E)).mod_floor(&p);
+      }
+      if swap {
+         mem::swap(&mut x_2, &mut x_3);
+         mem::swap(&mut z_2, &mut z_3);
+      }
+
+      // x_2 / z_2, where 0^(p - 2) = 0 maps the neutral element to 0.
+      (x_2 * z_2.modpow(&(&p - BigInt::from(2_u8)), &p)).mod_floor(&p)
+   }
+
+   /// The X25519 and X448 functions: decode and clamp `k`, decode `u`, run
+   /// the ladder and encode the result.
+   fn scalar_multiply(&self, k: &[u8], u: &[u8]) -> Vec<u8> {
+      self.encode_u(&self.ladder(&self.clamp_scalar(k), &self.decode_u(u)))
+   }
+}
+
+/// X25519 of RFC 7748 5. The all-zero output for low order `u` is not
+/// rejected here.
+pub fn x25519(k: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+   let mut result = [0_u8; 32];
+   result.copy_from_slice(&Curve25519::new().scalar_multiply(k, u));
+   result
+}
+
+/// X448 of RFC 7748 5. The all-zero output for low order `u` is not rejected
+/// here.
+pub fn x448(k: &[u8; 56], u: &[u8; 56]) -> [u8; 56] {
+   let mut result = [0_u8; 56];
+   result.copy_from_slice(&Curve448::new().scalar_multiply(k, u));
+   result
+}
@@ -1,25 +1,143 @@
-extern crate num;
+use num::BigInt;
+use num::{Integer, Num, One, Zero};
 
-use self::num::BigInt;
-use self::num::bigint::ParseBigIntError;
-use self::num::Num;
+use alloc::string::String;
+use core::fmt;
+use core::convert::TryFrom;
 
-use std;
-use std::fmt;
-use std::convert::TryFrom;
-
-use super::{Point, PointFrom};
-use super::super::super::ECCValue;
+use super::super::curves::ECCurve;
+use super::super::trace::{self, Operation};
+use super::{naf, AffineFraction, Point, PointCalculation, PointFraction, PointFrom};
+use super::PointParseError;
+use super::super::super::{ECCValue, ECCValueRes};
 
 /// The `AffineCoordinates` struct represents a certain point on the elliptic curve,
 /// which are also called _Affine Coordinate_ Points.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Equality, hashing and ordering compare the coordinates as they are, so
+/// they are only meaningful between points reduced modulo `p`. See
+/// `ECCurvePoint::canonical_point`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct AffineCoordinates {
    pub x: BigInt,
    pub y: BigInt,
 }
 
-impl AffineCoordinates {}
+impl AffineCoordinates {
+   /// Return `1/v mod p`, as `v^(p - 2) mod p` for the prime `p`.
+   fn inverse(v: &BigInt, p: &BigInt) -> BigInt { v.modpow(&(p - BigInt::from(2_u8)), p) }
+
+   /// `former + latter`, where `former` may be the point at infinity.
+   fn add_to_value<C: ECCurve>(
+      curve: &C,
+      former: &ECCValueRes<AffineCoordinates>,
+      latter: &AffineCoordinates,
+   ) -> ECCValueRes<AffineCoordinates> {
+      match *former {
+         ECCValueRes::Finite(ref former) => Self::point_addition(curve, former, latter),
+         ECCValueRes::Infinity => ECCValueRes::Finite(latter.clone()),
+      }
+   }
+}
+
+#[allow(non_snake_case)]
+impl<Curve> PointCalculation<Curve> for AffineCoordinates
+where
+   Curve: ECCurve,
+{
+   /// There is no affine point at infinity, so it is returned separately.
+   type Output = ECCValueRes<AffineCoordinates>;
+
+   fn point_addition(curve: &Curve, former: &Self, latter: &Self) -> Self::Output {
+      let p = curve.p();
+
+      // Coordinates may not be reduced, e.g. as parsed from a public key.
+      if former.x.mod_floor(&p) == latter.x.mod_floor(&p) {
+         // Either P + (-P) or P + P
+         if (&former.y + &latter.y).mod_floor(&p).is_zero() {
+            return ECCValueRes::Infinity;
+         } else {
+            return Self::point_doublation(curve, former);
+         }
+      }
+
+      info!("** Point Addition!");
+      // lambda = (y2 - y1) / (x2 - x1)
+      let inv = Self::inverse(&(&latter.x - &former.x).mod_floor(&p), &p);
+      let lambda = ((&latter.y - &former.y) * inv).mod_floor(&p);
+      debug!("lambda: {:x}", lambda);
+
+      let x = (&lambda * &lambda - &former.x - &latter.x).mod_floor(&p);
+      let y = (&lambda * (&former.x - &x) - &former.y).mod_floor(&p);
+      trace::operation(
+         Operation::Addition,
+         "AffineCoordinates",
+         &[("lambda", &lambda)],
+         &[("x", &x), ("y", &y)],
+      );
+
+      ECCValueRes::Finite(AffineCoordinates { x, y })
+   }
+
+   fn point_subtraction(curve: &Curve, former: &Self, latter: &Self) -> Self::Output {
+      Self::point_addition(curve, former, &Self::point_negation(curve, latter))
+   }
+
+   fn point_negation(curve: &Curve, point: &Self) -> Self {
+      Self {
+         y: (-&point.y).mod_floor(&curve.p()),
+         ..point.clone()
+      }
+   }
+
+   fn point_doublation(curve: &Curve, point: &Self) -> Self::Output {
+      let p = curve.p();
+
+      // The tangent is vertical, so 2P is the point at infinity.
+      if point.y.mod_floor(&p).is_zero() {
+         return ECCValueRes::Infinity;
+      }
+
+      info!("** Point Doubling!");
+      // lambda = (3x^2 + a) / 2y
+      let inv = Self::inverse(&(&point.y * BigInt::from(2_u8)).mod_floor(&p), &p);
+      let lambda = ((&point.x * &point.x * BigInt::from(3_u8) + curve.a()) * inv).mod_floor(&p);
+      debug!("lambda: {:x}", lambda);
+
+      let x = (&lambda * &lambda - &point.x * BigInt::from(2_u8)).mod_floor(&p);
+      let y = (&lambda * (&point.x - &x) - &point.y).mod_floor(&p);
+      trace::operation(
+         Operation::Doubling,
+         "AffineCoordinates",
+         &[("lambda", &lambda)],
+         &[("x", &x), ("y", &y)],
+      );
+
+      ECCValueRes::Finite(AffineCoordinates { x, y })
+   }
+
+   /// Binary NAF method as `naf::multiply`, which can not be used here as
+   /// intermediate results may be the point at infinity.
+   fn point_multipication(curve: &Curve, point: &Self, k: BigInt) -> Self::Output {
+      let mut stack = naf::naf(&k);
+      debug!("\n{} {:?}", "  *  NAF(k):", stack);
+      trace::naf(&k, &stack);
+
+      let mut Q = ECCValueRes::Infinity;
+      while let Some(top) = stack.pop() {
+         Q = match Q {
+            ECCValueRes::Finite(ref R) => Self::point_doublation(curve, R),
+            ECCValueRes::Infinity => ECCValueRes::Infinity,
+         };
+         match top {
+            1 => Q = Self::add_to_value(curve, &Q, point),
+            -1 => Q = Self::add_to_value(curve, &Q, &Self::point_negation(curve, point)),
+            _ => (),
+         }
+      }
+      Q
+   }
+}
 
 /* -- Formatter impls -- */
 impl fmt::Display for AffineCoordinates {
@@ -69,9 +187,22 @@ impl fmt::Octal for AffineCoordinates {
 
 impl Point for AffineCoordinates {}
 
+impl PointFraction for AffineCoordinates {
+   fn affine_fraction(&self) -> ECCValueRes<AffineFraction> {
+      ECCValueRes::Finite(AffineFraction {
+         x_num: self.x.clone(),
+         x_den: BigInt::one(),
+         y_num: self.y.clone(),
+         y_den: BigInt::one(),
+      })
+   }
+}
+
 /* -- Point Convertion impls -- */
 impl PointFrom<AffineCoordinates> for AffineCoordinates {
-   fn convert_from(point: &AffineCoordinates, _i: &BigInt) -> Self { point.clone() }
+   fn convert_from<C: ECCurve>(point: &AffineCoordinates, _curve: &C) -> ECCValueRes<Self> {
+      ECCValueRes::Finite(point.clone())
+   }
 }
 
 impl TryFrom<ECCValue> for AffineCoordinates {
@@ -94,44 +225,52 @@ impl From<AffineCoordinates> for ECCValue {
       }
    }
 }
+
+impl From<ECCValue> for ECCValueRes<AffineCoordinates> {
+   fn from(value: ECCValue) -> ECCValueRes<AffineCoordinates> {
+      match value {
+         ECCValue::Finite { x, y } => ECCValueRes::Finite(AffineCoordinates { x, y }),
+         ECCValue::Infinity => ECCValueRes::Infinity,
+      }
+   }
+}
+
+impl From<ECCValueRes<AffineCoordinates>> for ECCValue {
+   fn from(value: ECCValueRes<AffineCoordinates>) -> ECCValue {
+      match value {
+         ECCValueRes::Finite(point) => ECCValue::from(point),
+         ECCValueRes::Infinity => ECCValue::Infinity,
+      }
+   }
+}
 /* -- Point Convertion impls -- */
 
 pub trait NewPoint<T, U>
 where
-   Self: std::marker::Sized,
+   Self: core::marker::Sized,
 {
    type Error;
    fn try_new(x_str: T, y_str: T, base: U) -> Result<Self, Self::Error>;
 }
 
-impl NewPoint<&'static str, u32> for AffineCoordinates {
-   type Error = ParseBigIntError;
+impl NewPoint<&str, u32> for AffineCoordinates {
+   type Error = PointParseError;
 
    fn try_new(s1: &str, s2: &str, base: u32) -> Result<Self, Self::Error> {
-      match BigInt::from_str_radix(s1, base) {
-         Ok(u1) => {
-            match BigInt::from_str_radix(s2, base) {
-               Ok(u2) => Ok(AffineCoordinates { x: u1, y: u2 }),
-               Err(err) => Err(err),
-            }
-         },
-         Err(err) => Err(err),
+      // `from_str_radix` panics on any other base.
+      if !(2..=36).contains(&base) {
+         return Err(PointParseError::InvalidBase(base));
       }
+      let x = BigInt::from_str_radix(s1, base)?;
+      let y = BigInt::from_str_radix(s2, base)?;
+      Ok(AffineCoordinates { x, y })
    }
 }
 
 impl NewPoint<String, u32> for AffineCoordinates {
-   type Error = ParseBigIntError;
+   type Error = PointParseError;
 
    fn try_new(s1: String, s2: String, base: u32) -> Result<Self, Self::Error> {
-      match BigInt::from_str_radix(&s1, base) {
-         Ok(u1) => {
-            match BigInt::from_str_radix(&s2, base) {
-               Ok(u2) => Ok(AffineCoordinates { x: u1, y: u2 }),
-               Err(err) => Err(err),
-            }
-         },
-         Err(err) => Err(err),
-      }
+      AffineCoordinates::try_new(s1.as_str(), s2.as_str(), base)
    }
 }
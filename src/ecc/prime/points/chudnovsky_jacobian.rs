@@ -0,0 +1,333 @@
+use num::{BigInt, Integer, One, Zero};
+use super::super::curves::ECCurve;
+use super::super::trace::{self, Operation};
+
+use core::fmt;
+
+use super::super::super::{ECCValue, ECCValueRes};
+use super::{
+   naf,
+   AffineCoordinates,
+   AffineFraction,
+   JacobianCoordinates,
+   Point,
+   PointCalculation,
+   PointFraction,
+   PointFrom,
+   StandardProjectiveCoordinates,
+};
+
+#[derive(Debug, Clone)]
+/// Chudnovsky Jacobian Coordinates are Jacobian Coordinates `(X, Y, Z)` which
+/// also carry `Z^2` and `Z^3`, so that additions do not have to calculate them.
+/// `(X, Y, Z, Z^2, Z^3) -> (X/Z^2, Y/Z^3)`
+pub struct ChudnovskyJacobianCoordinates {
+   pub x:  BigInt,
+   pub y:  BigInt,
+   pub z:  BigInt,
+   pub z2: BigInt,
+   pub z3: BigInt,
+}
+
+impl ChudnovskyJacobianCoordinates {
+   pub fn is_point_at_infinity(&self) -> bool { self.z.is_zero() }
+
+   /// Build a point from `X`, `Y` and `Z`, calculating `Z^2` and `Z^3` over
+   /// `Fp`.
+   fn with_z(x: BigInt, y: BigInt, z: BigInt, p: &BigInt) -> Self {
+      let z2 = z.modpow(&BigInt::from(2_u8), p);
+      let z3 = (&z2 * &z).mod_floor(p);
+      ChudnovskyJacobianCoordinates { x, y, z, z2, z3 }
+   }
+}
+
+#[allow(non_snake_case)]
+impl<Curve> PointCalculation<Curve> for ChudnovskyJacobianCoordinates
+where
+   Curve: ECCurve,
+{
+   type Output = Self;
+
+   fn point_addition(curve: &Curve, former: &Self, latter: &Self) -> Self {
+      // fast return
+      if former.is_point_at_infinity() {
+         return latter.clone();
+      }
+      if latter.is_point_at_infinity() {
+         return former.clone();
+      }
+
+      let p = curve.p();
+      let TWO = BigInt::from(2_u8);
+
+      // Z^2 and Z^3 are already known, which is the point of this representation.
+      let u1 = (&former.x * &latter.z2).mod_floor(&p);
+      let u2 = (&latter.x * &former.z2).mod_floor(&p);
+      let s1 = (&former.y * &latter.z3).mod_floor(&p);
+      let s2 = (&latter.y * &former.z3).mod_floor(&p);
+
+      debug!("u1: {:x}, u2: {:x}", u1, u2);
+      if u1 == u2 {
+         debug!("s1: {:x}, s2: {:x}", s1, s2);
+         if s1 != s2 {
+            return Self::from(ECCValue::Infinity);
+         } else {
+            return Self::point_doublation(curve, former);
+         }
+      }
+
+      info!("** Point Addition!");
+      let H = (&u2 - &u1).mod_floor(&p);
+      let R = (&s2 - &s1).mod_floor(&p);
+      let H2 = (&H * &H).mod_floor(&p);
+      let H3 = (&H2 * &H).mod_floor(&p);
+      let u1H2 = (&u1 * &H2).mod_floor(&p);
+
+      let x = (&R * &R - &H3 - &u1H2 * &TWO).mod_floor(&p);
+      let y = (&R * (&u1H2 - &x) - &s1 * &H3).mod_floor(&p);
+      let z = (&former.z * &latter.z * &H).mod_floor(&p);
+      trace::operation(
+         Operation::Addition,
+         "ChudnovskyJacobianCoordinates",
+         &[("u1", &u1), ("u2", &u2), ("s1", &s1), ("s2", &s2), ("H", &H), ("R", &R)],
+         &[("x", &x), ("y", &y), ("z", &z)],
+      );
+
+      Self::with_z(x, y, z, &p)
+   }
+
+   fn point_subtraction(curve: &Curve, former: &Self, latter: &Self) -> Self::Output {
+      Self::point_addition(curve, former, &Self::point_negation(curve, latter))
+   }
+
+   fn point_negation(curve: &Curve, point: &Self) -> Self {
+      Self {
+         y: (-&point.y).mod_floor(&curve.p()),
+         ..point.clone()
+      }
+   }
+
+   fn point_doublation(curve: &Curve, point: &Self) -> Self {
+      if point.is_point_at_infinity() {
+         return Self::from(ECCValue::Infinity);
+      }
+
+      let p = curve.p();
+      let TWO = BigInt::from(2_u8);
+      let THREE = BigInt::from(3_u8);
+      let FOUR = BigInt::from(4_u8);
+      let EIGHT = BigInt::from(8_u8);
+
+      let A = (&point.y * &point.y).mod_floor(&p);
+      let B = (&point.x * &A * &FOUR).mod_floor(&p);
+      let C = (&A * &A * &EIGHT).mod_floor(&p);
+      // a * Z^4 = a * (Z^2)^2
+      let D = (&point.x * &point.x * &THREE + curve.a() * &point.z2 * &point.z2).mod_floor(&p);
+
+      info!("** Point Doubling!");
+      debug!("\n * A: {}, \n * B: {}, \n * C: {}, \n * D: {}", A, B, C, D);
+
+      let x = (&D * &D - &B * &TWO).mod_floor(&p);
+      let y = (&D * (&B - &x) - &C).mod_floor(&p);
+      let z = (&point.y * &point.z * &TWO).mod_floor(&p);
+      trace::operation(
+         Operation::Doubling,
+         "ChudnovskyJacobianCoordinates",
+         &[("A", &A), ("B", &B), ("C", &C), ("D", &D)],
+         &[("x", &x), ("y", &y), ("z", &z)],
+      );
+
+      Self::with_z(x, y, z, &p)
+   }
+
+   fn point_multipication(curve: &Curve, point: &Self, k: BigInt) -> Self {
+      naf::multiply(curve, point, &k, Self::from(ECCValue::Infinity))
+   }
+}
+
+/* -- Formatter impls -- */
+impl fmt::Display for ChudnovskyJacobianCoordinates {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "ChudnovskyJacobianCoordinates(x: ")?;
+      self.x.fmt(f)?;
+      write!(f, ", y: ")?;
+      self.y.fmt(f)?;
+      write!(f, ", z: ")?;
+      self.z.fmt(f)?;
+      write!(f, ", z2: ")?;
+      self.z2.fmt(f)?;
+      write!(f, ", z3: ")?;
+      self.z3.fmt(f)?;
+      write!(f, ")")?;
+      Ok(())
+   }
+}
+
+impl fmt::LowerHex for ChudnovskyJacobianCoordinates {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "ChudnovskyJacobianCoordinates(x: ")?;
+      self.x.fmt(f)?;
+      write!(f, ", y: ")?;
+      self.y.fmt(f)?;
+      write!(f, ", z: ")?;
+      self.z.fmt(f)?;
+      write!(f, ", z2: ")?;
+      self.z2.fmt(f)?;
+      write!(f, ", z3: ")?;
+      self.z3.fmt(f)?;
+      write!(f, ")")?;
+      Ok(())
+   }
+}
+
+impl fmt::UpperHex for ChudnovskyJacobianCoordinates {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "ChudnovskyJacobianCoordinates(x: ")?;
+      self.x.fmt(f)?;
+      write!(f, ", y: ")?;
+      self.y.fmt(f)?;
+      write!(f, ", z: ")?;
+      self.z.fmt(f)?;
+      write!(f, ", z2: ")?;
+      self.z2.fmt(f)?;
+      write!(f, ", z3: ")?;
+      self.z3.fmt(f)?;
+      write!(f, ")")?;
+      Ok(())
+   }
+}
+/* -- Formatter impls -- */
+
+impl Point for ChudnovskyJacobianCoordinates {}
+
+impl PointFraction for ChudnovskyJacobianCoordinates {
+   fn affine_fraction(&self) -> ECCValueRes<AffineFraction> {
+      if self.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      // (X/Z^2, Y/Z^3), both denominators are at hand.
+      ECCValueRes::Finite(AffineFraction {
+         x_num: self.x.clone(),
+         x_den: self.z2.clone(),
+         y_num: self.y.clone(),
+         y_den: self.z3.clone(),
+      })
+   }
+}
+
+/* -- Point Convertion impls -- */
+impl PointFrom<ChudnovskyJacobianCoordinates> for ChudnovskyJacobianCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &ChudnovskyJacobianCoordinates,
+      _curve: &C,
+   ) -> ECCValueRes<Self> {
+      if point.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      ECCValueRes::Finite(point.clone())
+   }
+}
+
+impl PointFrom<AffineCoordinates> for ChudnovskyJacobianCoordinates {
+   fn convert_from<C: ECCurve>(point: &AffineCoordinates, _curve: &C) -> ECCValueRes<Self> {
+      ECCValueRes::Finite(ChudnovskyJacobianCoordinates {
+         x:  point.x.clone(),
+         y:  point.y.clone(),
+         z:  BigInt::one(),
+         z2: BigInt::one(),
+         z3: BigInt::one(),
+      })
+   }
+}
+
+impl PointFrom<ChudnovskyJacobianCoordinates> for AffineCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &ChudnovskyJacobianCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<Self> {
+      JacobianCoordinates::convert_from(point, curve)
+         .and_then(|point| AffineCoordinates::convert_from(&point, curve))
+   }
+}
+
+impl PointFrom<JacobianCoordinates> for ChudnovskyJacobianCoordinates {
+   fn convert_from<C: ECCurve>(point: &JacobianCoordinates, curve: &C) -> ECCValueRes<Self> {
+      if point.z.is_zero() {
+         return ECCValueRes::Infinity;
+      }
+      ECCValueRes::Finite(Self::with_z(
+         point.x.clone(),
+         point.y.clone(),
+         point.z.clone(),
+         &curve.p(),
+      ))
+   }
+}
+
+impl PointFrom<ChudnovskyJacobianCoordinates> for JacobianCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &ChudnovskyJacobianCoordinates,
+      _curve: &C,
+   ) -> ECCValueRes<Self> {
+      if point.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      ECCValueRes::Finite(JacobianCoordinates {
+         x: point.x.clone(),
+         y: point.y.clone(),
+         z: point.z.clone(),
+      })
+   }
+}
+
+impl PointFrom<StandardProjectiveCoordinates> for ChudnovskyJacobianCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &StandardProjectiveCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<Self> {
+      JacobianCoordinates::convert_from(point, curve)
+         .and_then(|point| Self::convert_from(&point, curve))
+   }
+}
+
+impl PointFrom<ChudnovskyJacobianCoordinates> for StandardProjectiveCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &ChudnovskyJacobianCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<Self> {
+      if point.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+
+      // (X/Z^2, Y/Z^3) = (XZ/Z^3, Y/Z^3)
+      ECCValueRes::Finite(StandardProjectiveCoordinates {
+         x: (&point.x * &point.z).mod_floor(&curve.p()),
+         y: point.y.clone(),
+         z: point.z3.clone(),
+      })
+   }
+}
+
+impl From<ECCValue> for ChudnovskyJacobianCoordinates {
+   fn from(val: ECCValue) -> ChudnovskyJacobianCoordinates {
+      use self::ECCValue::{Finite, Infinity};
+
+      match val {
+         Finite { x, y } => ChudnovskyJacobianCoordinates {
+            x,
+            y,
+            z: BigInt::one(),
+            z2: BigInt::one(),
+            z3: BigInt::one(),
+         },
+         Infinity => ChudnovskyJacobianCoordinates {
+            x:  BigInt::one(),
+            y:  BigInt::one(),
+            z:  BigInt::zero(),
+            z2: BigInt::zero(),
+            z3: BigInt::zero(),
+         },
+      }
+   }
+}
+/* -- Point Convertion impls -- */
@@ -0,0 +1,415 @@
+use num::{BigInt, Integer, One, Zero};
+use super::super::curves::ECCurve;
+use super::super::trace::{self, Operation};
+
+use core::fmt;
+
+use super::super::super::{ECCValue, ECCValueRes};
+use super::{
+   naf,
+   AffineCoordinates,
+   AffineFraction,
+   JacobianCoordinates,
+   Point,
+   PointCalculation,
+   PointFraction,
+   PointFrom,
+   StandardProjectiveCoordinates,
+};
+
+#[derive(Debug, Clone)]
+/// Complete Projective Coordinates are Standard Projective Coordinates
+/// `(X, Y, Z) -> (X/Z, Y/Z)` calculated with the complete formulas of
+/// Renes, Costello and Batina (https://eprint.iacr.org/2015/1060).
+///
+/// The formulas are valid for every pair of input points on a prime order
+/// curve, so additions, doublings and the point at infinity `(0, 1, 0)` need
+/// no special cases.
+pub struct CompleteProjectiveCoordinates {
+   pub x: BigInt,
+   pub y: BigInt,
+   pub z: BigInt,
+}
+
+impl CompleteProjectiveCoordinates {
+   pub fn is_point_at_infinity(&self) -> bool { self.z.is_zero() }
+
+   /// Whether `a = -3` over `Fp`, for which cheaper formulas exist.
+   fn a_is_minus_three<C: ECCurve>(curve: &C) -> bool {
+      let p = curve.p();
+      curve.a().mod_floor(&p) == &p - BigInt::from(3_u8)
+   }
+
+   /// Algorithm 1, addition for general `a`.
+   #[allow(non_snake_case)]
+   fn add_general<C: ECCurve>(curve: &C, former: &Self, latter: &Self) -> Self {
+      let p = curve.p();
+      let a = curve.a();
+      let b3 = BigInt::from(3_u8) * curve.b();
+      let m = |v: BigInt| v.mod_floor(&p);
+      let (X1, Y1, Z1) = (&former.x, &former.y, &former.z);
+      let (X2, Y2, Z2) = (&latter.x, &latter.y, &latter.z);
+
+      let t0 = m(X1 * X2);
+      let t1 = m(Y1 * Y2);
+      let t2 = m(Z1 * Z2);
+      let t3 = m((X1 + Y1) * (X2 + Y2));
+      let t3 = m(t3 - (&t0 + &t1));
+      let t4 = m((X1 + Z1) * (X2 + Z2));
+      let t4 = m(t4 - (&t0 + &t2));
+      let t5 = m((Y1 + Z1) * (Y2 + Z2));
+      let t5 = m(t5 - (&t1 + &t2));
+      let Z3 = m(&a * &t4);
+      let X3 = m(&b3 * &t2);
+      let Z3 = m(&X3 + Z3);
+      let X3 = m(&t1 - &Z3);
+      let Z3 = m(&t1 + Z3);
+      let Y3 = m(&X3 * &Z3);
+      let t1 = m(&t0 * 3);
+      let t2 = m(&a * t2);
+      let t4 = m(&b3 * t4);
+      let t1 = m(t1 + &t2);
+      let t2 = m(&a * (&t0 - t2));
+      let t4 = m(t4 + &t2);
+      let Y3 = m(Y3 + &t1 * &t4);
+      let X3 = m(&t3 * X3 - &t5 * &t4);
+      let Z3 = m(&t5 * Z3 + &t3 * &t1);
+      trace::operation(
+         Operation::Addition,
+         "CompleteProjectiveCoordinates",
+         &[("t0", &t0), ("t1", &t1), ("t2", &t2), ("t3", &t3), ("t4", &t4), ("t5", &t5)],
+         &[("x", &X3), ("y", &Y3), ("z", &Z3)],
+      );
+
+      CompleteProjectiveCoordinates { x: X3, y: Y3, z: Z3 }
+   }
+
+   /// Algorithm 3, doubling for general `a`.
+   #[allow(non_snake_case)]
+   fn double_general<C: ECCurve>(curve: &C, point: &Self) -> Self {
+      let p = curve.p();
+      let a = curve.a();
+      let b3 = BigInt::from(3_u8) * curve.b();
+      let m = |v: BigInt| v.mod_floor(&p);
+      let (X, Y, Z) = (&point.x, &point.y, &point.z);
+
+      let t0 = m(X * X);
+      let t1 = m(Y * Y);
+      let t2 = m(Z * Z);
+      let t3 = m(X * Y * 2);
+      let Z3 = m(X * Z * 2);
+      let X3 = m(&a * &Z3);
+      let Y3 = m(&b3 * &t2);
+      let Y3 = m(X3 + Y3);
+      let X3 = m(&t1 - &Y3);
+      let Y3 = m(&t1 + Y3);
+      let Y3 = m(&X3 * Y3);
+      let X3 = m(&t3 * X3);
+      let Z3 = m(&b3 * Z3);
+      let t2 = m(&a * t2);
+      let t3 = m(&a * (&t0 - &t2));
+      let t3 = m(t3 + Z3);
+      let t0 = m(&t0 * 3 + t2);
+      let Y3 = m(Y3 + &t0 * &t3);
+      let t2 = m(Y * Z * 2);
+      let X3 = m(X3 - &t2 * &t3);
+      let Z3 = m(&t2 * &t1 * 4);
+      trace::operation(
+         Operation::Doubling,
+         "CompleteProjectiveCoordinates",
+         &[("t0", &t0), ("t1", &t1), ("t2", &t2), ("t3", &t3)],
+         &[("x", &X3), ("y", &Y3), ("z", &Z3)],
+      );
+
+      CompleteProjectiveCoordinates { x: X3, y: Y3, z: Z3 }
+   }
+
+   /// Algorithm 4, addition for `a = -3`.
+   #[allow(non_snake_case)]
+   fn add_a_minus_three<C: ECCurve>(curve: &C, former: &Self, latter: &Self) -> Self {
+      let p = curve.p();
+      let b = curve.b();
+      let m = |v: BigInt| v.mod_floor(&p);
+      let (X1, Y1, Z1) = (&former.x, &former.y, &former.z);
+      let (X2, Y2, Z2) = (&latter.x, &latter.y, &latter.z);
+
+      let t0 = m(X1 * X2);
+      let t1 = m(Y1 * Y2);
+      let t2 = m(Z1 * Z2);
+      let t3 = m((X1 + Y1) * (X2 + Y2));
+      let t3 = m(t3 - (&t0 + &t1));
+      let t4 = m((Y1 + Z1) * (Y2 + Z2));
+      let t4 = m(t4 - (&t1 + &t2));
+      let X3 = m((X1 + Z1) * (X2 + Z2));
+      let Y3 = m(X3 - (&t0 + &t2));
+      let Z3 = m(&b * &t2);
+      let X3 = m(&Y3 - Z3);
+      let X3 = m(X3 * 3);
+      let Z3 = m(&t1 - &X3);
+      let X3 = m(&t1 + X3);
+      let Y3 = m(&b * Y3);
+      let t2 = m(&t2 * 3);
+      let Y3 = m(Y3 - &t2 - &t0);
+      let Y3 = m(Y3 * 3);
+      let t0 = m(&t0 * 3 - &t2);
+      let X3_ = m(&t3 * &X3 - &t4 * &Y3);
+      let Y3_ = m(&X3 * &Z3 + &t0 * &Y3);
+      let Z3_ = m(&t4 * &Z3 + &t3 * &t0);
+      trace::operation(
+         Operation::Addition,
+         "CompleteProjectiveCoordinates",
+         &[
+            ("t0", &t0),
+            ("t1", &t1),
+            ("t2", &t2),
+            ("t3", &t3),
+            ("t4", &t4),
+            ("X3", &X3),
+            ("Y3", &Y3),
+            ("Z3", &Z3),
+         ],
+         &[("x", &X3_), ("y", &Y3_), ("z", &Z3_)],
+      );
+
+      CompleteProjectiveCoordinates { x: X3_, y: Y3_, z: Z3_ }
+   }
+
+   /// Algorithm 6, doubling for `a = -3`.
+   #[allow(non_snake_case)]
+   fn double_a_minus_three<C: ECCurve>(curve: &C, point: &Self) -> Self {
+      let p = curve.p();
+      let b = curve.b();
+      let m = |v: BigInt| v.mod_floor(&p);
+      let (X, Y, Z) = (&point.x, &point.y, &point.z);
+
+      let t0 = m(X * X);
+      let t1 = m(Y * Y);
+      let t2 = m(Z * Z);
+      let t3 = m(X * Y * 2);
+      let Z3 = m(X * Z * 2);
+      let Y3 = m(&b * &t2 - &Z3);
+      let Y3 = m(Y3 * 3);
+      let X3 = m(&t1 - &Y3);
+      let Y3 = m(&t1 + Y3);
+      let Y3 = m(&X3 * Y3);
+      let X3 = m(X3 * &t3);
+      let t2 = m(t2 * 3);
+      let Z3 = m(&b * Z3 - &t2 - &t0);
+      let Z3 = m(Z3 * 3);
+      let t0 = m(t0 * 3 - &t2);
+      let Y3 = m(Y3 + &t0 * &Z3);
+      let t0 = m(Y * Z * 2);
+      let X3 = m(X3 - &t0 * Z3);
+      let Z3 = m(&t0 * &t1 * 4);
+      trace::operation(
+         Operation::Doubling,
+         "CompleteProjectiveCoordinates",
+         &[("t0", &t0), ("t1", &t1), ("t2", &t2), ("t3", &t3)],
+         &[("x", &X3), ("y", &Y3), ("z", &Z3)],
+      );
+
+      CompleteProjectiveCoordinates { x: X3, y: Y3, z: Z3 }
+   }
+}
+
+#[allow(non_snake_case)]
+impl<Curve> PointCalculation<Curve> for CompleteProjectiveCoordinates
+where
+   Curve: ECCurve,
+{
+   type Output = Self;
+
+   fn point_addition(curve: &Curve, former: &Self, latter: &Self) -> Self {
+      info!("** Point Addition!");
+      if Self::a_is_minus_three(curve) {
+         Self::add_a_minus_three(curve, former, latter)
+      } else {
+         Self::add_general(curve, former, latter)
+      }
+   }
+
+   fn point_subtraction(curve: &Curve, former: &Self, latter: &Self) -> Self::Output {
+      Self::point_addition(curve, former, &Self::point_negation(curve, latter))
+   }
+
+   fn point_negation(curve: &Curve, point: &Self) -> Self {
+      Self {
+         y: (-&point.y).mod_floor(&curve.p()),
+         ..point.clone()
+      }
+   }
+
+   fn point_doublation(curve: &Curve, point: &Self) -> Self {
+      info!("** Point Doubling!");
+      if Self::a_is_minus_three(curve) {
+         Self::double_a_minus_three(curve, point)
+      } else {
+         Self::double_general(curve, point)
+      }
+   }
+
+   fn point_multipication(curve: &Curve, point: &Self, k: BigInt) -> Self {
+      naf::multiply(curve, point, &k, Self::from(ECCValue::Infinity))
+   }
+}
+
+/* -- Formatter impls -- */
+impl fmt::Display for CompleteProjectiveCoordinates {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "CompleteProjectiveCoordinates(x: ")?;
+      self.x.fmt(f)?;
+      write!(f, ", y: ")?;
+      self.y.fmt(f)?;
+      write!(f, ", z: ")?;
+      self.z.fmt(f)?;
+      write!(f, ")")?;
+      Ok(())
+   }
+}
+
+impl fmt::LowerHex for CompleteProjectiveCoordinates {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "CompleteProjectiveCoordinates(x: ")?;
+      self.x.fmt(f)?;
+      write!(f, ", y: ")?;
+      self.y.fmt(f)?;
+      write!(f, ", z: ")?;
+      self.z.fmt(f)?;
+      write!(f, ")")?;
+      Ok(())
+   }
+}
+
+impl fmt::UpperHex for CompleteProjectiveCoordinates {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "CompleteProjectiveCoordinates(x: ")?;
+      self.x.fmt(f)?;
+      write!(f, ", y: ")?;
+      self.y.fmt(f)?;
+      write!(f, ", z: ")?;
+      self.z.fmt(f)?;
+      write!(f, ")")?;
+      Ok(())
+   }
+}
+/* -- Formatter impls -- */
+
+impl Point for CompleteProjectiveCoordinates {}
+
+impl PointFraction for CompleteProjectiveCoordinates {
+   fn affine_fraction(&self) -> ECCValueRes<AffineFraction> {
+      if self.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      // (X/Z, Y/Z)
+      ECCValueRes::Finite(AffineFraction {
+         x_num: self.x.clone(),
+         x_den: self.z.clone(),
+         y_num: self.y.clone(),
+         y_den: self.z.clone(),
+      })
+   }
+}
+
+/* -- Point Convertion impls -- */
+impl PointFrom<CompleteProjectiveCoordinates> for CompleteProjectiveCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &CompleteProjectiveCoordinates,
+      _curve: &C,
+   ) -> ECCValueRes<Self> {
+      if point.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      ECCValueRes::Finite(point.clone())
+   }
+}
+
+impl PointFrom<AffineCoordinates> for CompleteProjectiveCoordinates {
+   fn convert_from<C: ECCurve>(point: &AffineCoordinates, _curve: &C) -> ECCValueRes<Self> {
+      ECCValueRes::Finite(CompleteProjectiveCoordinates {
+         x: point.x.clone(),
+         y: point.y.clone(),
+         z: BigInt::one(),
+      })
+   }
+}
+
+impl PointFrom<CompleteProjectiveCoordinates> for AffineCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &CompleteProjectiveCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<Self> {
+      StandardProjectiveCoordinates::convert_from(point, curve)
+         .and_then(|point| AffineCoordinates::convert_from(&point, curve))
+   }
+}
+
+// Both are `(X/Z, Y/Z)`, only the point at infinity is written differently.
+impl PointFrom<StandardProjectiveCoordinates> for CompleteProjectiveCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &StandardProjectiveCoordinates,
+      _curve: &C,
+   ) -> ECCValueRes<Self> {
+      if point.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      ECCValueRes::Finite(CompleteProjectiveCoordinates {
+         x: point.x.clone(),
+         y: point.y.clone(),
+         z: point.z.clone(),
+      })
+   }
+}
+
+impl PointFrom<CompleteProjectiveCoordinates> for StandardProjectiveCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &CompleteProjectiveCoordinates,
+      _curve: &C,
+   ) -> ECCValueRes<Self> {
+      if point.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      ECCValueRes::Finite(StandardProjectiveCoordinates {
+         x: point.x.clone(),
+         y: point.y.clone(),
+         z: point.z.clone(),
+      })
+   }
+}
+
+impl PointFrom<JacobianCoordinates> for CompleteProjectiveCoordinates {
+   fn convert_from<C: ECCurve>(point: &JacobianCoordinates, curve: &C) -> ECCValueRes<Self> {
+      StandardProjectiveCoordinates::convert_from(point, curve)
+         .and_then(|point| Self::convert_from(&point, curve))
+   }
+}
+
+impl PointFrom<CompleteProjectiveCoordinates> for JacobianCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &CompleteProjectiveCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<Self> {
+      StandardProjectiveCoordinates::convert_from(point, curve)
+         .and_then(|point| JacobianCoordinates::convert_from(&point, curve))
+   }
+}
+
+impl From<ECCValue> for CompleteProjectiveCoordinates {
+   fn from(val: ECCValue) -> CompleteProjectiveCoordinates {
+      use self::ECCValue::{Finite, Infinity};
+
+      match val {
+         Finite { x, y } => CompleteProjectiveCoordinates {
+            x,
+            y,
+            z: BigInt::one(),
+         },
+         Infinity => CompleteProjectiveCoordinates {
+            x: BigInt::zero(),
+            y: BigInt::one(),
+            z: BigInt::zero(),
+         },
+      }
+   }
+}
+/* -- Point Convertion impls -- */
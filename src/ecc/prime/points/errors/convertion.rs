@@ -1,4 +1,6 @@
-use std::{error, fmt};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
 
 #[derive(Debug, Clone)]
 // Define our error types. These may be customized for our error handling cases.
@@ -20,10 +22,11 @@ impl fmt::Display for ConvertionError {
 }
 
 // This is important for other errors to wrap this one.
+#[cfg(feature = "std")]
 impl error::Error for ConvertionError {
    fn description(&self) -> &str { "invalid first item to double" }
 
-   fn cause(&self) -> Option<&error::Error> {
+   fn cause(&self) -> Option<&dyn error::Error> {
       // Generic error, underlying cause isn't tracked.
       None
    }
@@ -1 +1,2 @@
 pub mod convertion;
+pub mod point_parse;
@@ -0,0 +1,44 @@
+use num::bigint::ParseBigIntError;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+#[derive(Debug, Clone, PartialEq)]
+// Returned by `NewPoint::try_new` when the coordinates can not be parsed.
+pub enum PointParseError {
+   /// The base is not in `2..=36`.
+   InvalidBase(u32),
+   /// A coordinate is not a number in the base.
+   InvalidNumber(ParseBigIntError),
+}
+
+impl From<ParseBigIntError> for PointParseError {
+   fn from(err: ParseBigIntError) -> PointParseError { PointParseError::InvalidNumber(err) }
+}
+
+impl fmt::Display for PointParseError {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         PointParseError::InvalidBase(base) => write!(f, "Base {} is not in 2..=36", base),
+         PointParseError::InvalidNumber(ref err) => write!(f, "Invalid coordinate: {}", err),
+      }
+   }
+}
+
+// This is important for other errors to wrap this one.
+#[cfg(feature = "std")]
+impl error::Error for PointParseError {
+   fn description(&self) -> &str {
+      match *self {
+         PointParseError::InvalidBase(_) => "Base is not in 2..=36",
+         PointParseError::InvalidNumber(_) => "Invalid coordinate",
+      }
+   }
+
+   fn cause(&self) -> Option<&dyn error::Error> {
+      match *self {
+         PointParseError::InvalidBase(_) => None,
+         PointParseError::InvalidNumber(ref err) => Some(err),
+      }
+   }
+}
@@ -1,13 +1,20 @@
-extern crate num;
-
-use self::num::bigint::ParseBigIntError;
-use self::num::{BigInt, Integer, Num, One, ToPrimitive, Zero};
+use num::{BigInt, Integer, Num, One, Zero};
 use super::super::curves::ECCurve;
-
-use std::fmt;
-
-use super::super::super::ECCValue;
-use super::{AffineCoordinates, Point, PointCalculation, PointFrom, PointInto};
+use super::super::trace::{self, Operation};
+
+use core::fmt;
+
+use super::super::super::{ECCValue, ECCValueRes};
+use super::{
+   naf,
+   AffineCoordinates,
+   AffineFraction,
+   Point,
+   PointCalculation,
+   PointFraction,
+   PointFrom,
+   PointParseError,
+};
 
 #[derive(Debug, Clone)]
 /// Jacobian Coordinates are used to represent elliptic curve points on prime curves
@@ -19,9 +26,36 @@ pub struct JacobianCoordinates {
 }
 
 impl JacobianCoordinates {
-   fn is_point_at_infinity(&self) -> bool {
+   pub fn is_point_at_infinity(&self) -> bool {
       self.z.is_zero()
    }
+
+   /// (X, Y, Z) -> (X/Z^2, Y/Z^3) over `Fp`.
+   fn to_affine(&self, p: &BigInt) -> ECCValueRes<AffineCoordinates> {
+      // The point at infinity has no affine coordinates.
+      if self.z.is_zero() {
+         return ECCValueRes::Infinity;
+      }
+
+      #[allow(non_snake_case)]
+      // Function to calculate 1/Z^n mod p as a multipication.
+      let inv_Zn_over_p = |z: &BigInt, n: usize, p: &BigInt| {
+         if z.is_one() {
+            BigInt::one()
+         } else {
+            let exp = p - (n + 1);
+            z.modpow(&exp, p)
+         }
+      };
+
+      let inv_z2 = inv_Zn_over_p(&self.z, 2, p);
+      let inv_z3 = inv_Zn_over_p(&self.z, 3, p);
+
+      let x = (&self.x * &inv_z2).mod_floor(p);
+      let y = (&self.y * &inv_z3).mod_floor(p);
+
+      ECCValueRes::Finite(AffineCoordinates { x, y })
+   }
 }
 
 #[allow(non_snake_case)]
@@ -29,6 +63,8 @@ impl<Curve> PointCalculation<Curve> for JacobianCoordinates
 where
    Curve: ECCurve,
 {
+   type Output = Self;
+
    /// Returns a function that takes a curve and return the result point.
    fn point_addition(curve: &Curve, former: &Self, latter: &Self) -> Self {
       // fast return
@@ -79,13 +115,19 @@ where
          let I = (&former.x * &G).mod_floor(&curve.p());
          let x = (F.modpow(&TWO, &curve.p()) - (&H + &TWO * &I)).mod_floor(&curve.p());
          let y = (&F * (I - &x) - &former.y * &H).mod_floor(&curve.p());
-         let z = (&former.z * E).mod_floor(&curve.p());
-
-         return JacobianCoordinates { x, y, z };
+         let z = (&former.z * &E).mod_floor(&curve.p());
+         trace::operation(
+            Operation::MixedAddition,
+            "JacobianCoordinates",
+            &[("A", &A), ("B", &B), ("C", &C), ("D", &D), ("E", &E), ("F", &F), ("G", &G)],
+            &[("x", &x), ("y", &y), ("z", &z)],
+         );
+
+         JacobianCoordinates { x, y, z }
       } else {
          info!("** Point Addition!");
 
-         let h = (&u1 - &u2).mod_floor(&curve.p());
+         let h = (&u2 - &u1).mod_floor(&curve.p());
          let r = (&s2 - &s1).mod_floor(&curve.p());
 
          let x = r.modpow(&TWO, &curve.p())
@@ -93,23 +135,31 @@ where
             - 2_usize * &u1 * h.modpow(&TWO, &curve.p());
          let x = x.mod_floor(&curve.p());
 
-         let y = &r * (u1 * h.modpow(&TWO, &curve.p()) - &x) - &s1 * h.modpow(&THREE, &curve.p());
+         let y = &r * (&u1 * h.modpow(&TWO, &curve.p()) - &x) - &s1 * h.modpow(&THREE, &curve.p());
          let y = y.mod_floor(&curve.p());
 
          let z = &h * &former.z * &latter.z;
          let z = z.mod_floor(&curve.p());
-
-         return JacobianCoordinates { x, y, z };
+         trace::operation(
+            Operation::Addition,
+            "JacobianCoordinates",
+            &[("u1", &u1), ("u2", &u2), ("s1", &s1), ("s2", &s2), ("h", &h), ("r", &r)],
+            &[("x", &x), ("y", &y), ("z", &z)],
+         );
+
+         JacobianCoordinates { x, y, z }
       }
    }
 
-   fn point_subtraction(curve: &Curve, former: &Self, latter: &Self) -> Self {
-      let latter = Self {
-         x: latter.x.clone(),
-         y: latter.y.clone() * -1,
-         z: latter.z.clone(),
-      };
-      Self::point_addition(curve, former, &latter)
+   fn point_subtraction(curve: &Curve, former: &Self, latter: &Self) -> Self::Output {
+      Self::point_addition(curve, former, &Self::point_negation(curve, latter))
+   }
+
+   fn point_negation(curve: &Curve, point: &Self) -> Self {
+      Self {
+         y: (-&point.y).mod_floor(&curve.p()),
+         ..point.clone()
+      }
    }
 
    #[allow(non_snake_case)]
@@ -134,50 +184,19 @@ where
 
       let x = (D.modpow(&TWO, &curve.p()) - BigInt::from(2) * &B).mod_floor(&curve.p());
       let y = (&D * (&B - &x) - &C).mod_floor(&curve.p());
-      let z = (BigInt::from(4) * &point.y * &point.z).mod_floor(&curve.p());
-
-      return JacobianCoordinates { x, y, z };
+      let z = (BigInt::from(2) * &point.y * &point.z).mod_floor(&curve.p());
+      trace::operation(
+         Operation::Doubling,
+         "JacobianCoordinates",
+         &[("A", &A), ("B", &B), ("C", &C), ("D", &D)],
+         &[("x", &x), ("y", &y), ("z", &z)],
+      );
+
+      JacobianCoordinates { x, y, z }
    }
 
-   #[allow(non_snake_case)]
    fn point_multipication(curve: &Curve, point: &Self, k: BigInt) -> Self {
-      // NAF(k), Algorithm 3.30
-      let NAF = |mut k: BigInt| -> Vec<i8> {
-         let mut vec = Vec::new();
-         while k >= BigInt::one() {
-            if k.is_odd() {
-               let mod4 = (k.mod_floor(&BigInt::from(4))).to_i64().unwrap();
-               let ki = 2 - (mod4 as i8);
-               assert!(
-                  (-1..2).contains(&ki),
-                  "NAF: Unexpected Ki number error: {}",
-                  ki
-               );
-
-               vec.push(ki);
-               k = k - ki;
-            } else {
-               vec.push(0_i8);
-            }
-            k = k / 2;
-         }
-         return vec;
-      };
-
-      // Algorithm 3.31
-      let mut stack = NAF(k);
-      debug!("\n{} {:?}", "  *  NAF(k):", stack);
-      let mut Q = JacobianCoordinates::from(ECCValue::Infinity);
-      while let Some(top) = stack.pop() {
-         debug!("\n * Q: {:x}", Q);
-         Q = Self::point_doublation(curve, &Q);
-         match top {
-            1 => Q = Self::point_addition(curve, &Q, &point),
-            -1 => Q = Self::point_subtraction(curve, &Q, &point),
-            _ => (),
-         }
-      }
-      return Q;
+      naf::multiply(curve, point, &k, JacobianCoordinates::from(ECCValue::Infinity))
    }
 }
 
@@ -237,48 +256,54 @@ impl fmt::Octal for JacobianCoordinates {
 
 impl Point for JacobianCoordinates {}
 
+impl PointFraction for JacobianCoordinates {
+   fn affine_fraction(&self) -> ECCValueRes<AffineFraction> {
+      if self.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      // (X/Z^2, Y/Z^3)
+      let z2 = &self.z * &self.z;
+      ECCValueRes::Finite(AffineFraction {
+         x_num: self.x.clone(),
+         x_den: z2.clone(),
+         y_num: self.y.clone(),
+         y_den: z2 * &self.z,
+      })
+   }
+}
+
 /* -- Point Convertion impls -- */
 impl PointFrom<AffineCoordinates> for JacobianCoordinates {
-   fn convert_from(point: &AffineCoordinates, _i: &BigInt) -> JacobianCoordinates {
-      JacobianCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &AffineCoordinates,
+      _curve: &C,
+   ) -> ECCValueRes<JacobianCoordinates> {
+      ECCValueRes::Finite(JacobianCoordinates {
          x: point.x.clone(),
          y: point.y.clone(),
          z: BigInt::one(),
-      }
+      })
    }
 }
 
 impl PointFrom<JacobianCoordinates> for AffineCoordinates {
-   fn convert_from(jacob: &JacobianCoordinates, p: &BigInt) -> AffineCoordinates {
-      // fast fail
-      if jacob.z.is_zero() {
-         panic!("Zero division!")
-      }
-
-      #[allow(non_snake_case)]
-      // Function to calculate 1/Z^n mod p as a multipication.
-      let inv_Zn_over_p = |z: &BigInt, n: usize, p: &BigInt| {
-         if z.is_one() {
-            BigInt::one()
-         } else {
-            let exp = p - (n + 1);
-            z.modpow(&exp, p)
-         }
-      };
-
-      let inv_z2 = inv_Zn_over_p(&jacob.z, 2, p);
-      let inv_z3 = inv_Zn_over_p(&jacob.z, 3, p);
-
-      let x = (&jacob.x * &inv_z2).mod_floor(p);
-      let y = (&jacob.y * &inv_z3).mod_floor(p);
-
-      AffineCoordinates { x, y }
+   fn convert_from<C: ECCurve>(
+      jacob: &JacobianCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<AffineCoordinates> {
+      jacob.to_affine(&curve.p())
    }
 }
 
 impl PointFrom<JacobianCoordinates> for JacobianCoordinates {
-   fn convert_from(point: &JacobianCoordinates, _i: &BigInt) -> JacobianCoordinates {
-      point.clone()
+   fn convert_from<C: ECCurve>(
+      point: &JacobianCoordinates,
+      _curve: &C,
+   ) -> ECCValueRes<JacobianCoordinates> {
+      if point.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      ECCValueRes::Finite(point.clone())
    }
 }
 
@@ -301,7 +326,7 @@ impl From<ECCValue> for JacobianCoordinates {
    }
 }
 
-impl<'a> From<&'a JacobianCoordinates> for JacobianCoordinates {
+impl From<&JacobianCoordinates> for JacobianCoordinates {
    fn from(val: &JacobianCoordinates) -> JacobianCoordinates {
       JacobianCoordinates {
          x: val.x.clone(),
@@ -320,24 +345,17 @@ where
    fn try_new(x_str: T, y_str: T, z_str: T, base: U) -> Result<Self, Self::Error>;
 }
 
-impl NewPoint<&'static str, u32> for JacobianCoordinates {
-   type Error = ParseBigIntError;
+impl NewPoint<&str, u32> for JacobianCoordinates {
+   type Error = PointParseError;
 
    fn try_new(s1: &str, s2: &str, s3: &str, base: u32) -> Result<Self, Self::Error> {
-      let x = BigInt::from_str_radix(s1, base);
-      let y = BigInt::from_str_radix(s2, base);
-      let z = BigInt::from_str_radix(s3, base);
-
-      match (x, y, z) {
-         (Ok(x), Ok(y), Ok(z)) => Ok(JacobianCoordinates { x, y, z }),
-         _ => Err(ParseBigIntError::Other),
+      // `from_str_radix` panics on any other base.
+      if !(2..=36).contains(&base) {
+         return Err(PointParseError::InvalidBase(base));
       }
-   }
-}
-
-impl PartialEq for JacobianCoordinates {
-   fn eq(&self, other: &Self) -> bool {
-      let i = BigInt::zero();
-      AffineCoordinates::convert_from(self, &i) == other.convert_into(&i)
+      let x = BigInt::from_str_radix(s1, base)?;
+      let y = BigInt::from_str_radix(s2, base)?;
+      let z = BigInt::from_str_radix(s3, base)?;
+      Ok(JacobianCoordinates { x, y, z })
    }
 }
@@ -1,6 +1,7 @@
 use super::super::prime;
-use std::fmt;
-use super::num::BigInt;
+use super::super::ECCValueRes;
+use core::fmt;
+use num::BigInt;
 
 pub trait Point
    : fmt::Debug + fmt::Display + fmt::LowerHex + fmt::UpperHex + Clone {
@@ -9,23 +10,57 @@ pub trait Point
 }
 
 pub trait PointCalculation<Curve: prime::ECCurve>: Point {
-   fn point_addition(&Curve, &Self, &Self) -> Self;
+   /// Type of the results. Projective coordinates can represent the point at
+   /// infinity and use `Self`, while `AffineCoordinates` can not and use
+   /// `ECCValueRes<AffineCoordinates>`.
+   type Output;
 
-   fn point_subtraction(&Curve, &Self, &Self) -> Self;
+   fn point_addition(curve: &Curve, former: &Self, latter: &Self) -> Self::Output;
 
-   fn point_doublation(&Curve, &Self) -> Self;
+   fn point_subtraction(curve: &Curve, former: &Self, latter: &Self) -> Self::Output;
 
-   fn point_multipication(&Curve, &Self, BigInt) -> Self;
+   /// `-P`, which is never infinity unless `P` is.
+   fn point_negation(curve: &Curve, point: &Self) -> Self;
+
+   fn point_doublation(curve: &Curve, point: &Self) -> Self::Output;
+
+   fn point_multipication(curve: &Curve, point: &Self, k: BigInt) -> Self::Output;
+}
+
+/// Affine coordinates of a point as fractions over `Fp`,
+/// `(x_num / x_den, y_num / y_den)`.
+#[derive(Debug, Clone)]
+pub struct AffineFraction {
+   pub x_num: BigInt,
+   pub x_den: BigInt,
+   pub y_num: BigInt,
+   pub y_den: BigInt,
+}
+
+/// Points which can be compared by cross multiplication, without the
+/// inversion a conversion to `AffineCoordinates` needs.
+///
+/// The projective representations don't implement `PartialEq`: the same
+/// point has many coordinates, which are only proportional modulo `p`, and
+/// the points don't know their curve. Compare them with
+/// `ECCurvePoint::points_equal`, or wrap them in `CurvePoint`.
+pub trait PointFraction: Point {
+   /// Returns `ECCValueRes::Infinity` for the point at infinity. The fraction
+   /// isn't reduced, so it can be compared modulo any `p`.
+   fn affine_fraction(&self) -> ECCValueRes<AffineFraction>;
 }
 
 pub trait PointFrom<P: Point>: Point {
-   /// `p`: FIELD
-   fn convert_from(point: &P, p: &BigInt) -> Self;
+   /// `curve`: the curve both points are on.
+   ///
+   /// Returns `ECCValueRes::Infinity` if and only if `point` is the point at
+   /// infinity.
+   fn convert_from<C: prime::ECCurve>(point: &P, curve: &C) -> ECCValueRes<Self>;
 }
 
 pub trait PointInto<T: Point>: Sized + Point {
    /// Performs the conversion.
-   fn convert_into(&self, p: &BigInt) -> T;
+   fn convert_into<C: prime::ECCurve>(&self, curve: &C) -> ECCValueRes<T>;
 }
 
 impl<T, U> PointInto<U> for T
@@ -33,16 +68,26 @@ where
    U: PointFrom<T>,
    T: Point,
 {
-   fn convert_into(&self, i: &BigInt) -> U { U::convert_from(self, &i) }
+   fn convert_into<C: prime::ECCurve>(&self, curve: &C) -> ECCValueRes<U> {
+      U::convert_from(self, curve)
+   }
 }
 
 pub mod affine;
+pub mod chudnovsky_jacobian;
+pub mod complete_projective;
 pub mod jacobian;
+pub mod modified_jacobian;
 pub mod standard_projective;
+mod naf;
 
 pub use self::affine::AffineCoordinates;
+pub use self::chudnovsky_jacobian::ChudnovskyJacobianCoordinates;
+pub use self::complete_projective::CompleteProjectiveCoordinates;
 pub use self::jacobian::JacobianCoordinates;
+pub use self::modified_jacobian::ModifiedJacobianCoordinates;
 pub use self::standard_projective::StandardProjectiveCoordinates;
 
 mod errors;
 pub use self::errors::convertion::ConvertionError;
+pub use self::errors::point_parse::PointParseError;
@@ -0,0 +1,339 @@
+use num::{BigInt, Integer, One, Zero};
+use super::super::curves::ECCurve;
+use super::super::trace::{self, Operation};
+
+use core::fmt;
+
+use super::super::super::ECCValueRes;
+use super::{
+   naf,
+   AffineCoordinates,
+   AffineFraction,
+   ChudnovskyJacobianCoordinates,
+   JacobianCoordinates,
+   Point,
+   PointCalculation,
+   PointFraction,
+   PointFrom,
+   StandardProjectiveCoordinates,
+};
+
+#[derive(Debug, Clone)]
+/// Modified Jacobian Coordinates are Jacobian Coordinates `(X, Y, Z)` which
+/// also carry `aZ^4`, making repeated doublings cheaper.
+/// `(X, Y, Z, aZ^4) -> (X/Z^2, Y/Z^3)`
+pub struct ModifiedJacobianCoordinates {
+   pub x:   BigInt,
+   pub y:   BigInt,
+   pub z:   BigInt,
+   pub az4: BigInt,
+}
+
+impl ModifiedJacobianCoordinates {
+   pub fn is_point_at_infinity(&self) -> bool { self.z.is_zero() }
+
+   /// There is no `From<ECCValue>`, as `aZ^4` can not be known without the
+   /// curve. Finite points are converted with `PointFrom<AffineCoordinates>`.
+   fn infinity() -> Self {
+      ModifiedJacobianCoordinates {
+         x:   BigInt::one(),
+         y:   BigInt::one(),
+         z:   BigInt::zero(),
+         az4: BigInt::zero(),
+      }
+   }
+
+   /// Build a point from `X`, `Y` and `Z`, calculating `aZ^4` over `Fp`.
+   fn with_z<C: ECCurve>(x: BigInt, y: BigInt, z: BigInt, curve: &C) -> Self {
+      let p = curve.p();
+      let az4 = (curve.a() * z.modpow(&BigInt::from(4_u8), &p)).mod_floor(&p);
+      ModifiedJacobianCoordinates { x, y, z, az4 }
+   }
+}
+
+#[allow(non_snake_case)]
+impl<Curve> PointCalculation<Curve> for ModifiedJacobianCoordinates
+where
+   Curve: ECCurve,
+{
+   type Output = Self;
+
+   fn point_addition(curve: &Curve, former: &Self, latter: &Self) -> Self {
+      // fast return
+      if former.is_point_at_infinity() {
+         return latter.clone();
+      }
+      if latter.is_point_at_infinity() {
+         return former.clone();
+      }
+
+      let p = curve.p();
+      let TWO = BigInt::from(2_u8);
+      let THREE = BigInt::from(3_u8);
+
+      let u1 = (&former.x * latter.z.modpow(&TWO, &p)).mod_floor(&p);
+      let u2 = (&latter.x * former.z.modpow(&TWO, &p)).mod_floor(&p);
+      let s1 = (&former.y * latter.z.modpow(&THREE, &p)).mod_floor(&p);
+      let s2 = (&latter.y * former.z.modpow(&THREE, &p)).mod_floor(&p);
+
+      debug!("u1: {:x}, u2: {:x}", u1, u2);
+      if u1 == u2 {
+         debug!("s1: {:x}, s2: {:x}", s1, s2);
+         if s1 != s2 {
+            return Self::infinity();
+         } else {
+            return Self::point_doublation(curve, former);
+         }
+      }
+
+      info!("** Point Addition!");
+      let H = (&u2 - &u1).mod_floor(&p);
+      let R = (&s2 - &s1).mod_floor(&p);
+      let H2 = (&H * &H).mod_floor(&p);
+      let H3 = (&H2 * &H).mod_floor(&p);
+      let u1H2 = (&u1 * &H2).mod_floor(&p);
+
+      let x = (&R * &R - &H3 - &u1H2 * &TWO).mod_floor(&p);
+      let y = (&R * (&u1H2 - &x) - &s1 * &H3).mod_floor(&p);
+      let z = (&former.z * &latter.z * &H).mod_floor(&p);
+      trace::operation(
+         Operation::Addition,
+         "ModifiedJacobianCoordinates",
+         &[("u1", &u1), ("u2", &u2), ("s1", &s1), ("s2", &s2), ("H", &H), ("R", &R)],
+         &[("x", &x), ("y", &y), ("z", &z)],
+      );
+
+      // Additions have to pay for aZ^4, so that doublings do not.
+      Self::with_z(x, y, z, curve)
+   }
+
+   fn point_subtraction(curve: &Curve, former: &Self, latter: &Self) -> Self::Output {
+      Self::point_addition(curve, former, &Self::point_negation(curve, latter))
+   }
+
+   fn point_negation(curve: &Curve, point: &Self) -> Self {
+      Self {
+         y: (-&point.y).mod_floor(&curve.p()),
+         ..point.clone()
+      }
+   }
+
+   fn point_doublation(curve: &Curve, point: &Self) -> Self {
+      if point.is_point_at_infinity() {
+         return Self::infinity();
+      }
+
+      let p = curve.p();
+      let TWO = BigInt::from(2_u8);
+      let THREE = BigInt::from(3_u8);
+      let FOUR = BigInt::from(4_u8);
+      let EIGHT = BigInt::from(8_u8);
+
+      let Y2 = (&point.y * &point.y).mod_floor(&p);
+      let S = (&point.x * &Y2 * &FOUR).mod_floor(&p);
+      let U = (&Y2 * &Y2 * &EIGHT).mod_floor(&p);
+      let M = (&point.x * &point.x * &THREE + &point.az4).mod_floor(&p);
+
+      info!("** Point Doubling!");
+      debug!("\n * S: {}, \n * U: {}, \n * M: {}", S, U, M);
+
+      let x = (&M * &M - &S * &TWO).mod_floor(&p);
+      let y = (&M * (&S - &x) - &U).mod_floor(&p);
+      let z = (&point.y * &point.z * &TWO).mod_floor(&p);
+      // a(2YZ)^4 = 16Y^4 * aZ^4 = 2U * aZ^4
+      let az4 = (&U * &point.az4 * &TWO).mod_floor(&p);
+      trace::operation(
+         Operation::Doubling,
+         "ModifiedJacobianCoordinates",
+         &[("S", &S), ("U", &U), ("M", &M)],
+         &[("x", &x), ("y", &y), ("z", &z), ("az4", &az4)],
+      );
+
+      ModifiedJacobianCoordinates { x, y, z, az4 }
+   }
+
+   fn point_multipication(curve: &Curve, point: &Self, k: BigInt) -> Self {
+      naf::multiply(curve, point, &k, Self::infinity())
+   }
+}
+
+/* -- Formatter impls -- */
+impl fmt::Display for ModifiedJacobianCoordinates {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "ModifiedJacobianCoordinates(x: ")?;
+      self.x.fmt(f)?;
+      write!(f, ", y: ")?;
+      self.y.fmt(f)?;
+      write!(f, ", z: ")?;
+      self.z.fmt(f)?;
+      write!(f, ", az4: ")?;
+      self.az4.fmt(f)?;
+      write!(f, ")")?;
+      Ok(())
+   }
+}
+
+impl fmt::LowerHex for ModifiedJacobianCoordinates {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "ModifiedJacobianCoordinates(x: ")?;
+      self.x.fmt(f)?;
+      write!(f, ", y: ")?;
+      self.y.fmt(f)?;
+      write!(f, ", z: ")?;
+      self.z.fmt(f)?;
+      write!(f, ", az4: ")?;
+      self.az4.fmt(f)?;
+      write!(f, ")")?;
+      Ok(())
+   }
+}
+
+impl fmt::UpperHex for ModifiedJacobianCoordinates {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "ModifiedJacobianCoordinates(x: ")?;
+      self.x.fmt(f)?;
+      write!(f, ", y: ")?;
+      self.y.fmt(f)?;
+      write!(f, ", z: ")?;
+      self.z.fmt(f)?;
+      write!(f, ", az4: ")?;
+      self.az4.fmt(f)?;
+      write!(f, ")")?;
+      Ok(())
+   }
+}
+/* -- Formatter impls -- */
+
+impl Point for ModifiedJacobianCoordinates {}
+
+impl PointFraction for ModifiedJacobianCoordinates {
+   fn affine_fraction(&self) -> ECCValueRes<AffineFraction> {
+      if self.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      // (X/Z^2, Y/Z^3)
+      let z2 = &self.z * &self.z;
+      ECCValueRes::Finite(AffineFraction {
+         x_num: self.x.clone(),
+         x_den: z2.clone(),
+         y_num: self.y.clone(),
+         y_den: z2 * &self.z,
+      })
+   }
+}
+
+/* -- Point Convertion impls -- */
+impl PointFrom<ModifiedJacobianCoordinates> for ModifiedJacobianCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &ModifiedJacobianCoordinates,
+      _curve: &C,
+   ) -> ECCValueRes<Self> {
+      if point.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      ECCValueRes::Finite(point.clone())
+   }
+}
+
+impl PointFrom<AffineCoordinates> for ModifiedJacobianCoordinates {
+   fn convert_from<C: ECCurve>(point: &AffineCoordinates, curve: &C) -> ECCValueRes<Self> {
+      ECCValueRes::Finite(ModifiedJacobianCoordinates {
+         x:   point.x.clone(),
+         y:   point.y.clone(),
+         z:   BigInt::one(),
+         az4: curve.a().mod_floor(&curve.p()),
+      })
+   }
+}
+
+impl PointFrom<ModifiedJacobianCoordinates> for AffineCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &ModifiedJacobianCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<Self> {
+      JacobianCoordinates::convert_from(point, curve)
+         .and_then(|point| AffineCoordinates::convert_from(&point, curve))
+   }
+}
+
+impl PointFrom<JacobianCoordinates> for ModifiedJacobianCoordinates {
+   fn convert_from<C: ECCurve>(point: &JacobianCoordinates, curve: &C) -> ECCValueRes<Self> {
+      if point.z.is_zero() {
+         return ECCValueRes::Infinity;
+      }
+      ECCValueRes::Finite(Self::with_z(
+         point.x.clone(),
+         point.y.clone(),
+         point.z.clone(),
+         curve,
+      ))
+   }
+}
+
+impl PointFrom<ModifiedJacobianCoordinates> for JacobianCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &ModifiedJacobianCoordinates,
+      _curve: &C,
+   ) -> ECCValueRes<Self> {
+      if point.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      ECCValueRes::Finite(JacobianCoordinates {
+         x: point.x.clone(),
+         y: point.y.clone(),
+         z: point.z.clone(),
+      })
+   }
+}
+
+impl PointFrom<StandardProjectiveCoordinates> for ModifiedJacobianCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &StandardProjectiveCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<Self> {
+      JacobianCoordinates::convert_from(point, curve)
+         .and_then(|point| Self::convert_from(&point, curve))
+   }
+}
+
+impl PointFrom<ModifiedJacobianCoordinates> for StandardProjectiveCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &ModifiedJacobianCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<Self> {
+      JacobianCoordinates::convert_from(point, curve)
+         .and_then(|point| StandardProjectiveCoordinates::convert_from(&point, curve))
+   }
+}
+
+impl PointFrom<ChudnovskyJacobianCoordinates> for ModifiedJacobianCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &ChudnovskyJacobianCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<Self> {
+      if point.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+
+      // aZ^4 = a(Z^2)^2
+      let p = curve.p();
+      ECCValueRes::Finite(ModifiedJacobianCoordinates {
+         x:   point.x.clone(),
+         y:   point.y.clone(),
+         z:   point.z.clone(),
+         az4: (curve.a() * &point.z2 * &point.z2).mod_floor(&p),
+      })
+   }
+}
+
+impl PointFrom<ModifiedJacobianCoordinates> for ChudnovskyJacobianCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &ModifiedJacobianCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<Self> {
+      JacobianCoordinates::convert_from(point, curve)
+         .and_then(|point| ChudnovskyJacobianCoordinates::convert_from(&point, curve))
+   }
+}
+
+/* -- Point Convertion impls -- */
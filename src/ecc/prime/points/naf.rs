@@ -0,0 +1,61 @@
+use num::{BigInt, Integer, One, Signed, ToPrimitive};
+use alloc::vec::Vec;
+use super::super::curves::ECCurve;
+use super::super::trace;
+use super::PointCalculation;
+
+/// NAF(k), Algorithm 3.30
+///
+/// Digits are returned least significant first.
+/// NAF(-k) = -NAF(k), so a negative `k` only flips the sign of every digit.
+pub fn naf(k: &BigInt) -> Vec<i8> {
+   let mut vec = Vec::new();
+   let mut k_ = k.abs();
+   while k_ >= BigInt::one() {
+      if k_.is_odd() {
+         let mod4 = (k_.mod_floor(&BigInt::from(4))).to_i64().unwrap();
+         let ki = 2 - (mod4 as i8);
+         assert!(
+            (-1..2).contains(&ki),
+            "NAF: Unexpected Ki number error: {}",
+            ki
+         );
+
+         vec.push(ki);
+         k_ -= ki;
+      } else {
+         vec.push(0_i8);
+      }
+      k_ /= 2;
+   }
+
+   if k.is_negative() {
+      vec = vec.into_iter().map(|ki| -ki).collect();
+   }
+   vec
+}
+
+/// Binary NAF method for point multiplication, Algorithm 3.31
+///
+/// `infinity` is the representation of the point at infinity in `P`.
+#[allow(non_snake_case)]
+pub fn multiply<Curve, P>(curve: &Curve, point: &P, k: &BigInt, infinity: P) -> P
+where
+   Curve: ECCurve,
+   P: PointCalculation<Curve, Output = P>,
+{
+   let mut stack = naf(k);
+   debug!("\n{} {:?}", "  *  NAF(k):", stack);
+   trace::naf(k, &stack);
+   let mut Q = infinity;
+   while let Some(top) = stack.pop() {
+      debug!("\n * Q: {:x}", Q);
+      Q = P::point_doublation(curve, &Q);
+      match top {
+         1 => Q = P::point_addition(curve, &Q, point),
+         -1 => Q = P::point_subtraction(curve, &Q, point),
+         _ => (),
+      }
+   }
+   Q
+}
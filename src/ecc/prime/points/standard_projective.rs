@@ -1,13 +1,21 @@
-extern crate num;
-
-use self::num::bigint::ParseBigIntError;
-use self::num::{pow, BigInt, Integer, Num, One, ToPrimitive, Zero};
+use num::{pow, BigInt, Integer, Num, One, Zero};
 use super::super::curves::ECCurve;
-
-use std::fmt;
-
-use super::super::super::ECCValue;
-use super::{AffineCoordinates, Point, PointCalculation, PointFrom, PointInto};
+use super::super::trace::{self, Operation};
+
+use core::fmt;
+
+use super::super::super::{ECCValue, ECCValueRes};
+use super::{
+   naf,
+   AffineCoordinates,
+   AffineFraction,
+   JacobianCoordinates,
+   Point,
+   PointCalculation,
+   PointFraction,
+   PointFrom,
+   PointParseError,
+};
 
 #[derive(Debug, Clone)]
 /// Standard Projective Coordinates are used to represent elliptic curve points on prime curves
@@ -22,6 +30,32 @@ impl StandardProjectiveCoordinates {
    pub fn is_point_at_infinity(&self) -> bool {
       self.z.is_zero()
    }
+
+   /// (X, Y, Z) -> (X/Z, Y/Z) over `Fp`.
+   fn to_affine(&self, p: &BigInt) -> ECCValueRes<AffineCoordinates> {
+      // The point at infinity has no affine coordinates.
+      if self.z.is_zero() {
+         return ECCValueRes::Infinity;
+      }
+
+      #[allow(non_snake_case)]
+      // Function to calculate 1/Z^n mod p as a multipication.
+      let inv_Zn_over_p = |z: &BigInt, n: usize, p: &BigInt| {
+         if z.is_one() {
+            BigInt::one()
+         } else {
+            let exp = p - (n + 1);
+            z.modpow(&exp, p)
+         }
+      };
+
+      let inv_z = inv_Zn_over_p(&self.z, 1, p);
+
+      let x = (&self.x * &inv_z).mod_floor(p);
+      let y = (&self.y * &inv_z).mod_floor(p);
+
+      ECCValueRes::Finite(AffineCoordinates { x, y })
+   }
 }
 
 #[allow(non_snake_case)]
@@ -29,6 +63,8 @@ impl<Curve> PointCalculation<Curve> for StandardProjectiveCoordinates
 where
    Curve: ECCurve,
 {
+   type Output = Self;
+
    /// Returns a function that takes a curve and return the result point.
    fn point_addition(curve: &Curve, former: &Self, latter: &Self) -> Self {
       // U1 = Y2*Z1
@@ -70,11 +106,13 @@ where
 
       debug!("V1: {:x}, V2: {:x}", V1, V2);
       debug!("U1: {:x}, U2: {:x}", U1, U2);
-      if U1 == U2 {
-         if V1 == V2 {
-            return Self::point_doublation(curve, former);
-         } else {
+      // Equal x coordinates only, see the pseudo code above; points sharing
+      // their y coordinate can still be distinct.
+      if V1 == V2 {
+         if U1 != U2 {
             return StandardProjectiveCoordinates::from(ECCValue::Infinity);
+         } else {
+            return Self::point_doublation(curve, former);
          }
       }
 
@@ -90,17 +128,25 @@ where
       let x = (&V * &A).mod_floor(&curve.p());
       let y = (&U * (&V_ - &A) - V.modpow(&THREE, &curve.p()) * &U2).mod_floor(&curve.p());
       let z = (V.modpow(&THREE, &curve.p()) * &W).mod_floor(&curve.p());
+      trace::operation(
+         Operation::Addition,
+         "StandardProjectiveCoordinates",
+         &[("U", &U), ("V", &V), ("W", &W), ("A", &A)],
+         &[("x", &x), ("y", &y), ("z", &z)],
+      );
+
+      StandardProjectiveCoordinates { x, y, z }
+   }
 
-      return StandardProjectiveCoordinates { x, y, z };
+   fn point_subtraction(curve: &Curve, former: &Self, latter: &Self) -> Self::Output {
+      Self::point_addition(curve, former, &Self::point_negation(curve, latter))
    }
 
-   fn point_subtraction(curve: &Curve, former: &Self, latter: &Self) -> Self {
-      let latter = Self {
-         x: latter.x.clone(),
-         y: latter.y.clone() * -1,
-         z: latter.z.clone(),
-      };
-      Self::point_addition(curve, former, &latter)
+   fn point_negation(curve: &Curve, point: &Self) -> Self {
+      Self {
+         y: (-&point.y).mod_floor(&curve.p()),
+         ..point.clone()
+      }
    }
 
    #[allow(non_snake_case)]
@@ -134,53 +180,21 @@ where
       let y: BigInt =
          &W * (4 * &B - &H) - 8 * point.y.modpow(&TWO, &curve.p()) * S.modpow(&TWO, &curve.p());
       let y = y.mod_floor(&curve.p());
-      let z: BigInt = 8 * pow(S, 3);
+      let z: BigInt = 8 * pow(S.clone(), 3);
       debug!("{}", z);
       let z = z.mod_floor(&curve.p());
-
-      return StandardProjectiveCoordinates { x, y, z };
+      trace::operation(
+         Operation::Doubling,
+         "StandardProjectiveCoordinates",
+         &[("W", &W), ("S", &S), ("B", &B), ("H", &H)],
+         &[("x", &x), ("y", &y), ("z", &z)],
+      );
+
+      StandardProjectiveCoordinates { x, y, z }
    }
 
-   #[allow(non_snake_case)]
    fn point_multipication(curve: &Curve, point: &Self, k: BigInt) -> Self {
-      // NAF(k), Algorithm 3.30
-      let NAF = |mut k: BigInt| -> Vec<i8> {
-         let mut vec = Vec::new();
-         while k >= BigInt::one() {
-            if k.is_odd() {
-               let mod4 = (k.mod_floor(&BigInt::from(4))).to_i64().unwrap();
-               let ki = 2 - (mod4 as i8);
-               assert!(
-                  (-1..2).contains(&ki),
-                  "NAF: Unexpected Ki number error: {}",
-                  ki
-               );
-
-               vec.push(ki);
-               k = k - ki;
-            } else {
-               vec.push(0_i8);
-            }
-            k = k / 2;
-         }
-         return vec;
-      };
-
-      // Algorithm 3.31
-      let mut stack = NAF(k);
-      debug!("\n{} {:?}", "  *  NAF(k):", stack);
-      let mut Q = StandardProjectiveCoordinates::from(ECCValue::Infinity);
-      while let Some(top) = stack.pop() {
-         debug!("\n * Q: {:x}", Q);
-         debug!("top: {}", top);
-         Q = Self::point_doublation(curve, &Q);
-         match top {
-            1 => Q = Self::point_addition(curve, &Q, &point),
-            -1 => Q = Self::point_subtraction(curve, &Q, &point),
-            _ => (),
-         }
-      }
-      return Q;
+      naf::multiply(curve, point, &k, StandardProjectiveCoordinates::from(ECCValue::Infinity))
    }
 }
 
@@ -240,50 +254,91 @@ impl fmt::Octal for StandardProjectiveCoordinates {
 
 impl Point for StandardProjectiveCoordinates {}
 
+impl PointFraction for StandardProjectiveCoordinates {
+   fn affine_fraction(&self) -> ECCValueRes<AffineFraction> {
+      if self.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      // (X/Z, Y/Z)
+      ECCValueRes::Finite(AffineFraction {
+         x_num: self.x.clone(),
+         x_den: self.z.clone(),
+         y_num: self.y.clone(),
+         y_den: self.z.clone(),
+      })
+   }
+}
+
 /* -- Point Convertion impls -- */
 impl PointFrom<AffineCoordinates> for StandardProjectiveCoordinates {
-   fn convert_from(point: &AffineCoordinates, _i: &BigInt) -> StandardProjectiveCoordinates {
-      StandardProjectiveCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &AffineCoordinates,
+      _curve: &C,
+   ) -> ECCValueRes<StandardProjectiveCoordinates> {
+      ECCValueRes::Finite(StandardProjectiveCoordinates {
          x: point.x.clone(),
          y: point.y.clone(),
          z: BigInt::one(),
-      }
+      })
    }
 }
 
 impl PointFrom<StandardProjectiveCoordinates> for StandardProjectiveCoordinates {
-   fn convert_from(
+   fn convert_from<C: ECCurve>(
       point: &StandardProjectiveCoordinates,
-      _i: &BigInt,
-   ) -> StandardProjectiveCoordinates {
-      point.clone()
+      _curve: &C,
+   ) -> ECCValueRes<StandardProjectiveCoordinates> {
+      if point.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
+      ECCValueRes::Finite(point.clone())
    }
 }
 
 impl PointFrom<StandardProjectiveCoordinates> for AffineCoordinates {
-   fn convert_from(jacob: &StandardProjectiveCoordinates, p: &BigInt) -> AffineCoordinates {
-      // fast fail
-      if jacob.z.is_zero() {
-         panic!("Zero division!")
-      }
+   fn convert_from<C: ECCurve>(
+      point: &StandardProjectiveCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<AffineCoordinates> {
+      point.to_affine(&curve.p())
+   }
+}
 
-      #[allow(non_snake_case)]
-      // Function to calculate 1/Z^n mod p as a multipication.
-      let inv_Zn_over_p = |z: &BigInt, n: usize, p: &BigInt| {
-         if z.is_one() {
-            BigInt::one()
-         } else {
-            let exp = p - (n + 1);
-            z.modpow(&exp, p)
-         }
-      };
+impl PointFrom<JacobianCoordinates> for StandardProjectiveCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &JacobianCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<StandardProjectiveCoordinates> {
+      if point.z.is_zero() {
+         return ECCValueRes::Infinity;
+      }
 
-      let inv_z = inv_Zn_over_p(&jacob.z, 1, p);
+      // (X/Z^2, Y/Z^3) = (XZ/Z^3, Y/Z^3)
+      let p = curve.p();
+      ECCValueRes::Finite(StandardProjectiveCoordinates {
+         x: (&point.x * &point.z).mod_floor(&p),
+         y: point.y.clone(),
+         z: point.z.modpow(&BigInt::from(3_u8), &p),
+      })
+   }
+}
 
-      let x = (&jacob.x * &inv_z).mod_floor(p);
-      let y = (&jacob.y * &inv_z).mod_floor(p);
+impl PointFrom<StandardProjectiveCoordinates> for JacobianCoordinates {
+   fn convert_from<C: ECCurve>(
+      point: &StandardProjectiveCoordinates,
+      curve: &C,
+   ) -> ECCValueRes<JacobianCoordinates> {
+      if point.is_point_at_infinity() {
+         return ECCValueRes::Infinity;
+      }
 
-      AffineCoordinates { x, y }
+      // (X/Z, Y/Z) = (XZ/Z^2, YZ^2/Z^3)
+      let p = curve.p();
+      ECCValueRes::Finite(JacobianCoordinates {
+         x: (&point.x * &point.z).mod_floor(&p),
+         y: (&point.y * point.z.modpow(&BigInt::from(2_u8), &p)).mod_floor(&p),
+         z: point.z.clone(),
+      })
    }
 }
 
@@ -306,7 +361,7 @@ impl From<ECCValue> for StandardProjectiveCoordinates {
    }
 }
 
-impl<'a> From<&'a StandardProjectiveCoordinates> for StandardProjectiveCoordinates {
+impl From<&StandardProjectiveCoordinates> for StandardProjectiveCoordinates {
    fn from(val: &StandardProjectiveCoordinates) -> StandardProjectiveCoordinates {
       StandardProjectiveCoordinates {
          x: val.x.clone(),
@@ -325,24 +380,17 @@ where
    fn try_new(x_str: T, y_str: T, z_str: T, base: U) -> Result<Self, Self::Error>;
 }
 
-impl NewPoint<&'static str, u32> for StandardProjectiveCoordinates {
-   type Error = ParseBigIntError;
+impl NewPoint<&str, u32> for StandardProjectiveCoordinates {
+   type Error = PointParseError;
 
    fn try_new(s1: &str, s2: &str, s3: &str, base: u32) -> Result<Self, Self::Error> {
-      let x = BigInt::from_str_radix(s1, base);
-      let y = BigInt::from_str_radix(s2, base);
-      let z = BigInt::from_str_radix(s3, base);
-
-      match (x, y, z) {
-         (Ok(x), Ok(y), Ok(z)) => Ok(StandardProjectiveCoordinates { x, y, z }),
-         _ => Err(ParseBigIntError::Other),
+      // `from_str_radix` panics on any other base.
+      if !(2..=36).contains(&base) {
+         return Err(PointParseError::InvalidBase(base));
       }
-   }
-}
-
-impl PartialEq for StandardProjectiveCoordinates {
-   fn eq(&self, other: &Self) -> bool {
-      let i = BigInt::zero();
-      AffineCoordinates::convert_from(self, &i) == other.convert_into(&i)
+      let x = BigInt::from_str_radix(s1, base)?;
+      let y = BigInt::from_str_radix(s2, base)?;
+      let z = BigInt::from_str_radix(s3, base)?;
+      Ok(StandardProjectiveCoordinates { x, y, z })
    }
 }
@@ -0,0 +1,3 @@
+mod scalar_decode_error;
+
+pub use self::scalar_decode_error::ScalarDecodeError;
@@ -0,0 +1,46 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+#[derive(Debug, Clone, PartialEq)]
+// Returned when a byte string is not the fixed-length, big-endian encoding of
+// a scalar in `[0, n)`, or not the DER encoding of a signature.
+pub enum ScalarDecodeError {
+   /// The input length was not the byte length of the curve order.
+   InvalidLength { expected: usize, found: usize },
+   /// The decoded value was not smaller than the curve order.
+   OutOfRange,
+   /// The input was not a strict DER encoding.
+   InvalidDER,
+}
+
+impl fmt::Display for ScalarDecodeError {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         ScalarDecodeError::InvalidLength { expected, found } => write!(
+            f,
+            "Scalar must be {} bytes long, but got {} bytes",
+            expected, found
+         ),
+         ScalarDecodeError::OutOfRange => write!(f, "Scalar is not smaller than the curve order"),
+         ScalarDecodeError::InvalidDER => write!(f, "Input is not strictly DER encoded"),
+      }
+   }
+}
+
+// This is important for other errors to wrap this one.
+#[cfg(feature = "std")]
+impl error::Error for ScalarDecodeError {
+   fn description(&self) -> &str {
+      match *self {
+         ScalarDecodeError::InvalidLength { .. } => "Scalar has an invalid length",
+         ScalarDecodeError::OutOfRange => "Scalar is not smaller than the curve order",
+         ScalarDecodeError::InvalidDER => "Input is not strictly DER encoded",
+      }
+   }
+
+   fn cause(&self) -> Option<&dyn error::Error> {
+      // Generic error, underlying cause isn't tracked.
+      None
+   }
+}
@@ -0,0 +1,241 @@
+use num::bigint::{RandBigInt, Sign};
+use num::{BigInt, Integer, One, Zero};
+use rand::Rng;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Add, Mul, Neg, Sub};
+
+use super::curves::ECCurve;
+
+pub mod errors;
+
+/// A scalar modulo the order `n` of a curve's base point.
+///
+/// The curve is part of the type, so scalars of different curves can not be
+/// mixed up. Every constructor reduces the value into `[0, n)`, which means
+/// negative or oversized integers are taken modulo `n`.
+///
+/// ### Example
+///
+/// ```
+/// use ecc_calc::ecc::prime::curves::{ECCurve, ECCurveCalculation, Secp256k1};
+/// use ecc_calc::ecc::prime::scalar::Scalar;
+/// use ecc_calc::ecc::ECCValueRes;
+/// use num::BigInt;
+///
+/// # fn main() {
+/// # let curve = Secp256k1::new();
+/// # let g = curve.base_point();
+/// let k = Scalar::<Secp256k1>::from(BigInt::from(-1));  // n - 1
+/// let kG = curve.multipy_point(&g, k);
+/// assert_eq!(kG, ECCValueRes::Finite(curve.negate_point(&g)));
+/// # }
+/// ```
+pub struct Scalar<C: ECCurve> {
+   value: BigInt,
+   /// `n`, kept so the arithmetic doesn't parse it again for every result.
+   order: BigInt,
+   curve: PhantomData<C>,
+}
+
+impl<C: ECCurve> Scalar<C> {
+   /// Return the scalar `value mod n`.
+   pub fn new(value: BigInt) -> Self { Self::reduce(value, Self::order()) }
+
+   /// Return the scalar `value mod order`, `order` being `n`.
+   fn reduce(value: BigInt, order: BigInt) -> Self {
+      Scalar {
+         value: value.mod_floor(&order),
+         order,
+         curve: PhantomData,
+      }
+   }
+
+   pub fn zero() -> Self { Self::new(BigInt::zero()) }
+
+   pub fn one() -> Self { Self::new(BigInt::one()) }
+
+   /// Return a uniformly random scalar in `[1, n)`.
+   pub fn random<R: Rng>(rng: &mut R) -> Self {
+      let order = Self::order();
+      Scalar {
+         value: rng.gen_bigint_range(&BigInt::one(), &order),
+         order,
+         curve: PhantomData,
+      }
+   }
+
+   /// Return the order `n` the scalar is reduced by.
+   pub fn order() -> BigInt { C::new().n() }
+
+   /// Return the length of the byte encoding, which is the byte length of `n`.
+   pub fn byte_len() -> usize { (Self::order().bits() as usize).div_ceil(8) }
+
+   /// Return the reduced value in `[0, n)`.
+   pub fn value(&self) -> &BigInt { &self.value }
+
+   pub fn into_bigint(self) -> BigInt { self.value }
+
+   pub fn is_zero(&self) -> bool { self.value.is_zero() }
+
+   /// Return `k^-1 mod n`, or `None` if `k` is zero.
+   pub fn invert(&self) -> Option<Self> {
+      if self.is_zero() {
+         return None;
+      }
+
+      // `n` is prime, so k^(n - 2) = k^-1 mod n.
+      let exp = &self.order - BigInt::from(2_u8);
+      Some(Scalar {
+         value: self.value.modpow(&exp, &self.order),
+         order: self.order.clone(),
+         curve: PhantomData,
+      })
+   }
+
+   /// Encode the scalar as a big-endian byte string of `byte_len()` bytes.
+   pub fn to_bytes_be(&self) -> Vec<u8> {
+      let (_, bytes) = self.value.to_bytes_be();
+      let len = (self.order.bits() as usize).div_ceil(8);
+
+      if self.is_zero() {
+         return vec![0_u8; len];
+      }
+      let mut encoded = vec![0_u8; len - bytes.len()];
+      encoded.extend(bytes);
+      encoded
+   }
+
+   /// Decode a big-endian byte string of exactly `byte_len()` bytes.
+   /// Values not smaller than `n` are rejected instead of being reduced.
+   pub fn from_bytes_be(bytes: &[u8]) -> Result<Self, errors::ScalarDecodeError> {
+      let expected = Self::byte_len();
+      if bytes.len() != expected {
+         return Err(errors::ScalarDecodeError::InvalidLength {
+            expected,
+            found: bytes.len(),
+         });
+      }
+
+      let value = BigInt::from_bytes_be(Sign::Plus, bytes);
+      let order = Self::order();
+      if value >= order {
+         return Err(errors::ScalarDecodeError::OutOfRange);
+      }
+      Ok(Scalar {
+         value,
+         order,
+         curve: PhantomData,
+      })
+   }
+}
+
+/* -- Trait impls -- */
+// These are written by hand, as deriving them would require `C` itself to
+// implement the traits.
+impl<C: ECCurve> Clone for Scalar<C> {
+   fn clone(&self) -> Self {
+      Scalar {
+         value: self.value.clone(),
+         order: self.order.clone(),
+         curve: PhantomData,
+      }
+   }
+}
+
+impl<C: ECCurve> PartialEq for Scalar<C> {
+   fn eq(&self, other: &Self) -> bool { self.value == other.value }
+}
+
+impl<C: ECCurve> Eq for Scalar<C> {}
+
+impl<C: ECCurve> From<BigInt> for Scalar<C> {
+   fn from(value: BigInt) -> Self { Self::new(value) }
+}
+
+impl<C: ECCurve> From<&BigInt> for Scalar<C> {
+   fn from(value: &BigInt) -> Self { Self::new(value.clone()) }
+}
+/* -- Trait impls -- */
+
+/* -- Formatter impls -- */
+impl<C: ECCurve> fmt::Debug for Scalar<C> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "Scalar(")?;
+      fmt::Debug::fmt(&self.value, f)?;
+      write!(f, ")")
+   }
+}
+
+impl<C: ECCurve> fmt::Display for Scalar<C> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::Display::fmt(&self.value, f) }
+}
+
+impl<C: ECCurve> fmt::LowerHex for Scalar<C> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::LowerHex::fmt(&self.value, f) }
+}
+
+impl<C: ECCurve> fmt::UpperHex for Scalar<C> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { fmt::UpperHex::fmt(&self.value, f) }
+}
+/* -- Formatter impls -- */
+
+/* -- Arithmetic impls -- */
+impl<C: ECCurve> Add<&Scalar<C>> for &Scalar<C> {
+   type Output = Scalar<C>;
+
+   fn add(self, other: &Scalar<C>) -> Scalar<C> {
+      Scalar::reduce(&self.value + &other.value, self.order.clone())
+   }
+}
+
+impl<C: ECCurve> Add for Scalar<C> {
+   type Output = Scalar<C>;
+
+   fn add(self, other: Scalar<C>) -> Scalar<C> { &self + &other }
+}
+
+impl<C: ECCurve> Sub<&Scalar<C>> for &Scalar<C> {
+   type Output = Scalar<C>;
+
+   fn sub(self, other: &Scalar<C>) -> Scalar<C> {
+      Scalar::reduce(&self.value - &other.value, self.order.clone())
+   }
+}
+
+impl<C: ECCurve> Sub for Scalar<C> {
+   type Output = Scalar<C>;
+
+   fn sub(self, other: Scalar<C>) -> Scalar<C> { &self - &other }
+}
+
+impl<C: ECCurve> Mul<&Scalar<C>> for &Scalar<C> {
+   type Output = Scalar<C>;
+
+   fn mul(self, other: &Scalar<C>) -> Scalar<C> {
+      Scalar::reduce(&self.value * &other.value, self.order.clone())
+   }
+}
+
+impl<C: ECCurve> Mul for Scalar<C> {
+   type Output = Scalar<C>;
+
+   fn mul(self, other: Scalar<C>) -> Scalar<C> { &self * &other }
+}
+
+impl<C: ECCurve> Neg for &Scalar<C> {
+   type Output = Scalar<C>;
+
+   fn neg(self) -> Scalar<C> { Scalar::reduce(-&self.value, self.order.clone()) }
+}
+
+impl<C: ECCurve> Neg for Scalar<C> {
+   type Output = Scalar<C>;
+
+   fn neg(self) -> Scalar<C> { -&self }
+}
+/* -- Arithmetic impls -- */
+
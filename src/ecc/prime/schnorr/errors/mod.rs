@@ -0,0 +1,3 @@
+mod schnorr_error;
+
+pub use self::schnorr_error::SchnorrError;
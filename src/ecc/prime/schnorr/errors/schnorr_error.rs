@@ -0,0 +1,46 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+#[derive(Debug, Clone, PartialEq)]
+// Returned when a BIP-340 key or signature can not be decoded, created or
+// verified.
+pub enum SchnorrError {
+   /// The private key was zero or not smaller than `n`.
+   InvalidPrivateKey,
+   /// The x-only public key is not the x coordinate of a point on the curve.
+   InvalidPublicKey,
+   /// The signature is not 64 bytes, or `r >= p` or `s >= n`.
+   InvalidSignature,
+   /// The signature does not match the message and public key.
+   VerificationFailed,
+}
+
+impl fmt::Display for SchnorrError {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         SchnorrError::InvalidPrivateKey => write!(f, "Private key is out of range"),
+         SchnorrError::InvalidPublicKey => write!(f, "Public key is not on the curve"),
+         SchnorrError::InvalidSignature => write!(f, "Signature is malformed"),
+         SchnorrError::VerificationFailed => write!(f, "Signature does not match"),
+      }
+   }
+}
+
+// This is important for other errors to wrap this one.
+#[cfg(feature = "std")]
+impl error::Error for SchnorrError {
+   fn description(&self) -> &str {
+      match *self {
+         SchnorrError::InvalidPrivateKey => "Private key is out of range",
+         SchnorrError::InvalidPublicKey => "Public key is not on the curve",
+         SchnorrError::InvalidSignature => "Signature is malformed",
+         SchnorrError::VerificationFailed => "Signature does not match",
+      }
+   }
+
+   fn cause(&self) -> Option<&dyn error::Error> {
+      // Generic error, underlying cause isn't tracked.
+      None
+   }
+}
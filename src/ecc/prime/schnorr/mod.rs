@@ -0,0 +1,244 @@
+//! BIP-340 Schnorr signatures on secp256k1.
+//! @see https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki
+//!
+//! ### Example
+//!
+//! ```
+//! use ecc_calc::ecc::prime::scalar::Scalar;
+//! use ecc_calc::ecc::prime::schnorr::errors::SchnorrError;
+//! use ecc_calc::ecc::prime::schnorr::{self, XOnlyPublicKey};
+//! use num::BigInt;
+//!
+//! # fn main() -> Result<(), SchnorrError> {
+//! # let d = Scalar::new(BigInt::from(0x1234_5678_u64));
+//! # let message = b"message";
+//! # let aux_rand = [0_u8; 32];
+//! let public_key = XOnlyPublicKey::from_private_key(&d)?;
+//! let signature = schnorr::sign(&d, message, &aux_rand)?;
+//! schnorr::verify(&public_key, message, &signature)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use num::bigint::Sign;
+use num::{BigInt, Integer};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use super::curve_point::CurvePoint;
+use super::curves::{ECCurve, Secp256k1};
+use super::points::{AffineCoordinates, JacobianCoordinates, PointFrom};
+use super::scalar::Scalar;
+use super::super::ECCValueRes;
+
+pub mod errors;
+
+use self::errors::SchnorrError;
+
+type Point = CurvePoint<Secp256k1, JacobianCoordinates>;
+
+/// Return `SHA256(SHA256(tag) || SHA256(tag) || data[0] || data[1] || ...)`.
+pub fn tagged_hash(tag: &str, data: &[&[u8]]) -> [u8; 32] {
+   let tag_hash = Sha256::digest(tag.as_bytes());
+   let mut hasher = Sha256::new();
+   hasher.update(tag_hash);
+   hasher.update(tag_hash);
+   for bytes in data {
+      hasher.update(bytes);
+   }
+
+   let mut hash = [0_u8; 32];
+   hash.copy_from_slice(&hasher.finalize());
+   hash
+}
+
+/// A public key given only by its x coordinate. The point is the one with an
+/// even y coordinate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XOnlyPublicKey {
+   point: AffineCoordinates,
+}
+
+impl XOnlyPublicKey {
+   /// Return the public key of the private key `d`, i.e. `dG` with its y
+   /// coordinate dropped.
+   pub fn from_private_key(d: &Scalar<Secp256k1>) -> Result<Self, SchnorrError> {
+      if d.is_zero() {
+         return Err(SchnorrError::InvalidPrivateKey);
+      }
+      Ok(XOnlyPublicKey {
+         point: even_y(&to_affine(&(&Point::generator() * d))),
+      })
+   }
+
+   /// Decode the 32 byte x coordinate, `lift_x` in BIP-340.
+   pub fn from_bytes(bytes: &[u8]) -> Result<Self, SchnorrError> {
+      if bytes.len() != 32 {
+         return Err(SchnorrError::InvalidPublicKey);
+      }
+
+      let x = BigInt::from_bytes_be(Sign::Plus, bytes);
+      lift_x(&x)
+         .map(|point| XOnlyPublicKey { point })
+         .ok_or(SchnorrError::InvalidPublicKey)
+   }
+
+   pub fn to_bytes(&self) -> [u8; 32] { field_bytes(&self.point.x) }
+
+   /// Return the point with the even y coordinate.
+   pub fn point(&self) -> &AffineCoordinates { &self.point }
+}
+
+/// A BIP-340 signature, the x coordinate of `R` and `s`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+   pub r: BigInt,
+   pub s: Scalar<Secp256k1>,
+}
+
+impl Signature {
+   /// Decode `bytes(r) || bytes(s)`, rejecting `r >= p` and `s >= n`.
+   pub fn from_bytes(bytes: &[u8]) -> Result<Self, SchnorrError> {
+      if bytes.len() != 64 {
+         return Err(SchnorrError::InvalidSignature);
+      }
+
+      let r = BigInt::from_bytes_be(Sign::Plus, &bytes[..32]);
+      if r >= Secp256k1::new().p() {
+         return Err(SchnorrError::InvalidSignature);
+      }
+      let s = Scalar::from_bytes_be(&bytes[32..]).map_err(|_| SchnorrError::InvalidSignature)?;
+      Ok(Signature { r, s })
+   }
+
+   pub fn to_bytes(&self) -> [u8; 64] {
+      let mut bytes = [0_u8; 64];
+      bytes[..32].copy_from_slice(&field_bytes(&self.r));
+      bytes[32..].copy_from_slice(&self.s.to_bytes_be());
+      bytes
+   }
+}
+
+/// Sign `message` with the private key `d`. `aux_rand` is mixed into the
+/// nonce, it should be fresh randomness but signing stays safe without it.
+pub fn sign(
+   d: &Scalar<Secp256k1>,
+   message: &[u8],
+   aux_rand: &[u8; 32],
+) -> Result<Signature, SchnorrError> {
+   if d.is_zero() {
+      return Err(SchnorrError::InvalidPrivateKey);
+   }
+
+   let point_p = to_affine(&(&Point::generator() * d));
+   let d = if point_p.y.is_even() { d.clone() } else { -d };
+   let public_key = XOnlyPublicKey {
+      point: even_y(&point_p),
+   };
+   let px = public_key.to_bytes();
+
+   let mut t = tagged_hash("BIP0340/aux", &[aux_rand]);
+   for (t, d) in t.iter_mut().zip(d.to_bytes_be()) {
+      *t ^= d;
+   }
+   let rand = tagged_hash("BIP0340/nonce", &[&t, &px, message]);
+   let k = Scalar::<Secp256k1>::new(BigInt::from_bytes_be(Sign::Plus, &rand));
+   if k.is_zero() {
+      return Err(SchnorrError::InvalidSignature);
+   }
+
+   let point_r = to_affine(&(&Point::generator() * &k));
+   let k = if point_r.y.is_even() { k } else { -k };
+   let e = challenge(&point_r.x, &px, message);
+
+   let signature = Signature {
+      r: point_r.x,
+      s: &k + &(&e * &d),
+   };
+   // As recommended by BIP-340, to catch faults during the calculation.
+   verify(&public_key, message, &signature)?;
+   Ok(signature)
+}
+
+/// Verify `signature` of `message` against `public_key`.
+pub fn verify(
+   public_key: &XOnlyPublicKey,
+   message: &[u8],
+   signature: &Signature,
+) -> Result<(), SchnorrError> {
+   let e = challenge(&signature.r, &public_key.to_bytes(), message);
+
+   // R = sG - eP
+   let point_r = &Point::generator() * &signature.s - &public_key_point(public_key) * &e;
+   match point_r.to_affine() {
+      ECCValueRes::Finite(ref point) if point.y.is_even() && point.x == signature.r => Ok(()),
+      _ => Err(SchnorrError::VerificationFailed),
+   }
+}
+
+/// Verify all signatures at once, which is faster than verifying them one by
+/// one. On failure, it is not known which signature is invalid.
+///
+/// Every signature but the first is weighted with a random scalar from `rng`,
+/// so `rng` has to be a cryptographically secure generator.
+pub fn verify_batch<R: Rng>(
+   rng: &mut R,
+   batch: &[(&XOnlyPublicKey, &[u8], &Signature)],
+) -> Result<(), SchnorrError> {
+   // (s1 + a2s2 + ... + ausu)G = R1 + a2R2 + ... + auRu + e1P1 + (a2e2)P2 + ... + (aueu)Pu
+   let mut s_sum = Scalar::<Secp256k1>::zero();
+   let mut right = Point::identity();
+   for (i, &(public_key, message, signature)) in batch.iter().enumerate() {
+      let a = if i == 0 { Scalar::one() } else { Scalar::random(rng) };
+      let e = challenge(&signature.r, &public_key.to_bytes(), message);
+      let point_r = lift_x(&signature.r).ok_or(SchnorrError::VerificationFailed)?;
+      let point_r = JacobianCoordinates::convert_from(&point_r, &Secp256k1::new());
+      let point_r = Point::new(point_r.unwrap_finite());
+
+      s_sum = &s_sum + &(&a * &signature.s);
+      right += &point_r * &a;
+      right += &public_key_point(public_key) * &(&a * &e);
+   }
+
+   if Point::generator() * s_sum == right {
+      Ok(())
+   } else {
+      Err(SchnorrError::VerificationFailed)
+   }
+}
+
+/// `int(hash_BIP0340/challenge(bytes(r) || bytes(P) || m)) mod n`
+fn challenge(r: &BigInt, px: &[u8; 32], message: &[u8]) -> Scalar<Secp256k1> {
+   let hash = tagged_hash("BIP0340/challenge", &[&field_bytes(r), px, message]);
+   Scalar::new(BigInt::from_bytes_be(Sign::Plus, &hash))
+}
+
+/// Return the point with x coordinate `x` and an even y coordinate.
+fn lift_x(x: &BigInt) -> Option<AffineCoordinates> { Secp256k1::new().decompress_point(x, false) }
+
+fn even_y(point: &AffineCoordinates) -> AffineCoordinates {
+   if point.y.is_even() {
+      point.clone()
+   } else {
+      AffineCoordinates {
+         x: point.x.clone(),
+         y: Secp256k1::new().p() - &point.y,
+      }
+   }
+}
+
+fn public_key_point(public_key: &XOnlyPublicKey) -> Point {
+   let point = JacobianCoordinates::convert_from(&public_key.point, &Secp256k1::new());
+   Point::new(point.unwrap_finite())
+}
+
+/// Only used for multiples of `G` by a non-zero scalar, which are finite.
+fn to_affine(point: &Point) -> AffineCoordinates { point.to_affine().unwrap_finite() }
+
+/// Encode a field element as 32 big-endian bytes.
+fn field_bytes(x: &BigInt) -> [u8; 32] {
+   let (_, bytes) = x.to_bytes_be();
+   let mut encoded = [0_u8; 32];
+   encoded[32 - bytes.len()..].copy_from_slice(&bytes);
+   encoded
+}
@@ -0,0 +1,261 @@
+//! Opt-in recording of the steps of a calculation, the NAF digits, every
+//! doubling and addition with its intermediate values and the resulting
+//! coordinates, for showing how a multiplication proceeds.
+//!
+//! Nothing is recorded unless the calculation runs inside `record`, the
+//! `log` output is unaffected either way. Recording keeps the steps per
+//! thread and needs the `std` feature, without it nothing is recorded.
+//!
+//! ### Example
+//!
+//! ```
+//! use ecc_calc::ecc::prime::curves::{ECCurve, ECCurveCalculation, Secp256k1};
+//! use ecc_calc::ecc::prime::points::{JacobianCoordinates, PointFrom};
+//! use ecc_calc::ecc::prime::trace;
+//! use num::BigInt;
+//!
+//! # fn main() {
+//! # let curve = Secp256k1::new();
+//! # let point_g = JacobianCoordinates::convert_from(&curve.base_point(), &curve).unwrap_finite();
+//! # let k = BigInt::from(1234);
+//! let (point, trace) = trace::record(|| curve.multipy_point(&point_g, k));
+//! println!("{}", trace.to_markdown());
+//! # assert!(!trace.steps.is_empty());
+//! # }
+//! ```
+
+use num::BigInt;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+
+#[cfg(feature = "std")]
+thread_local! {
+   static RECORDER: RefCell<Option<Vec<Step>>> = const { RefCell::new(None) };
+}
+
+/// Run `calculation` and return its result with the steps it took on this
+/// thread. A `record` inside `calculation` keeps its steps to itself.
+#[cfg(feature = "std")]
+pub fn record<T, F: FnOnce() -> T>(calculation: F) -> (T, Trace) {
+   // Restores the enclosing recording, also when `calculation` panics.
+   struct Restore(Option<Vec<Step>>);
+
+   impl Drop for Restore {
+      fn drop(&mut self) {
+         let previous = self.0.take();
+         RECORDER.with(|recorder| *recorder.borrow_mut() = previous);
+      }
+   }
+
+   let previous = RECORDER.with(|recorder| recorder.replace(Some(Vec::new())));
+   let _restore = Restore(previous);
+   let result = calculation();
+   let steps = RECORDER.with(|recorder| recorder.replace(None)).unwrap_or_default();
+   (result, Trace { steps })
+}
+
+/// Whether the calculation is being recorded.
+#[cfg(feature = "std")]
+pub fn is_recording() -> bool { RECORDER.with(|recorder| recorder.borrow().is_some()) }
+
+/// Whether the calculation is being recorded, never without `std`.
+#[cfg(not(feature = "std"))]
+pub fn is_recording() -> bool { false }
+
+/// Record the digits of NAF(k), least significant first.
+pub fn naf(k: &BigInt, digits: &[i8]) {
+   push(|| Step::NAF {
+      k: k.clone(),
+      digits: digits.to_vec(),
+   });
+}
+
+/// Record a point operation in `coordinates` with its named intermediate
+/// `values` and the `result` coordinates.
+pub fn operation(
+   operation: Operation,
+   coordinates: &'static str,
+   values: &[(&'static str, &BigInt)],
+   result: &[(&'static str, &BigInt)],
+) {
+   let to_owned = |values: &[(&'static str, &BigInt)]| {
+      values.iter().map(|&(name, value)| (name, value.clone())).collect()
+   };
+   push(|| Step::Operation {
+      operation,
+      coordinates,
+      values: to_owned(values),
+      result: to_owned(result),
+   });
+}
+
+#[cfg(feature = "std")]
+fn push<F: FnOnce() -> Step>(step: F) {
+   RECORDER.with(|recorder| {
+      if let Some(ref mut steps) = *recorder.borrow_mut() {
+         steps.push(step());
+      }
+   });
+}
+
+#[cfg(not(feature = "std"))]
+fn push<F: FnOnce() -> Step>(_step: F) {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+   Addition,
+   /// An addition of a point with `z = 1`, which only `JacobianCoordinates`
+   /// has cheaper formulas for. The other representations record these as
+   /// `Addition`.
+   MixedAddition,
+   Doubling,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+   NAF {
+      k: BigInt,
+      /// Least significant first.
+      digits: Vec<i8>,
+   },
+   Operation {
+      operation: Operation,
+      /// The name of the point type, e.g. `JacobianCoordinates`.
+      coordinates: &'static str,
+      values: Vec<(&'static str, BigInt)>,
+      result: Vec<(&'static str, BigInt)>,
+   },
+}
+
+/// The recorded steps in order. `Display` renders them as text, numbers are
+/// hex throughout.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trace {
+   pub steps: Vec<Step>,
+}
+
+impl Trace {
+   /// Render as a JSON object `{"steps": [...]}`, numbers as hex strings.
+   pub fn to_json(&self) -> String {
+      let object = |values: &[(&str, BigInt)]| {
+         let members: Vec<String> = values
+            .iter()
+            .map(|&(name, ref value)| format!("\"{}\":\"{:x}\"", name, value))
+            .collect();
+         format!("{{{}}}", members.join(","))
+      };
+      let steps: Vec<String> = self
+         .steps
+         .iter()
+         .map(|step| match *step {
+            Step::NAF { ref k, ref digits } => {
+               let digits: Vec<String> = digits.iter().map(i8::to_string).collect();
+               format!("{{\"step\":\"naf\",\"k\":\"{:x}\",\"digits\":[{}]}}", k, digits.join(","))
+            },
+            Step::Operation {
+               operation,
+               coordinates,
+               ref values,
+               ref result,
+            } => format!(
+               "{{\"step\":\"{}\",\"coordinates\":\"{}\",\"values\":{},\"result\":{}}}",
+               operation.id(),
+               coordinates,
+               object(values),
+               object(result)
+            ),
+         })
+         .collect();
+      format!("{{\"steps\":[{}]}}", steps.join(","))
+   }
+
+   /// Render as a Markdown table with a row per step.
+   pub fn to_markdown(&self) -> String {
+      let cell = |values: &[(&str, BigInt)]| {
+         let values: Vec<String> =
+            values.iter().map(|&(name, ref value)| format!("{} = {:x}", name, value)).collect();
+         values.join("<br>")
+      };
+      let mut table = String::from(
+         "| Step | Operation | Coordinates | Values | Result |\n\
+          |---|---|---|---|---|\n",
+      );
+      for (i, step) in self.steps.iter().enumerate() {
+         let row = match *step {
+            Step::NAF { ref k, ref digits } => {
+               format!("| {} | NAF | | k = {:x} | {:?} |\n", i + 1, k, digits)
+            },
+            Step::Operation {
+               operation,
+               coordinates,
+               ref values,
+               ref result,
+            } => format!(
+               "| {} | {} | {} | {} | {} |\n",
+               i + 1,
+               operation,
+               coordinates,
+               cell(values),
+               cell(result)
+            ),
+         };
+         table.push_str(&row);
+      }
+      table
+   }
+}
+
+impl Operation {
+   fn id(&self) -> &'static str {
+      match *self {
+         Operation::Addition => "addition",
+         Operation::MixedAddition => "mixed_addition",
+         Operation::Doubling => "doubling",
+      }
+   }
+}
+
+/* -- Formatter impls -- */
+impl fmt::Display for Operation {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match *self {
+         Operation::Addition => write!(f, "Point Addition"),
+         Operation::MixedAddition => write!(f, "Point Mixed Addition"),
+         Operation::Doubling => write!(f, "Point Doubling"),
+      }
+   }
+}
+
+impl fmt::Display for Trace {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      for (i, step) in self.steps.iter().enumerate() {
+         match *step {
+            Step::NAF { ref k, ref digits } => {
+               writeln!(f, "{}. NAF(k), k = {:x}: {:?}", i + 1, k, digits)?;
+            },
+            Step::Operation {
+               operation,
+               coordinates,
+               ref values,
+               ref result,
+            } => {
+               writeln!(f, "{}. {} in {}", i + 1, operation, coordinates)?;
+               for &(name, ref value) in values {
+                  writeln!(f, "   {}: {:x}", name, value)?;
+               }
+               let result: Vec<String> = result
+                  .iter()
+                  .map(|&(name, ref value)| format!("{}: {:x}", name, value))
+                  .collect();
+               writeln!(f, "   => {}", result.join(", "))?;
+            },
+         }
+      }
+      Ok(())
+   }
+}
@@ -1,33 +1,38 @@
-#![feature(range_contains)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+extern crate alloc;
 #[macro_use]
 extern crate log;
 
 pub mod ecc;
 
 #[cfg(test)]
-#[allow(unused_qualifications)]
+#[allow(unused_qualifications, non_snake_case)]
 mod overall {
-   #![allow(non_snake_case)]
-
-   extern crate num;
-   extern crate simple_logger;
-
-   use self::num::BigInt;
+   use num::{BigInt, Zero};
 
    use super::ecc::prime::curves::{ECCurve, ECCurveCalculation, Secp256k1, Secp256r1};
    use super::ecc::prime::points::affine::{AffineCoordinates, NewPoint as NewAffine};
+   use super::ecc::prime::points::jacobian::JacobianCoordinates;
+   use super::ecc::prime::points::{
+      ChudnovskyJacobianCoordinates,
+      CompleteProjectiveCoordinates,
+      ModifiedJacobianCoordinates,
+      PointCalculation,
+      PointFrom,
+   };
    use super::ecc::prime::points::standard_projective::StandardProjectiveCoordinates;
+   use super::ecc::prime::scalar::Scalar;
    use super::ecc::prime::ECCurvePoint;
 
    // #[test]
    fn create_affine_point() -> AffineCoordinates {
-      return AffineCoordinates::try_new(
+      AffineCoordinates::try_new(
          "6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296",
          "4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5",
          16,
       )
-      .unwrap();
+      .unwrap()
    }
 
    #[test]
@@ -58,8 +63,15 @@ mod overall {
 
       let point = secp256r1.base_point(); // AffineCoordinates
 
-      assert_eq!(secp256r1.point_is_on_curve(&point), true);
-      assert_eq!(secp256k1.point_is_on_curve(&point), false);
+      assert!(secp256r1.point_is_on_curve(&point));
+      assert!(!secp256k1.point_is_on_curve(&point));
+
+      let infinity = JacobianCoordinates {
+         x: BigInt::from(1),
+         y: BigInt::from(1),
+         z: BigInt::zero(),
+      };
+      assert!(secp256r1.point_is_on_curve(&infinity));
    }
 
    #[test]
@@ -78,19 +90,16 @@ mod overall {
       println!("\nG, 2G test 2 on {}", curve.name());
       let point_G = curve.convert_point_to::<StandardProjectiveCoordinates>(&curve.base_point());
       println!("G={:x}", &curve.base_point());
-      match point_G {
-         Ok(point) => {
-            println!("G= {:x}", point);
-            let point_2G = curve.multipy_point(&point, BigInt::from(2));
-            println!("2G= {:064x}", point_2G);
-            println!(
-               "2G= {:064x}",
-               curve
-                  .convert_point_to::<AffineCoordinates>(&point_2G)
-                  .unwrap()
-            )
-         }
-         _ => (),
+      if let Ok(point) = point_G {
+         println!("G= {:x}", point);
+         let point_2G = curve.multipy_point(&point, BigInt::from(2));
+         println!("2G= {:064x}", point_2G);
+         println!(
+            "2G= {:064x}",
+            curve
+               .convert_point_to::<AffineCoordinates>(&point_2G)
+               .unwrap()
+         )
       }
    }
 
@@ -100,19 +109,16 @@ mod overall {
       println!("\nG, 2G test 2 on {}", curve.name());
       let point_G = curve.convert_point_to::<StandardProjectiveCoordinates>(&curve.base_point());
       println!("G={:x}", &curve.base_point());
-      match point_G {
-         Ok(point) => {
-            println!("G= {:x}", point);
-            let point_2G = curve.multipy_point(&point, BigInt::from(2));
-            println!("2G= {:064x}", point_2G);
-            println!(
-               "2G= {:064x}",
-               curve
-                  .convert_point_to::<AffineCoordinates>(&point_2G)
-                  .unwrap()
-            )
-         }
-         _ => (),
+      if let Ok(point) = point_G {
+         println!("G= {:x}", point);
+         let point_2G = curve.multipy_point(&point, BigInt::from(2));
+         println!("2G= {:064x}", point_2G);
+         println!(
+            "2G= {:064x}",
+            curve
+               .convert_point_to::<AffineCoordinates>(&point_2G)
+               .unwrap()
+         )
       }
    }
 
@@ -123,19 +129,16 @@ mod overall {
       println!("\nn * G should be inf test on {}", curve.name());
       let point_G = curve.convert_point_to::<StandardProjectiveCoordinates>(&curve.base_point());
       let n = curve.n();
-      match point_G {
-         Ok(point) => {
-            let point_R = curve.multipy_point(&point, n);
-
-            println!("R= {:x}", point_R);
-            println!(
-               "R= {:x}",
-               curve
-                  .convert_point_to::<AffineCoordinates>(&point_R)
-                  .unwrap()
-            );
-         }
-         _ => (),
+      if let Ok(point) = point_G {
+         let point_R = curve.multipy_point(&point, n);
+
+         println!("R= {:x}", point_R);
+         println!(
+            "R= {:x}",
+            curve
+               .convert_point_to::<AffineCoordinates>(&point_R)
+               .unwrap()
+         );
       }
    }
 
@@ -146,19 +149,1770 @@ mod overall {
       println!("\nn * G should be inf test on {}", curve.name());
       let point_G = curve.convert_point_to::<StandardProjectiveCoordinates>(&curve.base_point());
       let n = curve.n();
-      match point_G {
-         Ok(point) => {
-            let point_R = curve.multipy_point(&point, n);
-
-            println!("R= {:x}", point_R);
-            println!(
-               "R= {:x}",
-               curve
-                  .convert_point_to::<AffineCoordinates>(&point_R)
-                  .unwrap()
+      if let Ok(point) = point_G {
+         let point_R = curve.multipy_point(&point, n);
+
+         println!("R= {:x}", point_R);
+         println!(
+            "R= {:x}",
+            curve
+               .convert_point_to::<AffineCoordinates>(&point_R)
+               .unwrap()
+         );
+      }
+   }
+
+   #[test]
+   fn scalars_are_reduced_before_multipication_on_Secp256k1() {
+      let curve = Secp256k1::new();
+      let point_G = curve
+         .convert_point_to::<JacobianCoordinates>(&curve.base_point())
+         .unwrap();
+      let minus_G = AffineCoordinates::try_new(
+         "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+         "B7C52588D95C3B9AA25B0403F1EEF75702E84BB7597AABE663B82F6F04EF2777",
+         16,
+      )
+      .unwrap();
+      let point_2G = AffineCoordinates::try_new(
+         "C6047F9441ED7D6D3045406E95C07CD85C778E4B8CEF3CA7ABAC09B95C709EE5",
+         "1AE168FEA63DC339A3C58419466CEAEEF7F632653266D0E1236431A950CFE52A",
+         16,
+      )
+      .unwrap();
+
+      for k in [BigInt::from(-1), curve.n() - 1] {
+         let point_R = curve.multipy_point(&point_G, k);
+         assert_eq!(
+            curve
+               .convert_point_to::<AffineCoordinates>(&point_R)
+               .unwrap(),
+            minus_G
+         );
+      }
+
+      let point_R = curve.multipy_point(&point_G, curve.n() + 2);
+      assert_eq!(
+         curve
+            .convert_point_to::<AffineCoordinates>(&point_R)
+            .unwrap(),
+         point_2G
+      );
+
+      let point_R = curve.multipy_point(&point_G, Scalar::zero());
+      assert!(point_R.z.is_zero());
+   }
+
+   #[test]
+   fn scalars_are_reduced_before_multipication_on_Secp256r1() {
+      let curve = Secp256r1::new();
+      let point_G = curve
+         .convert_point_to::<StandardProjectiveCoordinates>(&curve.base_point())
+         .unwrap();
+      let minus_G = AffineCoordinates::try_new(
+         "6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296",
+         "B01CBD1C01E58065711814B583F061E9D431CCA994CEA1313449BF97C840AE0A",
+         16,
+      )
+      .unwrap();
+
+      let k: Scalar<Secp256r1> = -Scalar::one();
+      let point_R = curve.multipy_point(&point_G, k);
+      assert_eq!(
+         curve
+            .convert_point_to::<AffineCoordinates>(&point_R)
+            .unwrap(),
+         minus_G
+      );
+
+      let point_R = curve.multipy_point(&point_G, BigInt::from(-1));
+      assert_eq!(
+         curve
+            .convert_point_to::<AffineCoordinates>(&point_R)
+            .unwrap(),
+         minus_G
+      );
+   }
+
+   #[test]
+   fn scalars_are_reduced_mod_n() {
+      let n = Secp256k1::new().n();
+
+      assert!(Scalar::<Secp256k1>::from(n.clone()).is_zero());
+      assert_eq!(Scalar::<Secp256k1>::from(&n + 5).value(), &BigInt::from(5));
+      assert_eq!(Scalar::<Secp256k1>::from(BigInt::from(-1)).value(), &(&n - 1));
+   }
+
+   #[test]
+   fn scalar_arithmetic_wraps_around_n() {
+      let n = Secp256k1::new().n();
+      let minus_one = Scalar::<Secp256k1>::from(&n - 1);
+      let two = Scalar::<Secp256k1>::from(BigInt::from(2));
+
+      assert_eq!(&minus_one + &two, Scalar::one());
+      assert_eq!(Scalar::zero() - Scalar::one(), minus_one);
+      assert_eq!(&minus_one * &minus_one, Scalar::one());
+      assert_eq!(-two.clone() + two, Scalar::zero());
+   }
+
+   #[test]
+   fn scalar_inversion() {
+      let k = Scalar::<Secp256k1>::from(BigInt::from(0x1234));
+      assert_eq!(&k * &k.invert().unwrap(), Scalar::one());
+      assert!(Scalar::<Secp256k1>::zero().invert().is_none());
+   }
+
+   #[test]
+   fn random_scalars_are_non_zero() {
+      let mut rng = rand::thread_rng();
+      for _ in 0..16 {
+         let k = Scalar::<Secp256k1>::random(&mut rng);
+         assert!(!k.is_zero());
+         assert!(k.value() < &Secp256k1::new().n());
+      }
+   }
+
+   #[test]
+   fn scalars_encode_to_fixed_length_bytes() {
+      use super::ecc::prime::scalar::errors::ScalarDecodeError;
+
+      let one = Scalar::<Secp256k1>::one().to_bytes_be();
+      assert_eq!(one.len(), 32);
+      assert_eq!(one[31], 1);
+      assert_eq!(Scalar::<Secp256k1>::zero().to_bytes_be(), [0_u8; 32]);
+
+      let k = Scalar::<Secp256k1>::from(BigInt::from(-2));
+      assert_eq!(Scalar::from_bytes_be(&k.to_bytes_be()), Ok(k));
+
+      assert_eq!(
+         Scalar::<Secp256k1>::from_bytes_be(&[1_u8; 31]),
+         Err(ScalarDecodeError::InvalidLength {
+            expected: 32,
+            found: 31,
+         })
+      );
+      let (_, n) = Secp256k1::new().n().to_bytes_be();
+      assert_eq!(Scalar::<Secp256k1>::from_bytes_be(&n), Err(ScalarDecodeError::OutOfRange));
+   }
+
+   #[test]
+   fn jacobian_addition_of_two_projective_points_on_Secp256k1() {
+      let curve = Secp256k1::new();
+      let point_G = curve
+         .convert_point_to::<JacobianCoordinates>(&curve.base_point())
+         .unwrap();
+      // Neither of these has z = 1, so the general addition formula is used.
+      let point_2G = curve.double_point(&point_G);
+      let point_3G = curve.add_points(&point_2G, &point_G);
+      let point_5G = curve.add_points(&point_2G, &point_3G);
+
+      assert_eq!(
+         curve
+            .convert_point_to::<AffineCoordinates>(&point_5G)
+            .unwrap(),
+         AffineCoordinates::try_new(
+            "2F8BDE4D1A07209355B4A7250A5C5128E88B84BDDC619AB7CBA8D569B240EFE4",
+            "D8AC222636E5E3D6D4DBA9DDA6C9C426F788271BAB0D6840DCA87D3AA6AC62D6",
+            16,
+         )
+         .unwrap()
+      );
+   }
+
+   fn multipy_base_point_in<C, P>(curve: &C, k: BigInt) -> AffineCoordinates
+   where
+      C: ECCurve + ECCurveCalculation<P> + ECCurvePoint<P> + ECCurvePoint<AffineCoordinates>,
+      P: PointCalculation<C, Output = P> + PointFrom<AffineCoordinates>,
+      AffineCoordinates: PointFrom<P>,
+   {
+      let point_G = curve.convert_point_to::<P>(&curve.base_point()).unwrap();
+      let point_R = curve.multipy_point(&point_G, k);
+      curve
+         .convert_point_to::<AffineCoordinates>(&point_R)
+         .unwrap()
+   }
+
+   #[test]
+   fn all_coordinates_agree_on_multipication() {
+      let k = BigInt::from(0x1234);
+
+      let curve = Secp256k1::new();
+      let expected = AffineCoordinates::try_new(
+         "37A4AEF1F8423CA076E4B7D99A8CABFF40DDB8231F2A9F01081F15D7FA65C1BA",
+         "B96CED90A1B8F9B43A18FC900FF55AF2BE0E94B90A434FCA5B9E226B835024CD",
+         16,
+      )
+      .unwrap();
+      assert_eq!(multipy_base_point_in::<_, JacobianCoordinates>(&curve, k.clone()), expected);
+      assert_eq!(
+         multipy_base_point_in::<_, StandardProjectiveCoordinates>(&curve, k.clone()),
+         expected
+      );
+      assert_eq!(
+         multipy_base_point_in::<_, ChudnovskyJacobianCoordinates>(&curve, k.clone()),
+         expected
+      );
+      assert_eq!(
+         multipy_base_point_in::<_, ModifiedJacobianCoordinates>(&curve, k.clone()),
+         expected
+      );
+      assert_eq!(
+         multipy_base_point_in::<_, CompleteProjectiveCoordinates>(&curve, k.clone()),
+         expected
+      );
+
+      let curve = Secp256r1::new();
+      let expected = AffineCoordinates::try_new(
+         "ED5784A75391DC43ADCD42DBC4C938E80690C75B3F4309049D5076692F8DAFE9",
+         "7ED5E8B3D94DD41F677D0E25F6EA5B332495BBDB74923EABBE9E7D2C1D09A08A",
+         16,
+      )
+      .unwrap();
+      assert_eq!(multipy_base_point_in::<_, JacobianCoordinates>(&curve, k.clone()), expected);
+      assert_eq!(
+         multipy_base_point_in::<_, StandardProjectiveCoordinates>(&curve, k.clone()),
+         expected
+      );
+      assert_eq!(
+         multipy_base_point_in::<_, ChudnovskyJacobianCoordinates>(&curve, k.clone()),
+         expected
+      );
+      assert_eq!(
+         multipy_base_point_in::<_, ModifiedJacobianCoordinates>(&curve, k.clone()),
+         expected
+      );
+      assert_eq!(
+         multipy_base_point_in::<_, CompleteProjectiveCoordinates>(&curve, k.clone()),
+         expected
+      );
+   }
+
+   #[test]
+   fn point_convertion_between_projective_coordinates() {
+      let curve = Secp256r1::new();
+      let point_G = curve
+         .convert_point_to::<ModifiedJacobianCoordinates>(&curve.base_point())
+         .unwrap();
+      // Make sure z != 1 before going around.
+      let point_R = curve.double_point(&curve.double_point(&point_G));
+
+      let point_R = curve
+         .convert_point_to::<ChudnovskyJacobianCoordinates>(&point_R)
+         .unwrap();
+      let point_R = curve
+         .convert_point_to::<StandardProjectiveCoordinates>(&point_R)
+         .unwrap();
+      let point_R = curve
+         .convert_point_to::<JacobianCoordinates>(&point_R)
+         .unwrap();
+      let point_R = curve
+         .convert_point_to::<ModifiedJacobianCoordinates>(&point_R)
+         .unwrap();
+      // Doubling uses the carried aZ^4, so it has to survive the round trip.
+      let point_R = curve.double_point(&point_R);
+
+      assert_eq!(
+         curve
+            .convert_point_to::<AffineCoordinates>(&point_R)
+            .unwrap(),
+         multipy_base_point_in::<_, JacobianCoordinates>(&curve, BigInt::from(8))
+      );
+   }
+
+   #[test]
+   fn addition_of_points_sharing_y_coordinate_on_Secp256k1() {
+      let curve = Secp256k1::new();
+      // (beta * Gx, Gy) = lambda * G, where beta^3 = 1 mod p.
+      let point_P = AffineCoordinates::try_new(
+         "BCACE2E99DA01887AB0102B696902325872844067F15E98DA7BBA04400B88FCB",
+         "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+         16,
+      )
+      .unwrap();
+      let expected = AffineCoordinates::try_new(
+         "C994B69768832BCBFF5E9AB39AE8D1D3763BBF1E531BED98FE51DE5EE84F50FB",
+         "B7C52588D95C3B9AA25B0403F1EEF75702E84BB7597AABE663B82F6F04EF2777",
+         16,
+      )
+      .unwrap();
+
+      let point_G = curve
+         .convert_point_to::<StandardProjectiveCoordinates>(&curve.base_point())
+         .unwrap();
+      let point_Q = curve
+         .convert_point_to::<StandardProjectiveCoordinates>(&point_P)
+         .unwrap();
+      let point_R = curve.add_points(&point_G, &point_Q);
+      assert_eq!(
+         curve
+            .convert_point_to::<AffineCoordinates>(&point_R)
+            .unwrap(),
+         expected
+      );
+
+      let point_G = curve
+         .convert_point_to::<CompleteProjectiveCoordinates>(&curve.base_point())
+         .unwrap();
+      let point_Q = curve
+         .convert_point_to::<CompleteProjectiveCoordinates>(&point_P)
+         .unwrap();
+      let point_R = curve.add_points(&point_G, &point_Q);
+      assert_eq!(
+         curve
+            .convert_point_to::<AffineCoordinates>(&point_R)
+            .unwrap(),
+         expected
+      );
+   }
+
+   fn complete_formulas_handle_exceptional_cases<C>(curve: &C)
+   where
+      C: ECCurve
+         + ECCurveCalculation<CompleteProjectiveCoordinates>
+         + ECCurvePoint<AffineCoordinates>
+         + ECCurvePoint<CompleteProjectiveCoordinates>,
+   {
+      let point_G = curve
+         .convert_point_to::<CompleteProjectiveCoordinates>(&curve.base_point())
+         .unwrap();
+      let point_O = CompleteProjectiveCoordinates::from(super::ecc::ECCValue::Infinity);
+      let to_affine = |point: &CompleteProjectiveCoordinates| {
+         curve
+            .convert_point_to::<AffineCoordinates>(point)
+            .unwrap()
+      };
+
+      assert!(curve.add_points(&point_O, &point_O).is_point_at_infinity());
+      assert!(curve.double_point(&point_O).is_point_at_infinity());
+      assert_eq!(to_affine(&curve.add_points(&point_G, &point_O)), curve.base_point());
+      assert_eq!(to_affine(&curve.add_points(&point_O, &point_G)), curve.base_point());
+      assert_eq!(
+         to_affine(&curve.add_points(&point_G, &point_G)),
+         to_affine(&curve.double_point(&point_G))
+      );
+      assert!(curve
+         .subtract_points(&point_G, &point_G)
+         .is_point_at_infinity());
+   }
+
+   #[test]
+   fn complete_formulas_handle_exceptional_cases_on_Secp256k1() {
+      complete_formulas_handle_exceptional_cases(&Secp256k1::new());
+   }
+
+   #[test]
+   fn complete_formulas_handle_exceptional_cases_on_Secp256r1() {
+      complete_formulas_handle_exceptional_cases(&Secp256r1::new());
+   }
+
+   #[test]
+   fn affine_point_calculation_on_Secp256k1() {
+      use super::ecc::ECCValueRes;
+
+      let curve = Secp256k1::new();
+      let point_G = curve.base_point();
+      let expected = AffineCoordinates::try_new(
+         "37A4AEF1F8423CA076E4B7D99A8CABFF40DDB8231F2A9F01081F15D7FA65C1BA",
+         "B96CED90A1B8F9B43A18FC900FF55AF2BE0E94B90A434FCA5B9E226B835024CD",
+         16,
+      )
+      .unwrap();
+
+      assert_eq!(
+         curve.multipy_point(&point_G, BigInt::from(0x1234)),
+         ECCValueRes::Finite(expected)
+      );
+      assert_eq!(
+         curve.multipy_point(&point_G, curve.n()),
+         ECCValueRes::Infinity
+      );
+      assert_eq!(
+         curve.subtract_points(&point_G, &point_G),
+         ECCValueRes::Infinity
+      );
+
+      let point_2G = match curve.double_point(&point_G) {
+         ECCValueRes::Finite(point) => point,
+         ECCValueRes::Infinity => panic!("2G is not infinity"),
+      };
+      assert_eq!(
+         curve.add_points(&point_G, &point_2G),
+         ECCValueRes::Finite(multipy_base_point_in::<_, JacobianCoordinates>(
+            &curve,
+            BigInt::from(3)
+         ))
+      );
+
+      // x offset by p is still the x of G, or of -G.
+      let unreduced_G = AffineCoordinates {
+         x: &point_G.x + curve.p(),
+         y: point_G.y.clone(),
+      };
+      assert_eq!(
+         curve.add_points(&point_G, &unreduced_G),
+         ECCValueRes::Finite(point_2G)
+      );
+      assert_eq!(
+         curve.add_points(&curve.negate_point(&point_G), &unreduced_G),
+         ECCValueRes::Infinity
+      );
+   }
+
+   #[test]
+   fn affine_point_calculation_on_Secp256r1() {
+      use super::ecc::ECCValueRes;
+
+      let curve = Secp256r1::new();
+      let point_G = curve.base_point();
+      let expected = AffineCoordinates::try_new(
+         "ED5784A75391DC43ADCD42DBC4C938E80690C75B3F4309049D5076692F8DAFE9",
+         "7ED5E8B3D94DD41F677D0E25F6EA5B332495BBDB74923EABBE9E7D2C1D09A08A",
+         16,
+      )
+      .unwrap();
+
+      assert_eq!(
+         curve.multipy_point(&point_G, BigInt::from(0x1234)),
+         ECCValueRes::Finite(expected.clone())
+      );
+      assert_eq!(
+         curve.multipy_point(&point_G, -(curve.n() - BigInt::from(0x1234))),
+         ECCValueRes::Finite(expected)
+      );
+      assert_eq!(
+         curve.multipy_point(&point_G, BigInt::zero()),
+         ECCValueRes::Infinity
+      );
+   }
+
+   #[test]
+   fn points_from_malformed_strings() {
+      use super::ecc::prime::points::jacobian::NewPoint as NewJacobian;
+
+      for &base in &[0, 1, 37, u32::MAX] {
+         assert!(AffineCoordinates::try_new("1", "2", base).is_err());
+         assert!(JacobianCoordinates::try_new("1", "2", "1", base).is_err());
+      }
+      assert!(AffineCoordinates::try_new("", "2", 16).is_err());
+      assert!(AffineCoordinates::try_new(String::from("-"), String::from("2"), 16).is_err());
+
+      // Borrowed from anything, not only literals.
+      let y = String::from("4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5");
+      let point = AffineCoordinates::try_new(
+         "6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296",
+         y.as_str(),
+         16,
+      );
+      assert_eq!(point.unwrap(), create_affine_point());
+   }
+
+   #[test]
+   fn conversions_of_the_point_at_infinity() {
+      use super::ecc::{ECCValue, ECCValueRes, InfinityError};
+
+      let curve = Secp256k1::new();
+      let point_O = JacobianCoordinates::from(ECCValue::Infinity);
+
+      // No more `panic!("Zero division!")`
+      assert_eq!(
+         AffineCoordinates::convert_from(&point_O, &curve),
+         ECCValueRes::Infinity
+      );
+      assert!(ChudnovskyJacobianCoordinates::convert_from(&point_O, &curve).is_infinity());
+      assert!(curve
+         .convert_point_to::<AffineCoordinates>(&point_O)
+         .is_err());
+
+      let point_G = JacobianCoordinates::convert_from(&curve.base_point(), &curve);
+      assert!(point_G.is_finite());
+      let point_G = point_G.and_then(|point| AffineCoordinates::convert_from(&point, &curve));
+      assert_eq!(point_G.clone().unwrap_finite(), curve.base_point());
+      assert_eq!(
+         point_G.clone().map(|point| point.x).ok_or(()),
+         Ok(curve.base_point().x)
+      );
+
+      let result: Result<AffineCoordinates, InfinityError> =
+         curve.subtract_points(&curve.base_point(), &curve.base_point()).into();
+      assert!(result.is_err());
+
+      // `ECCValue` and `ECCValueRes<AffineCoordinates>` are interchangeable.
+      let value = ECCValue::from(point_G.clone());
+      assert_eq!(ECCValueRes::<AffineCoordinates>::from(value), point_G);
+      match ECCValue::from(ECCValueRes::<AffineCoordinates>::Infinity) {
+         ECCValue::Infinity => (),
+         _ => panic!("infinity has to stay infinity"),
+      }
+   }
+
+   #[test]
+   fn points_are_compared_over_the_curve_field() {
+      let curve = Secp256k1::new();
+      let point_G = curve
+         .convert_point_to::<JacobianCoordinates>(&curve.base_point())
+         .unwrap();
+      // G again, but with z != 1
+      let point_3G = curve.add_points(&curve.double_point(&point_G), &point_G);
+      let point_G_ = curve.subtract_points(&point_3G, &curve.double_point(&point_G));
+      assert!(point_G_.z != point_G.z);
+
+      assert!(curve.points_equal(&point_G, &point_G_));
+      assert!(!curve.points_equal(&point_G, &point_3G));
+      assert!(!curve.points_equal(&point_G, &curve.multipy_point(&point_G, BigInt::from(-1))));
+
+      // Different representations of the same point
+      let point_S = curve
+         .convert_point_to::<StandardProjectiveCoordinates>(&point_G_)
+         .unwrap();
+      let point_C = curve
+         .convert_point_to::<ChudnovskyJacobianCoordinates>(&point_G_)
+         .unwrap();
+      assert!(curve.points_equal(&point_G_, &point_S));
+      assert!(curve.points_equal(&point_G_, &point_C));
+      assert!(curve.points_equal(&point_G_, &curve.base_point()));
+
+      // The point at infinity is only equal to itself.
+      let point_O = curve.multipy_point(&point_G, curve.n());
+      let point_O_ = curve.subtract_points(&point_3G, &point_3G);
+      assert!(curve.points_equal(&point_O, &point_O_));
+      assert!(!curve.points_equal(&point_O, &point_G));
+      assert!(!curve.points_equal(&point_G, &point_O));
+   }
+
+   #[test]
+   fn projective_points_are_compared_as_fractions() {
+      let scaled = |point: &JacobianCoordinates, k: u32| JacobianCoordinates {
+         x: &point.x * k.pow(2),
+         y: &point.y * k.pow(3),
+         z: &point.z * k,
+      };
+
+      let curve = Secp256k1::new();
+      let point_G = curve
+         .convert_point_to::<JacobianCoordinates>(&curve.base_point())
+         .unwrap();
+      let point_2G = curve.double_point(&point_G);
+      assert!(curve.points_equal(&scaled(&point_2G, 5), &scaled(&point_2G, 7)));
+      assert!(!curve.points_equal(&point_2G, &point_G));
+
+      let point_S = curve
+         .convert_point_to::<StandardProjectiveCoordinates>(&point_G)
+         .unwrap();
+      let point_S_ = StandardProjectiveCoordinates {
+         x: &point_S.x * 3,
+         y: &point_S.y * 3,
+         z: &point_S.z * 3,
+      };
+      assert!(curve.points_equal(&point_S, &point_S_));
+   }
+
+   #[test]
+   fn canonical_points_as_keys() {
+      use super::ecc::ECCValueRes;
+      use std::collections::HashMap;
+
+      let curve = Secp256r1::new();
+      let point_G = curve
+         .convert_point_to::<JacobianCoordinates>(&curve.base_point())
+         .unwrap();
+      let point_2G = curve.double_point(&point_G);
+      let point_4G = curve.double_point(&point_2G);
+      let point_4G_ = curve.add_points(&curve.add_points(&point_2G, &point_G), &point_G);
+      let point_O = curve.subtract_points(&point_4G, &point_4G_);
+
+      let mut names = HashMap::new();
+      names.insert(curve.canonical_point(&point_4G), "4G");
+      names.insert(curve.canonical_point(&point_O), "O");
+      names.insert(curve.canonical_point(&point_4G_), "4G again");
+      assert_eq!(names.len(), 2);
+      assert_eq!(names[&curve.canonical_point(&point_4G)], "4G again");
+      assert_eq!(names[&ECCValueRes::Infinity], "O");
+
+      // Unreduced affine coordinates have the same canonical form.
+      let point_G_ = AffineCoordinates {
+         x: curve.base_point().x + curve.p(),
+         y: curve.base_point().y - curve.p(),
+      };
+      assert_eq!(
+         curve.canonical_point(&point_G_),
+         curve.canonical_point(&curve.base_point())
+      );
+
+      // Infinity sorts after every finite point.
+      let mut points = [
+         curve.canonical_point(&point_O),
+         curve.canonical_point(&point_2G),
+         curve.canonical_point(&point_G),
+      ];
+      points.sort();
+      assert_eq!(points[2], ECCValueRes::Infinity);
+      assert!(points[0] < points[1]);
+   }
+
+   #[test]
+   fn curve_point_operators() {
+      use super::ecc::prime::curve_point::CurvePoint;
+
+      type K1Point = CurvePoint<Secp256k1, JacobianCoordinates>;
+      type R1Point = CurvePoint<Secp256r1, CompleteProjectiveCoordinates>;
+
+      let point_G = K1Point::generator();
+      let point_2G = &point_G + &point_G;
+      let point_5G = &point_2G + &(&point_2G + &point_G);
+      assert_eq!(point_5G, point_G.clone() * BigInt::from(5));
+      assert_eq!(
+         point_5G.to_affine().unwrap_finite(),
+         AffineCoordinates::try_new(
+            "2F8BDE4D1A07209355B4A7250A5C5128E88B84BDDC619AB7CBA8D569B240EFE4",
+            "D8AC222636E5E3D6D4DBA9DDA6C9C426F788271BAB0D6840DCA87D3AA6AC62D6",
+            16,
+         )
+         .unwrap()
+      );
+
+      let mut point_R = point_5G.clone();
+      point_R -= point_2G.clone();
+      point_R += -point_G.clone();
+      assert_eq!(point_R, point_2G);
+      assert_eq!(-&point_G, &point_G * &-Scalar::one());
+      assert_eq!(&point_G - &point_G, K1Point::identity());
+      assert!(point_G != point_2G);
+
+      let point_G = R1Point::generator();
+      let k = Scalar::<Secp256r1>::from(BigInt::from(0x1234));
+      assert_eq!(
+         (&point_G * &k).to_affine().unwrap_finite(),
+         AffineCoordinates::try_new(
+            "ED5784A75391DC43ADCD42DBC4C938E80690C75B3F4309049D5076692F8DAFE9",
+            "7ED5E8B3D94DD41F677D0E25F6EA5B332495BBDB74923EABBE9E7D2C1D09A08A",
+            16,
+         )
+         .unwrap()
+      );
+      assert_eq!(-point_G.clone() + point_G, R1Point::identity());
+   }
+
+   /// `y^2 = x^3 + x + 6 over F97`, which has `4 * 29` points.
+   struct Toy97 {}
+
+   impl ECCurve for Toy97 {
+      fn new() -> Toy97 { Toy97 {} }
+
+      fn name(&self) -> &str { "Toy97" }
+
+      fn p(&self) -> BigInt { BigInt::from(97) }
+
+      fn a(&self) -> BigInt { BigInt::from(1) }
+
+      fn b(&self) -> BigInt { BigInt::from(6) }
+
+      fn n(&self) -> BigInt { BigInt::from(29) }
+
+      fn h(&self) -> BigInt { BigInt::from(4) }
+
+      fn base_point(&self) -> AffineCoordinates {
+         AffineCoordinates {
+            x: BigInt::from(3),
+            y: BigInt::from(6),
+         }
+      }
+   }
+
+   impl<P: super::ecc::prime::points::Point> ECCurvePoint<P> for Toy97 {}
+   impl ECCurveCalculation<JacobianCoordinates> for Toy97 {}
+   impl ECCurveCalculation<AffineCoordinates> for Toy97 {}
+   impl super::ecc::prime::ecdsa::ECDSA for Toy97 {}
+
+   #[test]
+   fn subgroup_membership_and_point_order_on_a_curve_with_cofactor() {
+      use super::ecc::prime::ecdsa::errors::ECDSAError;
+      use super::ecc::prime::ecdsa::{Signature, ECDSA};
+
+      let curve = Toy97::new();
+      let jacobian = |x: u32, y: u32| {
+         curve
+            .convert_point_to::<JacobianCoordinates>(&AffineCoordinates {
+               x: BigInt::from(x),
+               y: BigInt::from(y),
+            })
+            .unwrap()
+      };
+      let point_G = jacobian(3, 6);
+      let point_T = jacobian(12, 0); // order 2
+      let point_P = jacobian(0, 43); // order 58
+      let point_O = curve.subtract_points(&point_G, &point_G);
+
+      assert!(curve.is_in_subgroup(&point_G));
+      assert!(curve.is_in_subgroup(&point_O));
+      assert!(!curve.is_in_subgroup(&point_T));
+      assert!(!curve.is_in_subgroup(&point_P));
+
+      assert_eq!(curve.point_order(&point_O), BigInt::from(1));
+      assert_eq!(curve.point_order(&point_T), BigInt::from(2));
+      assert_eq!(curve.point_order(&point_G), BigInt::from(29));
+      assert_eq!(curve.point_order(&point_P), BigInt::from(58));
+
+      // Affine coordinates, as parsed from a public key, work the same.
+      let affine_P = AffineCoordinates {
+         x: BigInt::from(0),
+         y: BigInt::from(43),
+      };
+      assert!(!curve.is_in_subgroup(&affine_P));
+      assert_eq!(curve.point_order(&affine_P), BigInt::from(58));
+      assert!(curve.is_in_subgroup(&curve.base_point()));
+
+      let point_Q = curve.clear_cofactor(&point_P);
+      assert!(curve.points_equal(&point_Q, &jacobian(30, 19)));
+      assert!(curve.is_in_subgroup(&point_Q));
+      assert!(curve
+         .clear_cofactor(&point_T)
+         .is_point_at_infinity());
+
+      let minus_G = curve.negate_point(&point_G);
+      assert!(curve.points_equal(&minus_G, &jacobian(3, 91)));
+      assert!(curve
+         .add_points(&point_G, &minus_G)
+         .is_point_at_infinity());
+
+      // ECDSA rejects public keys outside of the subgroup.
+      let signature = Signature::new(Scalar::one(), Scalar::one());
+      assert_eq!(
+         ECDSA::verify(&curve, &affine_P, &[1], &signature),
+         Err(ECDSAError::InvalidPublicKey)
+      );
+   }
+
+   #[test]
+   fn negation_and_subgroup_on_Secp256k1() {
+      let curve = Secp256k1::new();
+      let point_G = curve
+         .convert_point_to::<StandardProjectiveCoordinates>(&curve.base_point())
+         .unwrap();
+
+      let minus_G = curve.negate_point(&point_G);
+      assert!(minus_G.y >= BigInt::zero() && minus_G.y < curve.p());
+      assert!(curve.points_equal(&minus_G, &curve.multipy_point(&point_G, BigInt::from(-1))));
+
+      assert!(curve.is_in_subgroup(&point_G));
+      assert_eq!(curve.point_order(&point_G), curve.n());
+      assert!(curve.points_equal(&curve.clear_cofactor(&point_G), &point_G));
+   }
+
+   fn hex_bytes(hex: &str) -> Vec<u8> {
+      (0..hex.len())
+         .step_by(2)
+         .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+         .collect()
+   }
+
+   #[test]
+   fn ecdsa_public_key_recovery_on_Secp256k1() {
+      use super::ecc::prime::ecdsa::errors::ECDSAError;
+      use super::ecc::prime::ecdsa::{Signature, ECDSA};
+      use super::ecc::ECCValue;
+
+      let curve = Secp256k1::new();
+      // sha256("example message")
+      let hash = hex_bytes("ad84cd0b10fc028738971b078124aec2a0e7c6d986a381be0b386f32bee887af");
+      let vectors = [
+         (
+            "021a7a569e91dbf60581509c7fc946d1003b60c7dee85299538db6353538d59574",
+            "ce53abb3721bafc561408ce8ff99c909f7f0b18a2f788649d6470162ab1aa032\
+             3971edc523a6d6453f3fb6128d318d9db1a5ff3386feb1047d9816e780039d52",
+            0,
+         ),
+         (
+            "036d6caac248af96f6afa7f904f550253a0f3ef3f5aa2fe6838a95b216691468e2",
+            "46c05b6368a44b8810d79859441d819b8e7cdc8bfd371e35c53196f4bcacdb51\
+             35c7facce2a97b95eacba8a586d87b7958aaf8368ab29cee481f76e871dbd9cb",
+            1,
+         ),
+      ];
+
+      for &(public_key, signature, recovery_id) in vectors.iter() {
+         let signature = Signature::<Secp256k1>::from_bytes(&hex_bytes(signature)).unwrap();
+         let point_Q = curve
+            .recover_public_key(&hash, &signature, recovery_id)
+            .unwrap();
+         assert_eq!(ECCValue::from(point_Q.clone()).to_compressed(), public_key);
+         assert_eq!(curve.verify(&point_Q, &hash, &signature), Ok(()));
+
+         // The other parity yields another key, which does not match.
+         let other = curve
+            .recover_public_key(&hash, &signature, recovery_id ^ 1)
+            .unwrap();
+         assert!(other != point_Q);
+         // r + n is beyond p
+         assert_eq!(
+            curve.recover_public_key(&hash, &signature, recovery_id | 2),
+            Err(ECDSAError::InvalidRecoveryId)
+         );
+      }
+   }
+
+   #[test]
+   fn ecdsa_sign_verify_and_recover_on_Secp256k1() {
+      use super::ecc::prime::ecdsa::errors::ECDSAError;
+      use super::ecc::prime::ecdsa::{Signature, ECDSA};
+      use super::ecc::prime::scalar::errors::ScalarDecodeError;
+
+      let curve = Secp256k1::new();
+      let hash = hex_bytes("ad84cd0b10fc028738971b078124aec2a0e7c6d986a381be0b386f32bee887af");
+      let d = Scalar::new(BigInt::from(0x1234_5678_u64));
+      let k = Scalar::new(BigInt::from(0x0bad_cafe_u64));
+      let point_Q = multipy_base_point_in::<_, JacobianCoordinates>(&curve, d.value().clone());
+
+      let (signature, recovery_id) = curve.sign_with_nonce(&d, &hash, &k).unwrap();
+      assert_eq!(curve.verify(&point_Q, &hash, &signature), Ok(()));
+      assert_eq!(curve.recover_public_key(&hash, &signature, recovery_id), Ok(point_Q.clone()));
+
+      // n - s is valid as well, its R has the other parity.
+      let negated = Signature::new(signature.r.clone(), -&signature.s);
+      assert_eq!(curve.verify(&point_Q, &hash, &negated), Ok(()));
+      assert_eq!(curve.recover_public_key(&hash, &negated, recovery_id ^ 1), Ok(point_Q.clone()));
+      assert_ne!(signature.is_low_s(), negated.is_low_s());
+      assert_eq!(signature.normalize_s(), negated.normalize_s());
+      assert!(signature.normalize_s().is_low_s());
+
+      let mut tampered = hash.clone();
+      tampered[0] ^= 1;
+      assert_eq!(
+         curve.verify(&point_Q, &tampered, &signature),
+         Err(ECDSAError::VerificationFailed)
+      );
+      assert_eq!(
+         curve.sign_with_nonce(&d, &hash, &Scalar::zero()),
+         Err(ECDSAError::Infinity)
+      );
+
+      let der = signature.to_der();
+      assert_eq!(Signature::<Secp256k1>::from_der(&der), Ok(signature.clone()));
+      // A long form length which fits the short form.
+      let mut long_form = vec![0x30, 0x81];
+      long_form.extend(&der[1..]);
+      assert_eq!(Signature::<Secp256k1>::from_der(&long_form), Err(ScalarDecodeError::InvalidDER));
+
+      // Off the curve, and with a coordinate not reduced modulo p.
+      let mut off_curve = point_Q.clone();
+      off_curve.y += 1;
+      let mut unreduced = point_Q.clone();
+      unreduced.x += curve.p();
+      for public_key in [off_curve, unreduced] {
+         assert_eq!(
+            curve.verify(&public_key, &hash, &signature),
+            Err(ECDSAError::InvalidPublicKey)
+         );
+      }
+   }
+
+   /// `y^2 = x^3 + 5 over F103`, which has 97 points, so some x coordinates
+   /// are larger than `n`.
+   struct Toy103 {}
+
+   impl ECCurve for Toy103 {
+      fn new() -> Toy103 { Toy103 {} }
+
+      fn name(&self) -> &str { "Toy103" }
+
+      fn p(&self) -> BigInt { BigInt::from(103) }
+
+      fn a(&self) -> BigInt { BigInt::zero() }
+
+      fn b(&self) -> BigInt { BigInt::from(5) }
+
+      fn n(&self) -> BigInt { BigInt::from(97) }
+
+      fn h(&self) -> BigInt { BigInt::from(1) }
+
+      fn base_point(&self) -> AffineCoordinates {
+         AffineCoordinates {
+            x: BigInt::from(2),
+            y: BigInt::from(42),
+         }
+      }
+   }
+
+   impl<P: super::ecc::prime::points::Point> ECCurvePoint<P> for Toy103 {}
+   impl ECCurveCalculation<JacobianCoordinates> for Toy103 {}
+   impl super::ecc::prime::ecdsa::ECDSA for Toy103 {}
+
+   #[test]
+   fn ecdsa_recovery_when_r_overflowed_n() {
+      use super::ecc::prime::ecdsa::ECDSA;
+
+      let curve = Toy103::new();
+      // 7 bits of the hash are used, e = 13
+      let hash = [26_u8];
+      let d = Scalar::new(BigInt::from(11));
+      let point_Q = AffineCoordinates {
+         x: BigInt::from(64),
+         y: BigInt::from(80),
+      };
+
+      // R = 10G = (101, 10), so r = 4 and the recovery id carries j = 1.
+      let (signature, recovery_id) = curve
+         .sign_with_nonce(&d, &hash, &Scalar::new(BigInt::from(10)))
+         .unwrap();
+      assert_eq!(signature.r.value(), &BigInt::from(4));
+      assert_eq!(signature.s.value(), &BigInt::from(93));
+      assert_eq!(recovery_id, 2);
+
+      assert_eq!(curve.verify(&point_Q, &hash, &signature), Ok(()));
+      assert_eq!(curve.recover_public_key(&hash, &signature, recovery_id), Ok(point_Q.clone()));
+      assert!(curve.recover_public_key(&hash, &signature, 0) != Ok(point_Q));
+   }
+
+   #[test]
+   fn bip340_test_vectors() {
+      use super::ecc::prime::schnorr::{self, Signature, XOnlyPublicKey};
+
+      let vectors = include_str!("../tests/vectors/bip340-test-vectors.csv");
+      for line in vectors.lines().skip(1) {
+         let fields: Vec<&str> = line.trim_end().split(',').collect();
+         let (index, secret_key, public_key, aux_rand, message, signature, valid) =
+            (fields[0], fields[1], fields[2], fields[3], fields[4], fields[5], fields[6]);
+         let message = hex_bytes(message);
+
+         if !secret_key.is_empty() {
+            let d = Scalar::<Secp256k1>::from_bytes_be(&hex_bytes(secret_key)).unwrap();
+            assert_eq!(
+               XOnlyPublicKey::from_private_key(&d).unwrap().to_bytes().to_vec(),
+               hex_bytes(public_key),
+               "public key of vector {}",
+               index
+            );
+
+            let mut aux = [0_u8; 32];
+            aux.copy_from_slice(&hex_bytes(aux_rand));
+            let created = schnorr::sign(&d, &message, &aux).unwrap();
+            assert_eq!(
+               created.to_bytes().to_vec(),
+               hex_bytes(signature),
+               "signature of vector {}",
+               index
+            );
+         }
+
+         let verified = match (
+            XOnlyPublicKey::from_bytes(&hex_bytes(public_key)),
+            Signature::from_bytes(&hex_bytes(signature)),
+         ) {
+            (Ok(public_key), Ok(signature)) => {
+               schnorr::verify(&public_key, &message, &signature).is_ok()
+            },
+            _ => false,
+         };
+         assert_eq!(verified, valid == "TRUE", "verification of vector {}", index);
+      }
+   }
+
+   #[test]
+   fn bip340_batch_verification() {
+      use super::ecc::prime::schnorr::{self, XOnlyPublicKey};
+
+      let mut rng = rand::thread_rng();
+      let messages: Vec<Vec<u8>> = (0_u8..4).map(|i| vec![i; i as usize * 10]).collect();
+      let keys: Vec<Scalar<Secp256k1>> = (0..4).map(|_| Scalar::random(&mut rng)).collect();
+      let public_keys: Vec<XOnlyPublicKey> = keys
+         .iter()
+         .map(|d| XOnlyPublicKey::from_private_key(d).unwrap())
+         .collect();
+      let signatures: Vec<schnorr::Signature> = keys
+         .iter()
+         .zip(messages.iter())
+         .map(|(d, message)| schnorr::sign(d, message, &[0x42; 32]).unwrap())
+         .collect();
+
+      let mut batch: Vec<_> = (0..4)
+         .map(|i| (&public_keys[i], &messages[i][..], &signatures[i]))
+         .collect();
+      assert_eq!(schnorr::verify_batch(&mut rng, &batch), Ok(()));
+      assert_eq!(schnorr::verify_batch(&mut rng, &[]), Ok(()));
+
+      // A signature of another message spoils the whole batch.
+      batch[2].1 = &messages[3][..];
+      assert!(schnorr::verify_batch(&mut rng, &batch).is_err());
+      assert!(schnorr::verify_batch(&mut rng, &batch[2..3]).is_err());
+   }
+
+   #[test]
+   fn ed25519_rfc8032_test_vectors() {
+      use super::ecc::prime::edwards::ed25519::{self, PublicKey, Signature};
+      use super::ecc::prime::edwards::errors::EdDSAError;
+      use super::ecc::prime::edwards::{Edwards25519, TwistedEdwardsCurve};
+
+      // RFC 8032 7.1, TEST 1, 2, 3 and SHA(abc)
+      let vectors = [
+         (
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "",
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+             5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+         ),
+         (
+            "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "72",
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+             085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+         ),
+         (
+            "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            "af82",
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac\
+             18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+         ),
+         (
+            "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42",
+            "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf",
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            "dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b589\
+             09351fc9ac90b3ecfdfbc7c66431e0303dca179c138ac17ad9bef1177331a704",
+         ),
+      ];
+
+      for &(secret_key, public_key, message, signature) in vectors.iter() {
+         let mut sk = [0_u8; 32];
+         sk.copy_from_slice(&hex_bytes(secret_key));
+         let message = hex_bytes(message);
+
+         let pk = ed25519::public_key(&sk);
+         assert_eq!(pk.to_bytes().to_vec(), hex_bytes(public_key));
+         assert_eq!(PublicKey::from_bytes(&hex_bytes(public_key)), Ok(pk.clone()));
+
+         let created = ed25519::sign(&sk, &message);
+         assert_eq!(created.to_bytes().to_vec(), hex_bytes(signature));
+         assert_eq!(Signature::from_bytes(&hex_bytes(signature)), Ok(created.clone()));
+         assert_eq!(ed25519::verify(&pk, &message, &created), Ok(()));
+
+         let mut tampered = message.clone();
+         tampered.push(0);
+         assert_eq!(
+            ed25519::verify(&pk, &tampered, &created),
+            Err(EdDSAError::VerificationFailed)
+         );
+
+         // S + n satisfies the equation as well, but is not canonical.
+         let mut malleated = created.clone();
+         malleated.s += Edwards25519::new().n();
+         assert_eq!(
+            ed25519::verify(&pk, &message, &malleated),
+            Err(EdDSAError::InvalidSignature)
+         );
+      }
+   }
+
+   #[test]
+   fn edwards25519_point_encoding() {
+      use super::ecc::prime::edwards::ed25519::Signature;
+      use super::ecc::prime::edwards::errors::EdDSAError;
+      use super::ecc::prime::edwards::{
+         Edwards25519, ExtendedCoordinates, TwistedEdwardsCurve,
+      };
+
+      let curve = Edwards25519::new();
+      let point_B = curve.to_extended(&curve.base_point());
+      assert!(curve.point_is_on_curve(&curve.base_point()));
+      // The base point is encoded as 4/5 with an even x.
+      assert_eq!(
+         curve.encode_point(&curve.base_point()),
+         hex_bytes("5866666666666666666666666666666666666666666666666666666666666666")
+      );
+
+      let point_P = curve.to_affine(&curve.multipy_point(&point_B, BigInt::from(0x1234)));
+      assert!(curve.point_is_on_curve(&point_P));
+      assert_eq!(curve.decode_point(&curve.encode_point(&point_P)), Some(point_P.clone()));
+
+      // nB is the neutral element, doubling agrees with addition.
+      assert!(curve.points_equal(
+         &curve.multipy_point(&point_B, curve.n()),
+         &ExtendedCoordinates::identity()
+      ));
+      assert!(curve.points_equal(
+         &curve.double_point(&point_B),
+         &curve.add_points(&point_B, &point_B)
+      ));
+
+      // y = p is not canonical, and neither is x = 0 with its sign bit set.
+      let mut y_is_p =
+         hex_bytes("edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f");
+      assert_eq!(curve.decode_point(&y_is_p), None);
+      y_is_p[0] = 0x01;
+      y_is_p[31] = 0x80;
+      for byte in y_is_p[1..31].iter_mut() {
+         *byte = 0;
+      }
+      assert_eq!(curve.decode_point(&y_is_p), None);
+
+      // S = n
+      let mut signature = vec![0_u8; 32];
+      let n = hex_bytes("edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010");
+      signature.extend(n);
+      assert_eq!(Signature::from_bytes(&signature), Err(EdDSAError::InvalidSignature));
+   }
+
+   #[test]
+   fn x25519_and_x448_rfc7748_test_vectors() {
+      use super::ecc::prime::montgomery;
+
+      // RFC 7748 5.2
+      let vectors = [
+         (
+            "a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4",
+            "e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c",
+            "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552",
+         ),
+         (
+            "4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d",
+            "e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493",
+            "95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957",
+         ),
+         (
+            "3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121700a779c\
+             984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3",
+            "06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9814dc031\
+             ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086",
+            "ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239fe14fbaad\
+             eb445fc66a01b0779d98223961111e21766282f73dd96b6f",
+         ),
+         (
+            "203d494428b8399352665ddca42f9de8fef600908e0d461cb021f8c538345dd7\
+             7c3e4806e25f46d3315c44e0a5b4371282dd2c8d5be3095f",
+            "0fbcc2f993cd56d3305b0b7d9e55d4c1a8fb5dbb52f8e9a1e9b6201b165d0158\
+             94e56c4d3570bee52fe205e28a78b91cdfbde71ce8d157db",
+            "884a02576239ff7a2f2f63b2db6a9ff37047ac13568e1e30fe63c4a7ad1b3ee3\
+             a5700df34321d62077e63633c575c1c954514e99da7c179d",
+         ),
+      ];
+
+      for &(k, u, expected) in vectors.iter() {
+         let (k, u) = (hex_bytes(k), hex_bytes(u));
+         let result = if k.len() == 32 {
+            let (mut k32, mut u32) = ([0_u8; 32], [0_u8; 32]);
+            k32.copy_from_slice(&k);
+            u32.copy_from_slice(&u);
+            montgomery::x25519(&k32, &u32).to_vec()
+         } else {
+            let (mut k56, mut u56) = ([0_u8; 56], [0_u8; 56]);
+            k56.copy_from_slice(&k);
+            u56.copy_from_slice(&u);
+            montgomery::x448(&k56, &u56).to_vec()
+         };
+         assert_eq!(result, hex_bytes(expected));
+      }
+   }
+
+   #[test]
+   fn x25519_rfc7748_iterated_and_diffie_hellman() {
+      use super::ecc::prime::montgomery;
+
+      // RFC 7748 5.2, k = u = 9, then u = k and k = X25519(k, u)
+      let mut k = [0_u8; 32];
+      k[0] = 9;
+      let mut u = k;
+      for i in 1..1001 {
+         let result = montgomery::x25519(&k, &u);
+         u = k;
+         k = result;
+         if i == 1 {
+            assert_eq!(
+               k.to_vec(),
+               hex_bytes("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079")
             );
          }
-         _ => (),
       }
+      assert_eq!(
+         k.to_vec(),
+         hex_bytes("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51")
+      );
+
+      // RFC 7748 6.1
+      let mut base_u = [0_u8; 32];
+      base_u[0] = 9;
+      let (mut alice, mut bob) = ([0_u8; 32], [0_u8; 32]);
+      alice.copy_from_slice(&hex_bytes(
+         "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+      ));
+      bob.copy_from_slice(&hex_bytes(
+         "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
+      ));
+      let alice_public = montgomery::x25519(&alice, &base_u);
+      let bob_public = montgomery::x25519(&bob, &base_u);
+      assert_eq!(
+         alice_public.to_vec(),
+         hex_bytes("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+      );
+      assert_eq!(
+         bob_public.to_vec(),
+         hex_bytes("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+      );
+      let shared = montgomery::x25519(&alice, &bob_public);
+      assert_eq!(shared, montgomery::x25519(&bob, &alice_public));
+      assert_eq!(
+         shared.to_vec(),
+         hex_bytes("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742")
+      );
+   }
+
+   #[test]
+   fn x448_rfc7748_iterated() {
+      use super::ecc::prime::montgomery;
+
+      // RFC 7748 5.2, k = u = 5, then u = k and k = X448(k, u)
+      let mut k = [0_u8; 56];
+      k[0] = 5;
+      let mut u = k;
+      for i in 1..1001 {
+         let result = montgomery::x448(&k, &u);
+         u = k;
+         k = result;
+         if i == 1 {
+            assert_eq!(
+               k.to_vec(),
+               hex_bytes(
+                  "3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a4d23a8cd\
+                   0db897086239492caf350b51f833868b9bc2b3bca9cf4113"
+               )
+            );
+         }
+      }
+      assert_eq!(
+         k.to_vec(),
+         hex_bytes(
+            "aa3b4749d55b9daf1e5b00288826c467274ce3ebbdd5c17b975e09d4af6c67cf\
+             10d087202db88286e2b79fceea3ec353ef54faa26e219f38"
+         )
+      );
+   }
+
+   #[test]
+   fn field_square_roots() {
+      use num::{Integer, Num};
+      use super::ecc::prime::field;
+
+      // secp256k1's p = 3 mod 4, 2^255 - 19 = 5 mod 8 and P-224's p = 1 mod 2^96.
+      let primes = [
+         "FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFE_FFFFFC2F",
+         "7FFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFED",
+         "FFFFFFFF_FFFFFFFF_FFFFFFFF_FFFFFFFF_00000000_00000000_00000001",
+      ];
+      for p in primes.iter() {
+         let p = BigInt::from_str_radix(&p.replace("_", ""), 16).unwrap();
+         let half = (&p - BigInt::from(1)) / BigInt::from(2);
+         for v in 1..40 {
+            let v = BigInt::from(v);
+            match field::sqrt(&v, &p) {
+               Some(root) => {
+                  assert!(field::is_square(&v, &p));
+                  assert!(root <= half);
+                  assert_eq!((&root * &root).mod_floor(&p), v);
+               },
+               None => assert!(!field::is_square(&v, &p)),
+            }
+         }
+         assert_eq!(field::sqrt(&BigInt::zero(), &p), Some(BigInt::zero()));
+         let inverse = field::inverse(&BigInt::from(7), &p);
+         assert_eq!((inverse * BigInt::from(7)).mod_floor(&p), BigInt::from(1));
+      }
+
+      // Decompression works with p = 97 = 1 mod 4 as well.
+      let curve = Toy97::new();
+      assert_eq!(curve.decompress_point(&BigInt::from(3), false), Some(curve.base_point()));
+      assert_eq!(
+         curve.decompress_point(&BigInt::from(3), true),
+         Some(AffineCoordinates {
+            x: BigInt::from(3),
+            y: BigInt::from(91),
+         })
+      );
+   }
+
+   #[test]
+   fn birational_maps_between_curve25519_models() {
+      use num::{Integer, Num};
+      use super::ecc::prime::birational::{
+         EdwardsForm, MontgomeryEdwardsMap, MontgomeryForm, WeierstrassMontgomeryMap,
+      };
+      use super::ecc::prime::edwards::{Edwards25519, TwistedEdwardsCurve};
+      use super::ecc::prime::field;
+      use super::ecc::prime::montgomery::{Curve25519, MontgomeryCurve};
+      use super::ecc::ECCValueRes;
+
+      let hex = |value: &str| BigInt::from_str_radix(value, 16).unwrap();
+
+      let curve = Curve25519::new();
+      let edwards_curve = Edwards25519::new();
+      let p = curve.p();
+      let montgomery = MontgomeryForm::from_curve(&curve);
+      let edwards = EdwardsForm::from_curve(&edwards_curve);
+
+      // The base point (9, v) of RFC 7748 4.1, v being the smaller root.
+      let u = curve.base_u();
+      let v = field::sqrt(&(&u * &u * &u + &montgomery.a * &u * &u + &u), &p).unwrap();
+      assert_eq!(v, hex("20ae19a1b8a086b4e01edd2c7748d14c923d4d7e6d7c61b229e9c5a27eced3d9"));
+      let point_g = ECCValueRes::Finite(AffineCoordinates { x: u, y: v });
+      assert!(montgomery.is_on_curve(&point_g));
+
+      // Wei25519, draft-ietf-lwig-curve-representations
+      let to_weierstrass = WeierstrassMontgomeryMap::from_montgomery(&montgomery);
+      let weierstrass = to_weierstrass.weierstrass().clone();
+      assert_eq!(
+         weierstrass.a,
+         hex("2aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa984914a144")
+      );
+      assert_eq!(
+         weierstrass.b,
+         hex("7b425ed097b425ed097b425ed097b425ed097b425ed097b4260b5e9c7710c864")
+      );
+      let weierstrass_g = to_weierstrass.to_weierstrass(&point_g);
+      assert!(weierstrass.is_on_curve(&weierstrass_g));
+      assert_eq!(
+         weierstrass_g.clone().unwrap_finite().x,
+         hex("2aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaad245a")
+      );
+      assert_eq!(to_weierstrass.to_montgomery(&weierstrass_g), point_g);
+      assert_eq!(to_weierstrass.to_weierstrass(&ECCValueRes::Infinity), ECCValueRes::Infinity);
+
+      // (0, 0) maps to (A/3, 0), which gives Curve25519 back.
+      let alpha = (&montgomery.a * field::inverse(&BigInt::from(3), &p)).mod_floor(&p);
+      let from_weierstrass =
+         WeierstrassMontgomeryMap::from_weierstrass(&weierstrass, &alpha).unwrap();
+      assert_eq!(from_weierstrass.montgomery(), &montgomery);
+      assert_eq!(WeierstrassMontgomeryMap::from_weierstrass(&weierstrass, &BigInt::from(1)), None);
+
+      // edwards25519 is a = -1, which needs the scaling c = sqrt(-486664).
+      assert_ne!(MontgomeryEdwardsMap::from_montgomery(&montgomery).edwards(), &edwards);
+      assert_eq!(MontgomeryEdwardsMap::from_edwards(&edwards).montgomery().a, montgomery.a);
+      let map = MontgomeryEdwardsMap::between(&montgomery, &edwards).unwrap();
+
+      // RFC 7748 4.1 uses the other root, which maps onto the base point.
+      let point_b = edwards_curve.base_point();
+      let mapped_g = map.to_edwards(&point_g).unwrap();
+      assert_eq!(mapped_g.y, point_b.y);
+      assert_eq!(mapped_g.x, &p - &point_b.x);
+      assert_eq!(map.negated().to_edwards(&point_g), Some(point_b.clone()));
+      assert_eq!(map.to_montgomery(&mapped_g), point_g);
+
+      // The maps are homomorphisms, compare [k]B against the ladder.
+      let k = BigInt::from(0x1234567);
+      let point_kb = edwards_curve.to_extended(&point_b);
+      let point_kb = edwards_curve.to_affine(&edwards_curve.multipy_point(&point_kb, k.clone()));
+      let montgomery_kb = map.negated().to_montgomery(&point_kb);
+      assert!(montgomery.is_on_curve(&montgomery_kb));
+      assert_eq!(montgomery_kb.clone().unwrap_finite().x, curve.ladder(&k, &curve.base_u()));
+      assert_eq!(map.negated().to_edwards(&montgomery_kb), Some(point_kb));
+      assert!(weierstrass.is_on_curve(&to_weierstrass.to_weierstrass(&montgomery_kb)));
+
+      // The neutral elements and the point of order 2
+      let identity = AffineCoordinates {
+         x: BigInt::zero(),
+         y: BigInt::from(1),
+      };
+      let order_two = AffineCoordinates {
+         x: BigInt::zero(),
+         y: BigInt::zero(),
+      };
+      assert_eq!(map.to_edwards(&ECCValueRes::Infinity), Some(identity.clone()));
+      assert_eq!(map.to_montgomery(&identity), ECCValueRes::Infinity);
+      let minus_one = map.to_edwards(&ECCValueRes::Finite(order_two.clone())).unwrap();
+      assert!(edwards.is_on_curve(&minus_one));
+      assert_eq!(map.to_montgomery(&minus_one), ECCValueRes::Finite(order_two));
+   }
+
+   #[test]
+   fn ecies_primitives_known_answers() {
+      use super::ecc::prime::ecies::{Cipher, AES128CTR, HMACSHA256, KDF, MAC, X963KDF};
+
+      // NIST CAVS ANSI X9.63 KDF, SHA-256, COUNT = 0, without and with SharedInfo.
+      let z = hex_bytes("96c05619d56c328ab95fe84b18264b08725b85e33fd34f08");
+      assert_eq!(X963KDF.derive(&z, &[], 16), hex_bytes("443024c3dae66b95e6f5670601558f71"));
+      let z = hex_bytes("22518b10e70f2a3f243810ae3254139efbee04aa57c7af7d");
+      let shared_info = hex_bytes("75eef81aa3041e33b80971203d2c0c52");
+      assert_eq!(
+         X963KDF.derive(&z, &shared_info, 128),
+         hex_bytes(
+            "c498af77161cc59f2962b9a713e2b215152d139766ce34a776df11866a69bf2e\
+             52a13d9c7c6fc878c50c5ea0bc7b00e0da2447cfd874f6cf92f30d0097111485\
+             500c90c3af8b487872d04685d14c8d1dc8d7fa08beb0ce0ababc11f0bd496269\
+             142d43525a78e5bc79a17f59676a5706dc54d54d4d1f0bd7e386128ec26afc21"
+         )
+      );
+
+      // RFC 4231 test case 2.
+      assert_eq!(
+         HMACSHA256.tag(b"Jefe", b"what do ya want for nothing?"),
+         hex_bytes("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+      );
+
+      // The keystream of the all-zero key is AES-128 of the counter blocks 0 and 1.
+      let ciphertext = AES128CTR.encrypt(&[0_u8; 16], &[0_u8; 32]);
+      assert_eq!(
+         ciphertext,
+         hex_bytes("66e94bd4ef8a2c3b884cfa59ca342b2e58e2fccefa7e3061367f1d57a4e7455a")
+      );
+      assert_eq!(AES128CTR.decrypt(&[0_u8; 16], &ciphertext), [0_u8; 32]);
+   }
+
+   #[test]
+   fn ecies_deterministic_test_vectors() {
+      use super::ecc::prime::ecies::{ECIES, AES128CTR, HMACSHA256, X963KDF, XORCipher};
+
+      let d = BigInt::from(0x1234567890abcdef_u64);
+      let k = BigInt::from(0xfedcba0987654321_u64);
+      let message = b"ECIES test message";
+
+      let curve = Secp256k1::new();
+      let public_key = multipy_base_point_in::<_, JacobianCoordinates>(&curve, d.clone());
+      assert_eq!(
+         public_key,
+         AffineCoordinates::try_new(
+            "F973A0B87062C389D125D8199E803B832B6AC6BF7867A4F6CD87506060FC4C58",
+            "4B4A0A3F26C988C54C236B224C48BB605B265949E65C098ECD87A581CA10E25D",
+            16,
+         )
+         .unwrap()
+      );
+      let ecies = ECIES::new(X963KDF, AES128CTR, HMACSHA256);
+      let ciphertext = ecies
+         .encrypt_with_ephemeral_key(&curve, &Scalar::new(k.clone()), &public_key, message)
+         .unwrap();
+      assert_eq!(
+         ciphertext,
+         hex_bytes(
+            "040fd109be4a9e07d64737926c7e2d8d7afa63ec5991d126b8abe7919d3c62a7be\
+             6eac7732c97ce0145acd0545eb54a481b9b5a54598c03a1b97b3328b75abef4c\
+             70a46960103404f5bf4192056936d21ac64b\
+             0adff623a93082dae23bfc155f89e1dbbc2f7a953eb9e4822725cbac34c0d9b6"
+         )
+      );
+      let decrypted = ecies.decrypt(&curve, &Scalar::new(d.clone()), &ciphertext);
+      assert_eq!(decrypted.unwrap(), message.to_vec());
+
+      // The XOR encryption scheme, with shared information
+      let ecies = ECIES::new(X963KDF, XORCipher, HMACSHA256).with_shared_info(b"info1", b"info2");
+      let ciphertext = ecies
+         .encrypt_with_ephemeral_key(&curve, &Scalar::new(k.clone()), &public_key, message)
+         .unwrap();
+      assert_eq!(
+         ciphertext,
+         hex_bytes(
+            "040fd109be4a9e07d64737926c7e2d8d7afa63ec5991d126b8abe7919d3c62a7be\
+             6eac7732c97ce0145acd0545eb54a481b9b5a54598c03a1b97b3328b75abef4c\
+             cae00eacc5d0ca6f420f2a18e4411b4915a9\
+             1909e3e9f36b5af5994c7d708ed5c07541b3813a40b8adcd2f6290467738e539"
+         )
+      );
+      let decrypted = ecies.decrypt(&curve, &Scalar::new(d.clone()), &ciphertext);
+      assert_eq!(decrypted.unwrap(), message.to_vec());
+
+      let curve = Secp256r1::new();
+      let public_key = multipy_base_point_in::<_, JacobianCoordinates>(&curve, d.clone());
+      let ecies = ECIES::new(X963KDF, AES128CTR, HMACSHA256);
+      let ciphertext = ecies
+         .encrypt_with_ephemeral_key(&curve, &Scalar::new(k), &public_key, message)
+         .unwrap();
+      assert_eq!(
+         ciphertext,
+         hex_bytes(
+            "0452c6ff5b71de65d83c75d393e249ec03894370515d0b229b1c6224681f2a5030\
+             b6f323c20c20d021d611e087cf482f5297e6135cbee6d3994832164e479e6e96\
+             d336f449c1ff1c2502cd633c7d3a8fd5747a\
+             848b99d0d7058aafdae164b5f546ce6f50eece690a601a6d7319e9a133ad81c8"
+         )
+      );
+      assert_eq!(ecies.decrypt(&curve, &Scalar::new(d), &ciphertext).unwrap(), message.to_vec());
+   }
+
+   #[test]
+   fn ecies_rejects_tampering_and_invalid_keys() {
+      use super::ecc::prime::ecies::errors::ECIESError;
+      use super::ecc::prime::ecies::{ECIES, AES128CTR, HMACSHA256, X963KDF};
+
+      let mut rng = rand::thread_rng();
+      let curve = Secp256r1::new();
+      let d = Scalar::<Secp256r1>::new(BigInt::from(0x1234));
+      let public_key = multipy_base_point_in::<_, JacobianCoordinates>(&curve, d.value().clone());
+      let ecies = ECIES::new(X963KDF, AES128CTR, HMACSHA256);
+
+      let ciphertext = ecies.encrypt(&curve, &mut rng, &public_key, b"").unwrap();
+      assert_eq!(ciphertext.len(), 65 + 32);
+      assert_eq!(ecies.decrypt(&curve, &d, &ciphertext), Ok(Vec::new()));
+
+      let ciphertext = ecies.encrypt(&curve, &mut rng, &public_key, b"attack at dawn").unwrap();
+      assert_eq!(ecies.decrypt(&curve, &d, &ciphertext), Ok(b"attack at dawn".to_vec()));
+      // A wrong key, a flipped bit in R, in the message and in the tag
+      assert_eq!(
+         ecies.decrypt(&curve, &Scalar::new(BigInt::from(0x1235)), &ciphertext),
+         Err(ECIESError::TagMismatch)
+      );
+      for (i, expected) in [
+         (1, ECIESError::InvalidCiphertext),
+         (65, ECIESError::TagMismatch),
+         (ciphertext.len() - 1, ECIESError::TagMismatch),
+      ]
+      .iter()
+      {
+         let mut tampered = ciphertext.clone();
+         tampered[*i] ^= 1;
+         assert_eq!(ecies.decrypt(&curve, &d, &tampered), Err(expected.clone()));
+      }
+      assert_eq!(ecies.decrypt(&curve, &d, &ciphertext[..96]), Err(ECIESError::InvalidCiphertext));
+
+      let not_on_curve = AffineCoordinates {
+         x: public_key.x.clone(),
+         y: &public_key.y + BigInt::from(1),
+      };
+      assert_eq!(
+         ecies.encrypt(&curve, &mut rng, &not_on_curve, b""),
+         Err(ECIESError::InvalidPublicKey)
+      );
+
+      // (0, 43) is on Toy97 but of order 4 * 29.
+      let curve = Toy97::new();
+      let outside_subgroup = AffineCoordinates {
+         x: BigInt::zero(),
+         y: BigInt::from(43),
+      };
+      assert!(curve.point_is_on_curve(&outside_subgroup));
+      assert_eq!(
+         ecies.encrypt(&curve, &mut rng, &outside_subgroup, b""),
+         Err(ECIESError::InvalidPublicKey)
+      );
+   }
+
+   /// The messages of the RFC 9380 test vectors.
+   fn hash_to_curve_messages() -> Vec<Vec<u8>> {
+      vec![
+         b"".to_vec(),
+         b"abc".to_vec(),
+         b"abcdef0123456789".to_vec(),
+         [&b"q128_"[..], &[b'q'; 128][..]].concat(),
+         [&b"a512_"[..], &[b'a'; 512][..]].concat(),
+      ]
+   }
+
+   #[test]
+   fn expand_message_xmd_rfc9380_test_vectors() {
+      use super::ecc::prime::hash_to_curve::errors::HashToCurveError;
+      use super::ecc::prime::hash_to_curve::expand_message_xmd;
+
+      // RFC 9380 K.1
+      let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+      let messages = hash_to_curve_messages();
+      let expected = [
+         "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+         "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+         "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+         "b23a1d2b4d97b2ef7785562a7e8bac7eed54ed6e97e29aa51bfe3f12ddad1ff9",
+         "4623227bcc01293b8c130bf771da8c298dede7383243dc0993d2d94823958c4c",
+      ];
+      for (msg, expected) in messages.iter().zip(expected.iter()) {
+         assert_eq!(expand_message_xmd(msg, dst, 0x20).unwrap(), hex_bytes(expected));
+      }
+      assert_eq!(
+         expand_message_xmd(b"abc", dst, 0x80).unwrap(),
+         hex_bytes(
+            "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a\
+             647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635\
+             bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00\
+             058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40"
+         )
+      );
+
+      // A DST longer than 255 bytes is hashed.
+      let long_dst = b"QUUX-V01-CS02-with-expander-SHA256-128-long-DST-".to_vec();
+      let long_dst = [&long_dst[..], &[b'1'; 208][..]].concat();
+      assert_eq!(
+         expand_message_xmd(b"", &long_dst, 0x20).unwrap(),
+         hex_bytes("e8dc0c8b686b7ef2074086fbdd2f30e3f8bfbd3bdf177f73f04b97ce618a3ed3")
+      );
+      assert_eq!(expand_message_xmd(b"", dst, 255 * 32 + 1), Err(HashToCurveError::InvalidLength));
+   }
+
+   #[test]
+   fn hash_to_curve_rfc9380_test_vectors() {
+      use num::Num;
+      use super::ecc::prime::hash_to_curve::HashToCurve;
+
+      fn check<C>(curve: &C, dst: &[u8], expected: &[[&str; 4]])
+      where
+         C: HashToCurve + ECCurvePoint<AffineCoordinates>,
+      {
+         let hex = |value: &str| BigInt::from_str_radix(value, 16).unwrap();
+         for (msg, expected) in hash_to_curve_messages().iter().zip(expected.iter()) {
+            let u = curve.hash_to_field(msg, dst, 2).unwrap();
+            assert_eq!(u, vec![hex(expected[2]), hex(expected[3])]);
+
+            let point = curve.hash_to_curve(msg, dst).unwrap();
+            assert!(curve.point_is_on_curve(&point));
+            assert_eq!(point, AffineCoordinates {
+               x: hex(expected[0]),
+               y: hex(expected[1]),
+            });
+         }
+      }
+
+      // RFC 9380 J.1.1, P.x, P.y, u[0] and u[1]
+      let curve = Secp256r1::new();
+      let dst = format!("QUUX-V01-CS02-with-{}", curve.suite_id());
+      check(&curve, dst.as_bytes(), &[
+         [
+            "2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4",
+            "8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415",
+            "ad5342c66a6dd0ff080df1da0ea1c04b96e0330dd89406465eeba11582515009",
+            "8c0f1d43204bd6f6ea70ae8013070a1518b43873bcd850aafa0a9e220e2eea5a",
+         ],
+         [
+            "0bb8b87485551aa43ed54f009230450b492fead5f1cc91658775dac4a3388a0f",
+            "5c41b3d0731a27a7b14bc0bf0ccded2d8751f83493404c84a88e71ffd424212e",
+            "afe47f2ea2b10465cc26ac403194dfb68b7f5ee865cda61e9f3e07a537220af1",
+            "379a27833b0bfe6f7bdca08e1e83c760bf9a338ab335542704edcd69ce9e46e0",
+         ],
+         [
+            "65038ac8f2b1def042a5df0b33b1f4eca6bff7cb0f9c6c1526811864e544ed80",
+            "cad44d40a656e7aff4002a8de287abc8ae0482b5ae825822bb870d6df9b56ca3",
+            "0fad9d125a9477d55cf9357105b0eb3a5c4259809bf87180aa01d651f53d312c",
+            "b68597377392cd3419d8fcc7d7660948c8403b19ea78bbca4b133c9d2196c0fb",
+         ],
+         [
+            "4be61ee205094282ba8a2042bcb48d88dfbb609301c49aa8b078533dc65a0b5d",
+            "98f8df449a072c4721d241a3b1236d3caccba603f916ca680f4539d2bfb3c29e",
+            "3bbc30446f39a7befad080f4d5f32ed116b9534626993d2cc5033f6f8d805919",
+            "76bb02db019ca9d3c1e02f0c17f8baf617bbdae5c393a81d9ce11e3be1bf1d33",
+         ],
+         [
+            "457ae2981f70ca85d8e24c308b14db22f3e3862c5ea0f652ca38b5e49cd64bc5",
+            "ecb9f0eadc9aeed232dabc53235368c1394c78de05dd96893eefa62b0f4757dc",
+            "4ebc95a6e839b1ae3c63b847798e85cb3c12d3817ec6ebc10af6ee51adb29fec",
+            "4e21af88e22ea80156aff790750121035b3eefaa96b425a8716e0d20b4e269ee",
+         ],
+      ]);
+
+      // RFC 9380 J.8.1
+      let curve = Secp256k1::new();
+      let dst = format!("QUUX-V01-CS02-with-{}", curve.suite_id());
+      check(&curve, dst.as_bytes(), &[
+         [
+            "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+            "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+            "6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3",
+            "1ae6c212e08fe1a5937f6202f929a2cc8ef4ee5b9782db68b0d5799fd8f09e16",
+         ],
+         [
+            "3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+            "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+            "128aab5d3679a1f7601e3bdf94ced1f43e491f544767e18a4873f397b08a2b61",
+            "5897b65da3b595a813d0fdcc75c895dc531be76a03518b044daaa0f2e4689e00",
+         ],
+         [
+            "bac54083f293f1fe08e4a70137260aa90783a5cb84d3f35848b324d0674b0e3a",
+            "4436476085d4c3c4508b60fcf4389c40176adce756b398bdee27bca19758d828",
+            "ea67a7c02f2cd5d8b87715c169d055a22520f74daeb080e6180958380e2f98b9",
+            "7434d0d1a500d38380d1f9615c021857ac8d546925f5f2355319d823a478da18",
+         ],
+         [
+            "e2167bc785333a37aa562f021f1e881defb853839babf52a7f72b102e41890e9",
+            "f2401dd95cc35867ffed4f367cd564763719fbc6a53e969fb8496a1e6685d873",
+            "eda89a5024fac0a8207a87e8cc4e85aa3bce10745d501a30deb87341b05bcdf5",
+            "dfe78cd116818fc2c16f3837fedbe2639fab012c407eac9dfe9245bf650ac51d",
+         ],
+         [
+            "e3c8d35aaaf0b9b647e88a0a0a7ee5d5bed5ad38238152e4e6fd8c1f8cb7c998",
+            "8446eeb6181bf12f56a9d24e262221cc2f0c4725c7e3803024b5888ee5823aa6",
+            "8d862e7e7e23d7843fe16d811d46d7e6480127a6b78838c277bca17df6900e9f",
+            "68071d2530f040f081ba818d3c7188a94c900586761e9115efa47ae9bd847938",
+         ],
+      ]);
+   }
+
+   #[test]
+   #[cfg(feature = "std")]
+   fn trace_of_a_multiplication() {
+      use super::ecc::prime::trace::{self, Operation, Step};
+
+      let curve = Secp256k1::new();
+      let point_g = JacobianCoordinates::convert_from(&curve.base_point(), &curve).unwrap_finite();
+      let k = BigInt::from(0x1234);
+
+      let (point, recorded) = trace::record(|| curve.multipy_point(&point_g, k.clone()));
+      assert!(!trace::is_recording());
+
+      // NAF(0x1234) = 1 0 0 1 0 0 1 0 -1 0 1 0 0, 13 digits with 5 nonzero.
+      let digits = vec![0, 0, 1, 0, -1, 0, 1, 0, 0, 1, 0, 0, 1];
+      assert_eq!(recorded.steps[0], Step::NAF { k: k.clone(), digits: digits.clone() });
+      // Doubling the point at infinity and adding to it are not calculated.
+      let count = |expected: Operation| {
+         recorded
+            .steps
+            .iter()
+            .filter(|step| match **step {
+               Step::Operation { operation, .. } => operation == expected,
+               _ => false,
+            })
+            .count()
+      };
+      assert_eq!(count(Operation::Doubling), digits.len() - 1);
+      assert_eq!(count(Operation::MixedAddition), 4);
+      assert_eq!(count(Operation::Addition), 0);
+      match *recorded.steps.last().unwrap() {
+         Step::Operation { ref result, .. } => {
+            assert_eq!(result, &vec![("x", point.x), ("y", point.y), ("z", point.z)]);
+         },
+         _ => panic!("The last step is not a point operation"),
+      }
+
+      assert!(recorded.to_json().starts_with(
+         "{\"steps\":[{\"step\":\"naf\",\"k\":\"1234\",\"digits\":[0,0,1,0,-1,0,1,0,0,1,0,0,1]},\
+          {\"step\":\"doubling\",\"coordinates\":\"JacobianCoordinates\",\"values\":{\"A\":"
+      ));
+      let markdown = recorded.to_markdown();
+      assert_eq!(markdown.lines().count(), recorded.steps.len() + 2);
+      assert!(markdown.contains("| 5 | Point Mixed Addition | JacobianCoordinates | A = "));
+      assert!(format!("{}", recorded).starts_with("1. NAF(k), k = 1234: [0, 0, 1, 0, -1,"));
+
+      // An inner recording keeps its steps, nothing is recorded outside.
+      let ((_, inner), outer) = trace::record(|| {
+         curve.double_point(&point_g);
+         trace::record(|| curve.double_point(&point_g))
+      });
+      assert_eq!((inner.steps.len(), outer.steps.len()), (1, 1));
+      curve.double_point(&point_g);
+      assert!(trace::record(|| ()).1.steps.is_empty());
+   }
+
+   #[test]
+   #[cfg(feature = "std")]
+   fn trace_of_the_complete_formulas() {
+      use super::ecc::prime::trace::{self, Operation, Step};
+
+      let names = |step: &Step| match *step {
+         Step::Operation { operation, ref values, .. } => {
+            (operation, values.iter().map(|&(name, _)| name).collect::<Vec<_>>())
+         },
+         _ => panic!("Not a point operation"),
+      };
+      // General `a` on Secp256k1, `a = -3` on Secp256r1.
+      let k1 = Secp256k1::new();
+      let point_g =
+         CompleteProjectiveCoordinates::convert_from(&k1.base_point(), &k1).unwrap_finite();
+      let (_, recorded) = trace::record(|| k1.add_points(&k1.double_point(&point_g), &point_g));
+      assert_eq!(names(&recorded.steps[0]), (Operation::Doubling, vec!["t0", "t1", "t2", "t3"]));
+      assert_eq!(
+         names(&recorded.steps[1]),
+         (Operation::Addition, vec!["t0", "t1", "t2", "t3", "t4", "t5"])
+      );
+
+      let r1 = Secp256r1::new();
+      let point_g =
+         CompleteProjectiveCoordinates::convert_from(&r1.base_point(), &r1).unwrap_finite();
+      let (_, recorded) = trace::record(|| r1.add_points(&r1.double_point(&point_g), &point_g));
+      assert_eq!(names(&recorded.steps[0]), (Operation::Doubling, vec!["t0", "t1", "t2", "t3"]));
+      assert_eq!(
+         names(&recorded.steps[1]),
+         (Operation::Addition, vec!["t0", "t1", "t2", "t3", "t4", "X3", "Y3", "Z3"])
+      );
    }
 }
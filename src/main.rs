@@ -0,0 +1,15 @@
+use std::{env, process};
+
+mod cli;
+
+fn main() {
+   let args: Vec<String> = env::args().skip(1).collect();
+   match cli::run(&args) {
+      Ok(ref output) if output.is_empty() => {},
+      Ok(output) => println!("{}", output),
+      Err(err) => {
+         eprintln!("ecc_calc: {}", err);
+         process::exit(2);
+      },
+   }
+}
@@ -0,0 +1,242 @@
+//! Runs the NIST CAVP response files in `tests/cavp` against the point
+//! multiplication and public key validation of the crate.
+//!
+//! Every `*.rsp` file in the directory is run, record by record:
+//!
+//! * key pairs `d`, `Qx`, `Qy` as in `KeyPair.rsp` (or `SigGen.txt`): `dG = Q`
+//!   in every coordinate system, and `Q` has to be a valid public key.
+//! * public keys `Qx`, `Qy` with `Result = P` or `F (reason)` as in
+//!   `PKV.rsp`: `Q` has to parse, be on the curve and in the subgroup exactly
+//!   if it passes.
+//!
+//! Only P-256 is run, CAVP has no secp256k1 vectors. Sections of the NIST
+//! curves in `SKIPPED` are skipped, any other section fails the test.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use num::{BigInt, Num};
+
+use ecc_calc::ecc::prime::curves::{ECCurve, ECCurveCalculation, Secp256r1};
+use ecc_calc::ecc::prime::points::{
+   AffineCoordinates,
+   ChudnovskyJacobianCoordinates,
+   CompleteProjectiveCoordinates,
+   JacobianCoordinates,
+   ModifiedJacobianCoordinates,
+   PointCalculation,
+   PointFrom,
+   StandardProjectiveCoordinates,
+};
+use ecc_calc::ecc::prime::ECCurvePoint;
+use ecc_calc::ecc::ECCValue;
+
+/// The sections of curves the crate does not implement.
+const SKIPPED: &[&str] = &[
+   "P-192", "P-224", "P-384", "P-521", "K-163", "K-233", "K-283", "K-409", "K-571", "B-163",
+   "B-233", "B-283", "B-409", "B-571",
+];
+
+/// A record of a response file, the fields in order with the section it is
+/// in, e.g. `P-256` for `[P-256,SHA-256]`.
+struct Record {
+   curve: String,
+   line: usize,
+   fields: Vec<(String, String)>,
+}
+
+impl Record {
+   fn get(&self, name: &str) -> Option<&str> {
+      self.fields.iter().find(|field| field.0 == name).map(|field| field.1.as_str())
+   }
+
+   fn hex(&self, name: &str) -> Option<BigInt> {
+      self.get(name).map(|hex| BigInt::from_str_radix(hex, 16).unwrap())
+   }
+}
+
+#[test]
+fn cavp_test_vectors() {
+   let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("cavp");
+   let mut files: Vec<_> = fs::read_dir(&dir)
+      .unwrap()
+      .map(|entry| entry.unwrap().path())
+      .filter(|path| path.extension().is_some_and(|extension| extension == "rsp"))
+      .collect();
+   files.sort();
+   assert!(!files.is_empty(), "No response files in {}", dir.display());
+
+   let mut failures = Vec::new();
+   let mut counts = BTreeMap::new();
+   for path in &files {
+      let name = path.file_name().unwrap().to_string_lossy().into_owned();
+      for record in parse(&fs::read_to_string(path).unwrap()) {
+         let result = match record.curve.as_str() {
+            "P-256" => run_record(&Secp256r1::new(), &record),
+            curve if SKIPPED.contains(&curve) => continue,
+            curve => panic!("{} line {}: Unknown section {}", name, record.line, curve),
+         };
+         *counts.entry(record.curve.clone()).or_insert(0) += 1;
+         if let Err(mismatch) = result {
+            failures.push(format!("{} line {}: {}", name, record.line, mismatch));
+         }
+      }
+   }
+   assert!(counts.contains_key("P-256"), "No records of P-256");
+   assert!(failures.is_empty(), "{} mismatches:\n{}", failures.len(), failures.join("\n"));
+}
+
+/// Split a response file into records, which are separated by empty lines.
+/// Comments and lines outside of sections are ignored.
+fn parse(rsp: &str) -> Vec<Record> {
+   let mut records = Vec::new();
+   let mut curve = None;
+   let mut record: Option<Record> = None;
+
+   for (i, line) in rsp.lines().enumerate() {
+      let line = line.trim();
+      if line.is_empty() || line.starts_with('[') {
+         records.extend(record.take());
+      }
+      if line.starts_with('[') {
+         let section = line.trim_matches(|c| c == '[' || c == ']');
+         curve = Some(section.split(',').next().unwrap().to_string());
+         continue;
+      }
+      let (name, value) = match (line.starts_with('#'), line.find('=')) {
+         (false, Some(equals)) => (line[..equals].trim(), line[equals + 1..].trim()),
+         _ => continue,
+      };
+      if let Some(ref curve) = curve {
+         record
+            .get_or_insert_with(|| Record {
+               curve: curve.clone(),
+               line: i + 1,
+               fields: Vec::new(),
+            })
+            .fields
+            .push((name.to_string(), value.to_string()));
+      }
+   }
+   records.extend(record);
+   records
+}
+
+fn run_record<C>(curve: &C, record: &Record) -> Result<(), String>
+where
+   C: ECCurveCalculation<JacobianCoordinates> + ECCurvePoint<AffineCoordinates>,
+{
+   let (x, y) = match (record.get("Qx"), record.get("Qy")) {
+      (Some(x), Some(y)) => (x, y),
+      _ => panic!("Line {}: Neither a key pair nor a public key", record.line),
+   };
+   let point_q = parse_public_key(curve, x, y);
+
+   if let Some(result) = record.get("Result") {
+      let expected = match result.chars().next() {
+         Some('P') => true,
+         Some('F') => false,
+         _ => panic!("Line {}: Invalid result {}", record.line, result),
+      };
+      return match point_q {
+         Some(_) if !expected => Err(format!("Q is accepted, expected {}", result)),
+         None if expected => Err("Q is rejected, expected P".to_string()),
+         _ => Ok(()),
+      };
+   }
+
+   let d = record.hex("d").unwrap_or_else(|| panic!("Line {}: No d", record.line));
+   match point_q {
+      Some(point_q) => check_multiplication(curve, &d, &point_q),
+      None => Err("Q is not a valid public key".to_string()),
+   }
+}
+
+/// Parse `Qx`, `Qy` as an uncompressed SEC 1 public key and validate it as
+/// in SEC 1 3.2.2.1: the coordinates have to be below `p`, and the point
+/// has to be on the curve and in the subgroup.
+fn parse_public_key<C>(curve: &C, x: &str, y: &str) -> Option<AffineCoordinates>
+where
+   C: ECCurveCalculation<JacobianCoordinates> + ECCurvePoint<AffineCoordinates>,
+{
+   let p = curve.p();
+   let len = (p.bits() as usize).div_ceil(8) * 2;
+   let point = match curve.parse_public_key(format!("04{:0>len$}{:0>len$}", x, y, len = len)) {
+      Ok(ECCValue::Finite { x, y }) => AffineCoordinates { x, y },
+      _ => return None,
+   };
+   if point.x >= p || point.y >= p || !curve.point_is_on_curve(&point) {
+      return None;
+   }
+   let point_q = JacobianCoordinates::convert_from(&point, curve).unwrap_finite();
+   if curve.is_in_subgroup(&point_q) {
+      Some(point)
+   } else {
+      None
+   }
+}
+
+/// `dG = Q` in affine coordinates and every projective coordinate system.
+fn check_multiplication<C>(
+   curve: &C,
+   d: &BigInt,
+   point_q: &AffineCoordinates,
+) -> Result<(), String>
+where
+   C: ECCurveCalculation<JacobianCoordinates> + ECCurvePoint<AffineCoordinates>,
+{
+   let point_g = curve.base_point();
+   let affine = PointCalculation::point_multipication(curve, &point_g, d.clone());
+   let results = vec![
+      ("AffineCoordinates", ECCValue::from(affine)),
+      ("JacobianCoordinates", multiply::<_, JacobianCoordinates>(curve, d)),
+      ("StandardProjectiveCoordinates", multiply::<_, StandardProjectiveCoordinates>(curve, d)),
+      ("ChudnovskyJacobianCoordinates", multiply::<_, ChudnovskyJacobianCoordinates>(curve, d)),
+      ("ModifiedJacobianCoordinates", multiply::<_, ModifiedJacobianCoordinates>(curve, d)),
+      ("CompleteProjectiveCoordinates", multiply::<_, CompleteProjectiveCoordinates>(curve, d)),
+   ];
+
+   let expected = ECCValue::from(point_q.clone());
+   for (coordinates, result) in results {
+      if result != expected {
+         return Err(format!("dG in {} is {:?}", coordinates, result));
+      }
+   }
+   Ok(())
+}
+
+fn multiply<C, P>(curve: &C, d: &BigInt) -> ECCValue
+where
+   C: ECCurve,
+   P: PointCalculation<C, Output = P> + PointFrom<AffineCoordinates>,
+   AffineCoordinates: PointFrom<P>,
+{
+   let point_g = P::convert_from(&curve.base_point(), curve).unwrap_finite();
+   let point_r = P::point_multipication(curve, &point_g, d.clone());
+   ECCValue::from(AffineCoordinates::convert_from(&point_r, curve))
+}
+
+/// The `Result` of `PKV.rsp` records is checked both ways. No `PKV.rsp` is
+/// checked in, so these records are made up from the base point.
+#[test]
+fn pkv_results_are_checked() {
+   let curve = Secp256r1::new();
+   let point_g = curve.base_point();
+   let (x, y) = (format!("{:064x}", point_g.x), format!("{:064x}", point_g.y));
+   let rsp = format!(
+      "[P-256]\n\n\
+       Qx = {x}\nQy = {y}\nResult = P\n\n\
+       Qx = {x}\nQy = {off_curve:064x}\nResult = F (2 - Point not on curve)\n\n\
+       Qx = {too_long:x}\nQy = {y}\nResult = F (1 - Q_x or Q_y out of range)\n\n\
+       Qx = {x}\nQy = {y}\nResult = F (2 - Point not on curve)\n",
+      x = x,
+      y = y,
+      off_curve = &point_g.y + 1,
+      too_long = &point_g.x + curve.p(),
+   );
+
+   let results: Vec<bool> =
+      parse(&rsp).iter().map(|record| run_record(&curve, record).is_ok()).collect();
+   assert_eq!(results, vec![true, true, true, false]);
+}
@@ -0,0 +1,114 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn ecc_calc(args: &[&str]) -> Output {
+   Command::new(env!("CARGO_BIN_EXE_ecc_calc"))
+      .args(args)
+      .output()
+      .unwrap()
+}
+
+fn stdout(args: &[&str]) -> String {
+   let output = ecc_calc(args);
+   assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+   String::from_utf8(output.stdout).unwrap().trim_end().to_string()
+}
+
+const G_SECP256K1: &str = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+                           483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+const G2_SECP256K1: &str = "04c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5\
+                            1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a";
+
+#[test]
+fn point_arithmetic() {
+   assert_eq!(stdout(&["mul", "G", "2"]), G2_SECP256K1);
+   assert_eq!(stdout(&["mul", G_SECP256K1, "0x2"]), G2_SECP256K1);
+   assert_eq!(stdout(&["double", "G"]), G2_SECP256K1);
+   assert_eq!(stdout(&["add", "G", "G"]), G2_SECP256K1);
+   assert_eq!(
+      stdout(&["--curve", "secp256r1", "mul", "G", "0x1234"]),
+      "04ed5784a75391dc43adcd42dbc4c938e80690c75b3f4309049d5076692f8dafe9\
+       7ed5e8b3d94dd41f677d0e25f6ea5b332495bbdb74923eabbe9e7d2c1d09a08a"
+   );
+   // nG is the point at infinity.
+   assert_eq!(
+      stdout(&[
+         "mul",
+         "G",
+         "115792089237316195423570985008687907852837564279074904382605163141518161494337",
+      ]),
+      format!("00{:064x}", 0)
+   );
+}
+
+#[test]
+fn encodings_and_coordinates() {
+   let compressed = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+   assert_eq!(stdout(&["compress", "G"]), compressed);
+   assert_eq!(stdout(&["decompress", compressed]), G_SECP256K1);
+   assert_eq!(stdout(&["on-curve", "G"]), "true");
+   assert_eq!(stdout(&["on-curve", "1,2"]), "false");
+   assert_eq!(
+      stdout(&["convert", "--to", "jacobian", "G"]),
+      "JacobianCoordinates(x: 79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798, \
+       y: 483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8, z: 1)"
+   );
+}
+
+#[test]
+fn invalid_input_fails() {
+   for args in [
+      &["mul", "G"][..],
+      &["mul", "G", "0xzz"],
+      &["decompress", "02zz"],
+      &["--curve", "secp384r1", "double", "G"],
+      &["convert", "--to", "affine", "00"],
+      &["frobnicate"],
+   ]
+   .iter()
+   {
+      let output = ecc_calc(args);
+      assert_eq!(output.status.code(), Some(2));
+      assert!(output.stdout.is_empty());
+   }
+}
+
+#[test]
+fn repl_session() {
+   let mut child = Command::new(env!("CARGO_BIN_EXE_ecc_calc"))
+      .arg("repl")
+      .stdin(Stdio::piped())
+      .stdout(Stdio::piped())
+      .spawn()
+      .unwrap();
+   child
+      .stdin
+      .take()
+      .unwrap()
+      .write_all(
+         b"P = G * 2\n\
+           P - G as uncompressed\n\
+           Q = -(3 * 4 - 11) * G + P\n\
+           Q as compressed\n\
+           P + 1\n\
+           base dec\n\
+           2 * (3 + 4)\n\
+           curve secp256r1\n\
+           P\n\
+           quit\n\
+           G\n",
+      )
+      .unwrap();
+   let output = child.wait_with_output().unwrap();
+   assert!(output.status.success());
+   assert_eq!(
+      String::from_utf8(output.stdout).unwrap().lines().collect::<Vec<_>>(),
+      vec![
+         G_SECP256K1,
+         "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+         "error: Points and scalars can not be added",
+         "14",
+         "error: Unknown name: P",
+      ]
+   );
+}
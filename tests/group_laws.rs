@@ -0,0 +1,302 @@
+//! Property tests of the group laws, for every projective point
+//! representation on every curve, with random scalars and points `kG`.
+//!
+//! Scalars are drawn from `[0, n)` and, now and then, the edge cases
+//! `0, 1, n - 1, n, n + 1`, so the point at infinity, `P + P` through
+//! `point_addition` and `P + (-P)` come up as well.
+//!
+//! The big integer arithmetic is slow, so every property runs `CASES` cases
+//! unless `PROPTEST_CASES` asks for more.
+
+use num::bigint::Sign;
+use num::{BigInt, One, Zero};
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+
+use ecc_calc::ecc::prime::curves::{ECCurve, ECCurveCalculation, Secp256k1, Secp256r1};
+use ecc_calc::ecc::prime::points::{
+   AffineCoordinates,
+   ChudnovskyJacobianCoordinates,
+   CompleteProjectiveCoordinates,
+   JacobianCoordinates,
+   ModifiedJacobianCoordinates,
+   PointCalculation,
+   PointFraction,
+   PointFrom,
+   StandardProjectiveCoordinates,
+};
+use ecc_calc::ecc::prime::ECCurvePoint;
+use ecc_calc::ecc::ECCValueRes;
+
+const CASES: u32 = 16;
+
+fn config() -> ProptestConfig {
+   let cases = std::env::var("PROPTEST_CASES").ok().and_then(|cases| cases.parse().ok());
+   ProptestConfig::with_cases(cases.unwrap_or(CASES))
+}
+
+/// A scalar in `[0, n)`, or one of `0, 1, n - 1, n, n + 1`.
+fn scalar<C: ECCurve>() -> impl Strategy<Value = BigInt> {
+   let n = C::new().n();
+   let edge_cases = vec![
+      BigInt::zero(),
+      BigInt::one(),
+      &n - BigInt::one(),
+      n.clone(),
+      &n + BigInt::one(),
+   ];
+   prop_oneof![
+      1 => prop::sample::select(edge_cases),
+      4 => prop::array::uniform32(any::<u8>())
+         .prop_map(move |bytes| BigInt::from_bytes_be(Sign::Plus, &bytes) % &n),
+   ]
+}
+
+/// The points the laws are checked with need all of these.
+trait GroupPoint<C: ECCurve>:
+   PointCalculation<C, Output = Self> + PointFraction + PointFrom<AffineCoordinates>
+{
+}
+
+impl<C, P> GroupPoint<C> for P
+where
+   C: ECCurve,
+   P: PointCalculation<C, Output = P> + PointFraction + PointFrom<AffineCoordinates>,
+{
+}
+
+/// `k * G` in the representation `P`.
+fn multiple<C, P>(curve: &C, k: &BigInt) -> P
+where
+   C: ECCurveCalculation<P>,
+   P: GroupPoint<C>,
+{
+   let point_g = P::convert_from(&curve.base_point(), curve).unwrap_finite();
+   curve.multipy_point(&point_g, k.clone())
+}
+
+fn check_equal<C, P, Q>(curve: &C, law: &str, former: &P, latter: &Q) -> Result<(), TestCaseError>
+where
+   C: ECCurvePoint<P>,
+   P: PointFraction,
+   Q: PointFraction,
+{
+   prop_assert!(
+      curve.points_equal(former, latter),
+      "{} on {}: {:x} != {:x}",
+      law,
+      curve.name(),
+      former,
+      latter
+   );
+   Ok(())
+}
+
+fn associativity<C, P>(curve: &C, a: &BigInt, b: &BigInt, c: &BigInt) -> Result<(), TestCaseError>
+where
+   C: ECCurveCalculation<P> + ECCurvePoint<P>,
+   P: GroupPoint<C>,
+{
+   let (point_p, point_q, point_r) =
+      (multiple::<C, P>(curve, a), multiple(curve, b), multiple(curve, c));
+   let former = curve.add_points(&curve.add_points(&point_p, &point_q), &point_r);
+   let latter = curve.add_points(&point_p, &curve.add_points(&point_q, &point_r));
+   check_equal(curve, "(P + Q) + R = P + (Q + R)", &former, &latter)
+}
+
+fn commutativity<C, P>(curve: &C, a: &BigInt, b: &BigInt) -> Result<(), TestCaseError>
+where
+   C: ECCurveCalculation<P> + ECCurvePoint<P>,
+   P: GroupPoint<C>,
+{
+   let (point_p, point_q) = (multiple::<C, P>(curve, a), multiple(curve, b));
+   let former = curve.add_points(&point_p, &point_q);
+   let latter = curve.add_points(&point_q, &point_p);
+   check_equal(curve, "P + Q = Q + P", &former, &latter)
+}
+
+fn identity<C, P>(curve: &C, a: &BigInt) -> Result<(), TestCaseError>
+where
+   C: ECCurveCalculation<P> + ECCurvePoint<P>,
+   P: GroupPoint<C>,
+{
+   let point_p = multiple::<C, P>(curve, a);
+   let point_o = multiple::<C, P>(curve, &BigInt::zero());
+   check_equal(curve, "P + O = P", &curve.add_points(&point_p, &point_o), &point_p)?;
+   check_equal(curve, "O + P = P", &curve.add_points(&point_o, &point_p), &point_p)
+}
+
+fn inverses<C, P>(curve: &C, a: &BigInt) -> Result<(), TestCaseError>
+where
+   C: ECCurveCalculation<P> + ECCurvePoint<P>,
+   P: GroupPoint<C>,
+{
+   let point_p = multiple::<C, P>(curve, a);
+   let point_o = multiple::<C, P>(curve, &BigInt::zero());
+   let negated = curve.negate_point(&point_p);
+   check_equal(curve, "P + (-P) = O", &curve.add_points(&point_p, &negated), &point_o)?;
+   check_equal(curve, "P - P = O", &curve.subtract_points(&point_p, &point_p), &point_o)?;
+   // `P + P` has to be detected by `point_addition` and doubled.
+   let doubled = curve.double_point(&point_p);
+   check_equal(curve, "P + P = 2P", &curve.add_points(&point_p, &point_p), &doubled)
+}
+
+fn distributivity<C, P>(curve: &C, a: &BigInt, b: &BigInt, k: &BigInt) -> Result<(), TestCaseError>
+where
+   C: ECCurveCalculation<P> + ECCurvePoint<P>,
+   P: GroupPoint<C>,
+{
+   let point_p = multiple::<C, P>(curve, k);
+   let former = curve.multipy_point(&point_p, a + b);
+   let latter = curve.add_points(
+      &curve.multipy_point(&point_p, a.clone()),
+      &curve.multipy_point(&point_p, b.clone()),
+   );
+   check_equal(curve, "(a + b)P = aP + bP", &former, &latter)
+}
+
+fn compatibility<C, P>(curve: &C, a: &BigInt, b: &BigInt, k: &BigInt) -> Result<(), TestCaseError>
+where
+   C: ECCurveCalculation<P> + ECCurvePoint<P>,
+   P: GroupPoint<C>,
+{
+   let point_p = multiple::<C, P>(curve, k);
+   let former = curve.multipy_point(&curve.multipy_point(&point_p, b.clone()), a.clone());
+   let latter = curve.multipy_point(&point_p, a * b);
+   check_equal(curve, "a(bP) = (ab)P", &former, &latter)
+}
+
+/// `kP` for the edge cases of `k`, unreduced through `point_multipication`
+/// as well as through `multipy_point`.
+fn edge_scalars<C, P>(curve: &C, a: &BigInt) -> Result<(), TestCaseError>
+where
+   C: ECCurveCalculation<P> + ECCurvePoint<P>,
+   P: GroupPoint<C>,
+{
+   let n = curve.n();
+   let point_p = multiple::<C, P>(curve, a);
+   let point_o = multiple::<C, P>(curve, &BigInt::zero());
+   let negated = curve.negate_point(&point_p);
+   let cases = vec![
+      ("0P = O", BigInt::zero(), &point_o),
+      ("1P = P", BigInt::one(), &point_p),
+      ("(n - 1)P = -P", &n - BigInt::one(), &negated),
+      ("nP = O", n.clone(), &point_o),
+      ("(n + 1)P = P", &n + BigInt::one(), &point_p),
+   ];
+   for (law, k, expected) in cases {
+      check_equal(curve, law, &curve.multipy_point(&point_p, k.clone()), expected)?;
+      check_equal(curve, law, &P::point_multipication(curve, &point_p, k), expected)?;
+   }
+   Ok(())
+}
+
+/// Jacobian and standard projective coordinates agree after conversion, and
+/// both agree with affine coordinates.
+fn agreement<C>(curve: &C, a: &BigInt, b: &BigInt) -> Result<(), TestCaseError>
+where
+   C: ECCurveCalculation<JacobianCoordinates>
+      + ECCurveCalculation<StandardProjectiveCoordinates>
+      + ECCurveCalculation<AffineCoordinates>
+      + ECCurvePoint<JacobianCoordinates>
+      + ECCurvePoint<StandardProjectiveCoordinates>,
+{
+   let jacobian: JacobianCoordinates = multiple(curve, a);
+   let projective: StandardProjectiveCoordinates = multiple(curve, a);
+   check_equal(curve, "Jacobian aG = projective aG", &jacobian, &projective)?;
+
+   let jacobian = curve.add_points(&jacobian, &multiple(curve, b));
+   let projective = curve.add_points(&projective, &multiple(curve, b));
+   let converted = StandardProjectiveCoordinates::convert_from(&jacobian, curve);
+   let back = JacobianCoordinates::convert_from(&projective, curve);
+   match (converted, back) {
+      (ECCValueRes::Finite(converted), ECCValueRes::Finite(back)) => {
+         check_equal(curve, "converted Jacobian aG + bG", &converted, &projective)?;
+         check_equal(curve, "converted projective aG + bG", &jacobian, &back)?;
+      },
+      (ECCValueRes::Infinity, ECCValueRes::Infinity) => {},
+      _ => return Err(TestCaseError::fail("Only one of aG + bG is infinity")),
+   }
+
+   let affine = curve.multipy_point(&curve.base_point(), a + b);
+   prop_assert_eq!(AffineCoordinates::convert_from(&jacobian, curve), affine);
+   Ok(())
+}
+
+/// Run `check` for every projective representation.
+macro_rules! every_representation {
+   ($check:ident($curve:expr, $($arg:expr),*)) => {{
+      $check::<_, JacobianCoordinates>($curve, $($arg),*)?;
+      $check::<_, StandardProjectiveCoordinates>($curve, $($arg),*)?;
+      $check::<_, ChudnovskyJacobianCoordinates>($curve, $($arg),*)?;
+      $check::<_, ModifiedJacobianCoordinates>($curve, $($arg),*)?;
+      $check::<_, CompleteProjectiveCoordinates>($curve, $($arg),*)?;
+   }};
+}
+
+macro_rules! group_laws {
+   ($name:ident, $curve:ident) => {
+      mod $name {
+         use super::*;
+
+         proptest! {
+            #![proptest_config(config())]
+
+            #[test]
+            fn is_associative(
+               a in scalar::<$curve>(),
+               b in scalar::<$curve>(),
+               c in scalar::<$curve>(),
+            ) {
+               every_representation!(associativity(&$curve::new(), &a, &b, &c));
+            }
+
+            #[test]
+            fn is_commutative(a in scalar::<$curve>(), b in scalar::<$curve>()) {
+               every_representation!(commutativity(&$curve::new(), &a, &b));
+            }
+
+            #[test]
+            fn has_identity(a in scalar::<$curve>()) {
+               every_representation!(identity(&$curve::new(), &a));
+            }
+
+            #[test]
+            fn has_inverses(a in scalar::<$curve>()) {
+               every_representation!(inverses(&$curve::new(), &a));
+            }
+
+            #[test]
+            fn is_distributive(
+               a in scalar::<$curve>(),
+               b in scalar::<$curve>(),
+               k in scalar::<$curve>(),
+            ) {
+               every_representation!(distributivity(&$curve::new(), &a, &b, &k));
+            }
+
+            #[test]
+            fn is_compatible(
+               a in scalar::<$curve>(),
+               b in scalar::<$curve>(),
+               k in scalar::<$curve>(),
+            ) {
+               every_representation!(compatibility(&$curve::new(), &a, &b, &k));
+            }
+
+            #[test]
+            fn edge_case_scalars(a in scalar::<$curve>()) {
+               every_representation!(edge_scalars(&$curve::new(), &a));
+            }
+
+            #[test]
+            fn representations_agree(a in scalar::<$curve>(), b in scalar::<$curve>()) {
+               agreement(&$curve::new(), &a, &b)?;
+            }
+         }
+      }
+   };
+}
+
+group_laws!(secp256k1, Secp256k1);
+group_laws!(secp256r1, Secp256r1);
@@ -0,0 +1,245 @@
+//! Runs the Project Wycheproof vectors in `tests/wycheproof` against the
+//! parsing, point validation and arithmetic of the crate.
+//!
+//! Every file listed in `FILES` has to be present and is run, by its schema,
+//! with or without the `_v1` suffix of the current Wycheproof layout:
+//!
+//! * `ecdsa_verify_schema.json`, `ecdsa_bitcoin_verify_schema.json`: DER
+//!   signatures of SHA-256 hashes, Bitcoin additionally requiring low `s`.
+//! * `ecdh_test_schema.json`, `ecdh_ecpoint_test_schema.json`: public keys
+//!   in `SubjectPublicKeyInfo` or SEC 1 encoding.
+//!
+//! Vectors with the result `acceptable` may go either way and are skipped.
+//! Mismatches are reported by file and `tcId`.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use num::bigint::Sign;
+use num::BigInt;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use ecc_calc::ecc::prime::curves::{ECCurve, Secp256k1, Secp256r1};
+use ecc_calc::ecc::prime::ecdsa::{Signature, ECDSA};
+use ecc_calc::ecc::prime::points::{AffineCoordinates, JacobianCoordinates, PointFrom};
+use ecc_calc::ecc::ECCValue;
+
+/// The vector files, every one of which has to be found and run.
+const FILES: &[&str] = &[
+   "ecdh_secp256k1_test.json",
+   "ecdh_secp256r1_ecpoint_test.json",
+   "ecdh_secp256r1_test.json",
+   "ecdsa_secp256k1_sha256_bitcoin_test.json",
+   "ecdsa_secp256k1_sha256_test.json",
+   "ecdsa_secp256r1_sha256_test.json",
+];
+
+/// `SEQUENCE`, `BIT STRING` and `OBJECT IDENTIFIER` tags.
+const SEQUENCE: u8 = 0x30;
+const BIT_STRING: u8 = 0x03;
+const OBJECT_IDENTIFIER: u8 = 0x06;
+
+/// id-ecPublicKey, RFC 5480
+const EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+const SECP256K1_OID: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x0a];
+const SECP256R1_OID: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+
+#[test]
+fn wycheproof_test_vectors() {
+   let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("wycheproof");
+   let found: BTreeSet<String> = fs::read_dir(&dir)
+      .unwrap()
+      .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+      .filter(|name| name.ends_with("_test.json"))
+      .collect();
+   let expected: BTreeSet<String> = FILES.iter().map(|name| name.to_string()).collect();
+   assert_eq!(found, expected, "The vector files in {}", dir.display());
+
+   let mut failures = Vec::new();
+   for name in FILES {
+      let vectors = fs::read_to_string(dir.join(name)).unwrap();
+      let (run, mismatches) = run_file(&serde_json::from_str(&vectors).unwrap());
+      assert!(run > 0, "No vectors run from {}", name);
+      for mismatch in mismatches {
+         failures.push(format!("{}: {}", name, mismatch));
+      }
+   }
+   assert!(failures.is_empty(), "{} mismatches:\n{}", failures.len(), failures.join("\n"));
+}
+
+/// Run every vector of a JSON file and return how many were run, with the
+/// mismatches.
+fn run_file(vectors: &Value) -> (usize, Vec<String>) {
+   let schema = vectors["schema"].as_str().unwrap().replace("_v1.json", ".json");
+   let schema = schema.as_str();
+   let mut run = 0;
+   let mut mismatches = Vec::new();
+   for group in vectors["testGroups"].as_array().unwrap() {
+      let curve = match schema {
+         "ecdsa_verify_schema.json" | "ecdsa_bitcoin_verify_schema.json" => {
+            assert_eq!(group["sha"], "SHA-256", "Only SHA-256 is supported");
+            public_key(group)["curve"].as_str().unwrap()
+         },
+         "ecdh_test_schema.json" | "ecdh_ecpoint_test_schema.json" => {
+            group["curve"].as_str().unwrap()
+         },
+         other => panic!("Unsupported schema {}", other),
+      };
+
+      for test in group["tests"].as_array().unwrap() {
+         let expected = match test["result"].as_str().unwrap() {
+            "valid" => true,
+            "invalid" => false,
+            _ => continue,
+         };
+         let passed = match curve {
+            "secp256k1" => run_test(&Secp256k1::new(), SECP256K1_OID, schema, group, test),
+            "secp256r1" => run_test(&Secp256r1::new(), SECP256R1_OID, schema, group, test),
+            other => panic!("Unsupported curve {}", other),
+         };
+         run += 1;
+         if passed != expected {
+            mismatches.push(format!(
+               "tcId {} ({}) should be {}",
+               test["tcId"],
+               test["comment"].as_str().unwrap_or(""),
+               test["result"].as_str().unwrap()
+            ));
+         }
+      }
+   }
+   (run, mismatches)
+}
+
+/// Whether the vector passes on `curve`, which is named `oid` in DER.
+fn run_test<C>(curve: &C, oid: &[u8], schema: &str, group: &Value, test: &Value) -> bool
+where
+   C: ECDSA,
+{
+   match schema {
+      "ecdsa_verify_schema.json" | "ecdsa_bitcoin_verify_schema.json" => {
+         let encoded = hex(&public_key(group)["uncompressed"]);
+         let public_key = match parse_point(curve, &encoded) {
+            Some(public_key) => public_key,
+            None => return false,
+         };
+         let bitcoin = schema == "ecdsa_bitcoin_verify_schema.json";
+         verify_der(curve, &public_key, &hex(&test["msg"]), &hex(&test["sig"]), bitcoin)
+      },
+      "ecdh_test_schema.json" | "ecdh_ecpoint_test_schema.json" => {
+         let public = hex(&test["public"]);
+         let point = if group["encoding"] == "ecpoint" {
+            Some(public)
+         } else {
+            public_key_info(&public, oid)
+         };
+         let public_key = match point.and_then(|point| parse_point(curve, &point)) {
+            Some(public_key) => public_key,
+            None => return false,
+         };
+         // There is no ECDH API to validate the key, so it is done here.
+         let point_q = JacobianCoordinates::convert_from(&public_key, curve).unwrap_finite();
+         let p = curve.p();
+         let in_field = public_key.x < p && public_key.y < p;
+         if !in_field || !curve.point_is_on_curve(&public_key) || !curve.is_in_subgroup(&point_q) {
+            return false;
+         }
+
+         let d = BigInt::from_bytes_be(Sign::Plus, &hex(&test["private"]));
+         let shared = AffineCoordinates::convert_from(&curve.multipy_point(&point_q, d), curve);
+         match ECCValue::from(shared) {
+            ECCValue::Finite { x, .. } => {
+               x == BigInt::from_bytes_be(Sign::Plus, &hex(&test["shared"]))
+            },
+            ECCValue::Infinity => false,
+         }
+      },
+      _ => unreachable!(),
+   }
+}
+
+/// Whether `der` is a valid signature of SHA-256(`msg`), with low `s` if
+/// `bitcoin` is set. `ECDSA::verify` validates the public key.
+fn verify_der<C: ECDSA>(
+   curve: &C,
+   public_key: &AffineCoordinates,
+   msg: &[u8],
+   der: &[u8],
+   bitcoin: bool,
+) -> bool {
+   let signature = match Signature::<C>::from_der(der) {
+      Ok(signature) => signature,
+      Err(_) => return false,
+   };
+   // Bitcoin takes `n - s` as a malleated signature.
+   if bitcoin && !signature.is_low_s() {
+      return false;
+   }
+   curve.verify(public_key, &Sha256::digest(msg), &signature).is_ok()
+}
+
+/// The public key of an ECDSA group, `publicKey` since version 1 of the
+/// schemas, `key` before.
+fn public_key(group: &Value) -> &Value {
+   if group["publicKey"].is_null() {
+      &group["key"]
+   } else {
+      &group["publicKey"]
+   }
+}
+
+/// Parse a SEC 1 encoded point, which is not validated yet.
+fn parse_point<C: ECCurve>(curve: &C, encoded: &[u8]) -> Option<AffineCoordinates> {
+   let encoded: String = encoded.iter().map(|byte| format!("{:02x}", byte)).collect();
+   match curve.parse_public_key(encoded) {
+      Ok(ECCValue::Finite { x, y }) => Some(AffineCoordinates { x, y }),
+      _ => None,
+   }
+}
+
+/// Return the point of a DER `SubjectPublicKeyInfo` with the named curve
+/// `oid`, RFC 5480. Explicit curve parameters are not supported.
+fn public_key_info(der: &[u8], oid: &[u8]) -> Option<Vec<u8>> {
+   let (info, rest) = tlv(der, SEQUENCE)?;
+   let (algorithm, info) = tlv(info, SEQUENCE)?;
+   let (point, info) = tlv(info, BIT_STRING)?;
+   let (algorithm_oid, algorithm) = tlv(algorithm, OBJECT_IDENTIFIER)?;
+   let (curve_oid, algorithm) = tlv(algorithm, OBJECT_IDENTIFIER)?;
+
+   let trailing = !rest.is_empty() || !info.is_empty() || !algorithm.is_empty();
+   if trailing || algorithm_oid != EC_PUBLIC_KEY || curve_oid != oid {
+      return None;
+   }
+   // No unused bits.
+   match *point {
+      [0, ref point @ ..] => Some(point.to_vec()),
+      _ => None,
+   }
+}
+
+/// Split a DER element with `tag` off `der`, returning its content and the
+/// bytes following it. Lengths have to be minimally encoded.
+fn tlv(der: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+   let (len, rest) = match *der {
+      [t, len, ref rest @ ..] if t == tag && len < 0x80 => (len as usize, rest),
+      [t, 0x81, len, ref rest @ ..] if t == tag && len >= 0x80 => (len as usize, rest),
+      [t, 0x82, high, low, ref rest @ ..] if t == tag && high != 0 => {
+         ((high as usize) << 8 | low as usize, rest)
+      },
+      _ => return None,
+   };
+   if rest.len() < len {
+      return None;
+   }
+   Some(rest.split_at(len))
+}
+
+fn hex(value: &Value) -> Vec<u8> {
+   let hex = value.as_str().unwrap();
+   (0..hex.len())
+      .step_by(2)
+      .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+      .collect()
+}